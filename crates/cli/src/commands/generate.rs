@@ -0,0 +1,366 @@
+use crate::files::load_schemas;
+use clap::{Args, ValueEnum};
+use miette::{IntoDiagnostic, miette};
+use schematic::schema::*;
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+
+#[derive(Clone, Copy, ValueEnum)]
+pub enum RendererKind {
+    /// Environment variable documentation.
+    EnvVars,
+    /// JSON schema document.
+    JsonSchema,
+    /// JSON config template.
+    JsonTemplate,
+    /// JSON config template with comments.
+    JsoncTemplate,
+    /// Pkl config template.
+    PklTemplate,
+    /// TOML config template.
+    TomlTemplate,
+    /// TypeScript types.
+    #[value(name = "typescript")]
+    TypeScript,
+    /// YAML config template.
+    YamlTemplate,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+pub enum EnvVarsFormatArg {
+    Dotenv,
+    Markdown,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+pub enum EnumFormatArg {
+    Enum,
+    ValuedEnum,
+    Union,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+pub enum ObjectFormatArg {
+    Interface,
+    Type,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+pub enum PropertyFormatArg {
+    Required,
+    Optional,
+    OptionalUndefined,
+}
+
+#[derive(Args)]
+pub struct GenerateArgs {
+    /// File containing serialized schemas, as a map of names to schemas (JSON, TOML, or YAML).
+    #[arg(long, short)]
+    schemas: PathBuf,
+
+    /// Renderer to generate the output with.
+    #[arg(long, short)]
+    renderer: RendererKind,
+
+    /// File to write the output to. When not provided, writes to stdout.
+    #[arg(long, short)]
+    output: Option<PathBuf>,
+
+    /// Name of the schema to render as the root. Defaults to the last schema.
+    #[arg(long)]
+    root: Option<String>,
+
+    /// Verify the output file is up to date instead of writing it,
+    /// and fail if it is stale or missing.
+    #[arg(long, requires = "output")]
+    check: bool,
+
+    #[command(flatten)]
+    common: CommonArgs,
+
+    #[command(flatten)]
+    env_vars: EnvVarsArgs,
+
+    #[command(flatten)]
+    json_schema: JsonSchemaArgs,
+
+    #[command(flatten)]
+    template: TemplateArgs,
+
+    #[command(flatten)]
+    typescript: TypeScriptArgs,
+}
+
+#[derive(Args)]
+#[command(next_help_heading = "Common options")]
+struct CommonArgs {
+    /// Exclude field aliases from being rendered (JSON schema, TypeScript).
+    #[arg(long)]
+    exclude_aliases: bool,
+
+    /// Content to append to the bottom of the output (templates, env vars).
+    #[arg(long, default_value = "")]
+    footer: String,
+
+    /// Content to prepend to the top of the output (templates, env vars).
+    #[arg(long, default_value = "")]
+    header: String,
+
+    /// Character(s) to use for indentation (templates, TypeScript).
+    #[arg(long)]
+    indent: Option<String>,
+}
+
+#[derive(Args)]
+#[command(next_help_heading = "Env vars options")]
+struct EnvVarsArgs {
+    /// Format to render the variables as.
+    #[arg(long, value_enum, default_value = "markdown")]
+    env_format: EnvVarsFormatArg,
+
+    /// Include fields marked as hidden.
+    #[arg(long)]
+    include_hidden: bool,
+}
+
+#[derive(Args)]
+#[command(next_help_heading = "JSON schema options")]
+struct JsonSchemaArgs {
+    /// Allow newlines in descriptions, otherwise strips them.
+    #[arg(long)]
+    allow_newlines_in_description: bool,
+
+    /// Include a non-standard `markdownDescription` field.
+    #[arg(long)]
+    markdown_description: bool,
+
+    /// Do not mark non-optional struct fields as required.
+    #[arg(long)]
+    no_required_fields: bool,
+
+    /// Set the field's name as the `title` of each schema entry.
+    #[arg(long)]
+    set_field_name_as_title: bool,
+}
+
+#[derive(Args)]
+#[command(next_help_heading = "Template options")]
+struct TemplateArgs {
+    /// Comment out the field with the provided path. Can be repeated.
+    #[arg(long = "comment-field", value_name = "PATH")]
+    comment_fields: Vec<String>,
+
+    /// Prefix to use for comments.
+    #[arg(long)]
+    comment_prefix: Option<String>,
+
+    /// Expand the field with the provided path, instead of rendering an empty value.
+    /// Can be repeated.
+    #[arg(long = "expand-field", value_name = "PATH")]
+    expand_fields: Vec<String>,
+
+    /// Hide the field with the provided path. Can be repeated.
+    #[arg(long = "hide-field", value_name = "PATH")]
+    hide_fields: Vec<String>,
+
+    /// Do not render field comments.
+    #[arg(long)]
+    no_comments: bool,
+
+    /// Do not render a newline between each field.
+    #[arg(long)]
+    no_newline_between_fields: bool,
+
+    /// Only render the field with the provided path. Can be repeated.
+    #[arg(long = "only-field", value_name = "PATH")]
+    only_fields: Vec<String>,
+}
+
+#[derive(Args)]
+#[command(next_help_heading = "TypeScript options")]
+struct TypeScriptArgs {
+    /// Render a `const enum` instead of an `enum`.
+    #[arg(long)]
+    const_enum: bool,
+
+    /// Disable references and render all types inline recursively.
+    #[arg(long)]
+    disable_references: bool,
+
+    /// Format to render enums as.
+    #[arg(long, value_enum, default_value = "union")]
+    enum_format: EnumFormatArg,
+
+    /// Exclude the reference with the provided name from being exported. Can be repeated.
+    #[arg(long = "exclude-reference", value_name = "NAME")]
+    exclude_references: Vec<String>,
+
+    /// Import a type from a file, in the format of `path=Type`. Can be repeated.
+    #[arg(long = "external-type", value_name = "PATH=TYPE", value_parser = parse_external_type)]
+    external_types: Vec<(String, String)>,
+
+    /// Format to render objects as.
+    #[arg(long, value_enum, default_value = "interface")]
+    object_format: ObjectFormatArg,
+
+    /// Format to render object properties as.
+    #[arg(long, value_enum, default_value = "required")]
+    property_format: PropertyFormatArg,
+}
+
+fn parse_external_type(value: &str) -> Result<(String, String), String> {
+    match value.split_once('=') {
+        Some((path, ty)) if !path.is_empty() && !ty.is_empty() => {
+            Ok((path.to_owned(), ty.to_owned()))
+        }
+        _ => Err("expected the format of `path=Type`".into()),
+    }
+}
+
+impl GenerateArgs {
+    fn create_env_vars_options(&self) -> EnvVarsOptions {
+        EnvVarsOptions {
+            format: match self.env_vars.env_format {
+                EnvVarsFormatArg::Dotenv => EnvVarsFormat::Dotenv,
+                EnvVarsFormatArg::Markdown => EnvVarsFormat::Markdown,
+            },
+            footer: self.common.footer.clone(),
+            header: self.common.header.clone(),
+            include_hidden: self.env_vars.include_hidden,
+        }
+    }
+
+    fn create_json_schema_options(&self) -> JsonSchemaOptions {
+        JsonSchemaOptions {
+            allow_newlines_in_description: self.json_schema.allow_newlines_in_description,
+            exclude_aliases: self.common.exclude_aliases,
+            markdown_description: self.json_schema.markdown_description,
+            mark_struct_fields_required: !self.json_schema.no_required_fields,
+            set_field_name_as_title: self.json_schema.set_field_name_as_title,
+            ..JsonSchemaOptions::default()
+        }
+    }
+
+    fn create_template_options(&self) -> TemplateOptions {
+        let defaults = TemplateOptions::default();
+
+        TemplateOptions {
+            comments: !self.template.no_comments,
+            comment_fields: self.template.comment_fields.clone(),
+            comment_prefix: self
+                .template
+                .comment_prefix
+                .clone()
+                .unwrap_or(defaults.comment_prefix),
+            expand_fields: self.template.expand_fields.clone(),
+            footer: self.common.footer.clone(),
+            header: self.common.header.clone(),
+            hide_fields: self.template.hide_fields.clone(),
+            indent_char: self.common.indent.clone().unwrap_or(defaults.indent_char),
+            newline_between_fields: !self.template.no_newline_between_fields,
+            only_fields: self.template.only_fields.clone(),
+            ..defaults
+        }
+    }
+
+    fn create_typescript_options(&self) -> TypeScriptOptions {
+        let mut external_types: HashMap<String, Vec<String>> = HashMap::new();
+
+        for (path, ty) in &self.typescript.external_types {
+            external_types
+                .entry(path.to_owned())
+                .or_default()
+                .push(ty.to_owned());
+        }
+
+        TypeScriptOptions {
+            const_enum: self.typescript.const_enum,
+            disable_references: self.typescript.disable_references,
+            enum_format: match self.typescript.enum_format {
+                EnumFormatArg::Enum => EnumFormat::Enum,
+                EnumFormatArg::ValuedEnum => EnumFormat::ValuedEnum,
+                EnumFormatArg::Union => EnumFormat::Union,
+            },
+            exclude_aliases: self.common.exclude_aliases,
+            exclude_references: self.typescript.exclude_references.clone(),
+            external_types,
+            indent_char: self.common.indent.clone().unwrap_or_default(),
+            object_format: match self.typescript.object_format {
+                ObjectFormatArg::Interface => ObjectFormat::Interface,
+                ObjectFormatArg::Type => ObjectFormat::Type,
+            },
+            property_format: match self.typescript.property_format {
+                PropertyFormatArg::Required => PropertyFormat::Required,
+                PropertyFormatArg::Optional => PropertyFormat::Optional,
+                PropertyFormatArg::OptionalUndefined => PropertyFormat::OptionalUndefined,
+            },
+        }
+    }
+
+    fn render(&self, schemas: IndexMap<String, Schema>) -> RenderResult {
+        match self.renderer {
+            RendererKind::EnvVars => {
+                EnvVarsRenderer::new(self.create_env_vars_options()).render(schemas)
+            }
+            RendererKind::JsonSchema => {
+                JsonSchemaRenderer::new(self.create_json_schema_options()).render(schemas)
+            }
+            RendererKind::JsonTemplate => {
+                JsonTemplateRenderer::new(self.create_template_options()).render(schemas)
+            }
+            RendererKind::JsoncTemplate => {
+                JsoncTemplateRenderer::new(self.create_template_options()).render(schemas)
+            }
+            RendererKind::PklTemplate => {
+                PklTemplateRenderer::new(self.create_template_options()).render(schemas)
+            }
+            RendererKind::TomlTemplate => {
+                TomlTemplateRenderer::new(self.create_template_options()).render(schemas)
+            }
+            RendererKind::TypeScript => {
+                TypeScriptRenderer::new(self.create_typescript_options()).render(schemas)
+            }
+            RendererKind::YamlTemplate => {
+                YamlTemplateRenderer::new(self.create_template_options()).render(schemas)
+            }
+        }
+    }
+}
+
+pub fn generate(args: GenerateArgs) -> miette::Result<()> {
+    let schemas = load_schemas(&args.schemas, args.root.as_deref())?;
+
+    // Match the output of `SchemaGenerator.generate()`
+    let mut output = args.render(schemas)?;
+    output.push('\n');
+
+    let Some(output_file) = &args.output else {
+        print!("{output}");
+
+        return Ok(());
+    };
+
+    if args.check {
+        let current = fs::read_to_string(output_file).unwrap_or_default();
+
+        if current.replace("\r\n", "\n") != output {
+            return Err(miette!(
+                code = "schematic::generate::stale",
+                help = "Run the command without --check to regenerate it.",
+                "Generated file {} is out of date.",
+                output_file.display()
+            ));
+        }
+
+        return Ok(());
+    }
+
+    if let Some(parent) = output_file.parent() {
+        fs::create_dir_all(parent).into_diagnostic()?;
+    }
+
+    fs::write(output_file, output).into_diagnostic()?;
+
+    Ok(())
+}
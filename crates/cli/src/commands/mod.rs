@@ -0,0 +1,5 @@
+mod generate;
+mod validate;
+
+pub use generate::*;
+pub use validate::*;
@@ -0,0 +1,63 @@
+use crate::files::{load_schemas, parse_file};
+use clap::Args;
+use schematic::schema::{SchemaValidator, SchemaValidatorOptions};
+use schematic::{ConfigError, ValidatorError};
+use std::path::PathBuf;
+
+#[derive(Args)]
+pub struct ValidateArgs {
+    /// Configuration file to validate (JSON, TOML, or YAML).
+    file: PathBuf,
+
+    /// File containing serialized schemas, as a map of names to schemas (JSON, TOML, or YAML).
+    #[arg(long, short)]
+    schemas: PathBuf,
+
+    /// Name of the schema to validate against. Defaults to the last schema.
+    #[arg(long)]
+    root: Option<String>,
+
+    /// Allow fields that are not defined in the schema.
+    #[arg(long)]
+    allow_unknown_fields: bool,
+
+    /// Require all non-optional struct fields to be set, even those with a default.
+    #[arg(long)]
+    require_fields: bool,
+}
+
+pub fn validate(args: ValidateArgs) -> miette::Result<()> {
+    let schemas = load_schemas(&args.schemas, args.root.as_deref())?;
+    let value: serde_json::Value = parse_file(&args.file)?;
+
+    let validator = SchemaValidator::with_options(
+        &schemas,
+        SchemaValidatorOptions {
+            allow_unknown_fields: args.allow_unknown_fields,
+            // Settings are optional when loading, as defaults are applied
+            mark_struct_fields_required: args.require_fields,
+        },
+    );
+
+    // The root is always last after loading
+    let (_, root) = schemas.last().expect("at least 1 schema");
+    let (warnings, errors): (Vec<_>, Vec<_>) = validator
+        .validate(root, &value)
+        .into_iter()
+        .partition(|error| error.is_warning());
+
+    for warning in warnings {
+        eprintln!("{warning}");
+    }
+
+    if errors.is_empty() {
+        return Ok(());
+    }
+
+    Err(ConfigError::Validator {
+        location: args.file.to_string_lossy().into_owned(),
+        error: Box::new(ValidatorError { errors }),
+        help: None,
+    }
+    .into())
+}
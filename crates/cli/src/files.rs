@@ -0,0 +1,55 @@
+use miette::{IntoDiagnostic, WrapErr, miette};
+use schematic::schema::{IndexMap, Schema};
+use schematic::{JsonFormat, Source, SourceFormat, TomlFormat, YamlFormat};
+use serde::de::DeserializeOwned;
+use std::fs;
+use std::path::Path;
+
+/// Parse a JSON, TOML, or YAML file into the provided type,
+/// using the same formats as the config loader.
+pub fn parse_file<T: DeserializeOwned>(path: &Path) -> miette::Result<T> {
+    let source = Source::file(path, true)?;
+    let content = fs::read_to_string(path)
+        .into_diagnostic()
+        .wrap_err_with(|| format!("Failed to read {}.", path.display()))?;
+
+    let formats: [Box<dyn SourceFormat<T>>; 3] = [
+        Box::new(JsonFormat::default()),
+        Box::new(TomlFormat::default()),
+        Box::new(YamlFormat::default()),
+    ];
+
+    for format in formats {
+        if format.should_parse(&source) {
+            return Ok(format.parse(&source, &content, None)?);
+        }
+    }
+
+    Err(miette!(
+        "Unsupported format for {}, expected a JSON, TOML, or YAML file.",
+        path.display()
+    ))
+}
+
+/// Load serialized schemas from a file. If a root name is provided, the schema
+/// is moved to the end of the map, as renderers treat the last schema as the root.
+pub fn load_schemas(path: &Path, root: Option<&str>) -> miette::Result<IndexMap<String, Schema>> {
+    let mut schemas: IndexMap<String, Schema> = parse_file(path)?;
+
+    if schemas.is_empty() {
+        return Err(miette!("No schemas found in {}.", path.display()));
+    }
+
+    if let Some(name) = root {
+        let Some(schema) = schemas.shift_remove(name) else {
+            return Err(miette!(
+                "Unknown root schema {name}, available schemas: {}.",
+                schemas.keys().cloned().collect::<Vec<_>>().join(", ")
+            ));
+        };
+
+        schemas.insert(name.to_owned(), schema);
+    }
+
+    Ok(schemas)
+}
@@ -0,0 +1,30 @@
+mod commands;
+mod files;
+
+use clap::{Parser, Subcommand};
+use commands::{GenerateArgs, ValidateArgs};
+
+/// Render and validate schemas that were serialized with the
+/// `schema_serde` feature of schematic.
+#[derive(Parser)]
+#[command(name = "schematic", version, about, long_about = None)]
+struct Cli {
+    #[command(subcommand)]
+    command: Commands,
+}
+
+#[derive(Subcommand)]
+enum Commands {
+    /// Render schemas with a built-in renderer, and write the output to a file.
+    Generate(Box<GenerateArgs>),
+
+    /// Validate a configuration file against a schema.
+    Validate(ValidateArgs),
+}
+
+fn main() -> miette::Result<()> {
+    match Cli::parse().command {
+        Commands::Generate(args) => commands::generate(*args),
+        Commands::Validate(args) => commands::validate(args),
+    }
+}
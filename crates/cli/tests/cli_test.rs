@@ -0,0 +1,384 @@
+#![allow(dead_code)]
+
+use schematic::schema::*;
+use schematic::*;
+use starbase_sandbox::{Sandbox, SandboxAssert, create_empty_sandbox};
+use std::fs;
+
+derive_enum!(
+    #[derive(ConfigEnum, Default)]
+    pub enum LogLevel {
+        #[default]
+        Info,
+        Debug,
+    }
+);
+
+#[derive(Config)]
+pub struct ServerConfig {
+    /// Host name to bind to.
+    host: String,
+    #[setting(default = 8080, validate(range(min = 1)))]
+    port: u16,
+}
+
+#[derive(Config)]
+pub struct AppConfig {
+    /// Name of the application.
+    #[setting(env = "APP_NAME")]
+    name: String,
+    level: LogLevel,
+    #[setting(nested)]
+    server: ServerConfig,
+}
+
+fn create_generator() -> SchemaGenerator {
+    let mut generator = SchemaGenerator::default();
+    generator.add::<AppConfig>();
+    generator
+}
+
+fn create_schemas_sandbox() -> Sandbox {
+    let sandbox = create_empty_sandbox();
+
+    sandbox.create_file(
+        "schemas.json",
+        serde_json::to_string_pretty(&create_generator().schemas).unwrap(),
+    );
+
+    sandbox
+}
+
+fn run_cli<'s>(sandbox: &'s Sandbox, args: &[&str]) -> SandboxAssert<'s> {
+    sandbox.run_bin_with_name("schematic", |cmd| {
+        cmd.args(args);
+    })
+}
+
+/// Run the CLI, assert it succeeded, and return stdout.
+fn run_success(sandbox: &Sandbox, args: &[&str]) -> String {
+    let assert = run_cli(sandbox, args);
+    let stdout = assert.stdout();
+
+    assert.success();
+    stdout
+}
+
+/// Run the CLI, assert it failed, and return stderr.
+fn run_failure(sandbox: &Sandbox, args: &[&str]) -> String {
+    let assert = run_cli(sandbox, args);
+    let stderr = assert.stderr();
+
+    assert.failure();
+    stderr
+}
+
+fn render_with<O>(renderer: impl SchemaRenderer<O>) -> String {
+    let sandbox = create_empty_sandbox();
+    let file = sandbox.path().join("output");
+
+    create_generator().generate(&file, renderer).unwrap();
+
+    fs::read_to_string(file).unwrap()
+}
+
+mod generate {
+    use super::*;
+
+    #[test]
+    fn writes_to_stdout() {
+        let sandbox = create_schemas_sandbox();
+
+        let output = run_success(
+            &sandbox,
+            &[
+                "generate",
+                "--schemas",
+                "schemas.json",
+                "--renderer",
+                "typescript",
+            ],
+        );
+
+        assert_eq!(output, render_with(TypeScriptRenderer::default()));
+    }
+
+    #[test]
+    fn writes_to_output_file() {
+        let sandbox = create_schemas_sandbox();
+
+        run_success(
+            &sandbox,
+            &[
+                "generate",
+                "--schemas",
+                "schemas.json",
+                "--renderer",
+                "json-schema",
+                "--output",
+                "out/schema.json",
+            ],
+        );
+
+        assert_eq!(
+            fs::read_to_string(sandbox.path().join("out/schema.json")).unwrap(),
+            render_with(JsonSchemaRenderer::default())
+        );
+    }
+
+    #[test]
+    fn passes_options_to_renderers() {
+        let sandbox = create_schemas_sandbox();
+
+        let output = run_success(
+            &sandbox,
+            &[
+                "generate",
+                "--schemas",
+                "schemas.json",
+                "--renderer",
+                "typescript",
+                "--enum-format",
+                "enum",
+                "--object-format",
+                "type",
+                "--property-format",
+                "optional",
+                "--external-type",
+                "./types=Extra",
+            ],
+        );
+
+        assert_eq!(
+            output,
+            render_with(TypeScriptRenderer::new(TypeScriptOptions {
+                enum_format: EnumFormat::Enum,
+                object_format: ObjectFormat::Type,
+                property_format: PropertyFormat::Optional,
+                external_types: [("./types".into(), vec!["Extra".into()])].into(),
+                ..Default::default()
+            }))
+        );
+
+        let output = run_success(
+            &sandbox,
+            &[
+                "generate",
+                "--schemas",
+                "schemas.json",
+                "--renderer",
+                "yaml-template",
+                "--header",
+                "# header",
+                "--hide-field",
+                "level",
+                "--no-comments",
+            ],
+        );
+
+        assert_eq!(
+            output,
+            render_with(YamlTemplateRenderer::new(TemplateOptions {
+                comments: false,
+                header: "# header".into(),
+                hide_fields: vec!["level".into()],
+                ..Default::default()
+            }))
+        );
+
+        let output = run_success(
+            &sandbox,
+            &[
+                "generate",
+                "--schemas",
+                "schemas.json",
+                "--renderer",
+                "env-vars",
+                "--env-format",
+                "dotenv",
+            ],
+        );
+
+        assert_eq!(
+            output,
+            "# Name of the application.\n# @setting name\n# @type string\nAPP_NAME=\n"
+        );
+    }
+
+    #[test]
+    fn renders_the_provided_root() {
+        let sandbox = create_schemas_sandbox();
+
+        let output = run_success(
+            &sandbox,
+            &[
+                "generate",
+                "--schemas",
+                "schemas.json",
+                "--renderer",
+                "toml-template",
+                "--root",
+                "ServerConfig",
+            ],
+        );
+
+        assert!(output.contains("port = 8080"));
+        assert!(!output.contains("name = "));
+
+        assert!(
+            run_failure(
+                &sandbox,
+                &[
+                    "generate",
+                    "--schemas",
+                    "schemas.json",
+                    "--renderer",
+                    "toml-template",
+                    "--root",
+                    "Unknown",
+                ]
+            )
+            .contains("Unknown root schema Unknown")
+        );
+    }
+
+    #[test]
+    fn checks_for_stale_files() {
+        let sandbox = create_schemas_sandbox();
+        let args = [
+            "generate",
+            "--schemas",
+            "schemas.json",
+            "--renderer",
+            "typescript",
+            "--output",
+            "types.ts",
+            "--check",
+        ];
+
+        // Missing
+        assert!(run_failure(&sandbox, &args).contains("is out of date"));
+
+        // Up to date
+        sandbox.create_file("types.ts", render_with(TypeScriptRenderer::default()));
+
+        run_success(&sandbox, &args);
+
+        // Stale
+        sandbox.append_file("types.ts", "// edited");
+
+        run_failure(&sandbox, &args);
+
+        assert!(
+            fs::read_to_string(sandbox.path().join("types.ts"))
+                .unwrap()
+                .contains("// edited")
+        );
+    }
+}
+
+mod validate {
+    use super::*;
+
+    #[test]
+    fn passes_for_valid_files() {
+        let sandbox = create_schemas_sandbox();
+        sandbox.create_file(
+            "config.yml",
+            "name: app\nlevel: debug\nserver:\n  host: localhost\n  port: 80\n",
+        );
+
+        run_success(
+            &sandbox,
+            &["validate", "--schemas", "schemas.json", "config.yml"],
+        );
+    }
+
+    #[test]
+    fn passes_for_partial_files() {
+        let sandbox = create_schemas_sandbox();
+        sandbox.create_file("config.yml", "name: app\n");
+
+        run_success(
+            &sandbox,
+            &["validate", "--schemas", "schemas.json", "config.yml"],
+        );
+
+        let output = run_failure(
+            &sandbox,
+            &[
+                "validate",
+                "--schemas",
+                "schemas.json",
+                "--require-fields",
+                "config.yml",
+            ],
+        );
+
+        assert!(output.contains("server: this setting is required"));
+    }
+
+    #[test]
+    fn fails_for_invalid_files() {
+        let sandbox = create_schemas_sandbox();
+        sandbox.create_file(
+            "config.toml",
+            "name = \"app\"\nlevel = \"trace\"\nunknown = true\n\n[server]\nport = \"80\"\n",
+        );
+
+        let output = run_failure(
+            &sandbox,
+            &["validate", "--schemas", "schemas.json", "config.toml"],
+        );
+
+        assert!(output.contains("Failed to validate config.toml"));
+        assert!(output.contains("level: expected one of"));
+        assert!(output.contains("server.port: expected integer"));
+        assert!(output.contains("unknown"));
+
+        run_failure(
+            &sandbox,
+            &[
+                "validate",
+                "--schemas",
+                "schemas.json",
+                "--allow-unknown-fields",
+                "config.toml",
+            ],
+        );
+    }
+
+    #[test]
+    fn validates_against_the_provided_root() {
+        let sandbox = create_schemas_sandbox();
+        sandbox.create_file("server.json", r#"{ "host": "localhost", "port": 0 }"#);
+
+        let output = run_failure(
+            &sandbox,
+            &[
+                "validate",
+                "--schemas",
+                "schemas.json",
+                "--root",
+                "ServerConfig",
+                "server.json",
+            ],
+        );
+
+        assert!(output.contains("port:"));
+    }
+
+    #[test]
+    fn fails_for_unsupported_formats() {
+        let sandbox = create_schemas_sandbox();
+        sandbox.create_file("config.ini", "name = app");
+
+        assert!(
+            run_failure(
+                &sandbox,
+                &["validate", "--schemas", "schemas.json", "config.ini"]
+            )
+            .contains("Unsupported format")
+        );
+    }
+}
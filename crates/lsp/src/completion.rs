@@ -0,0 +1,116 @@
+use crate::document::{Document, DocumentFormat};
+use crate::hover::field_documentation;
+use crate::schema::SchemaWalker;
+use crate::syntax::{CursorContext, CursorKind};
+use lsp_types::{CompletionItem, CompletionItemKind, CompletionItemTag, Documentation};
+use schematic::schema::{LiteralValue, Schema, SchemaType};
+
+/// Generate completion items for the cursor context within the document.
+pub fn complete(
+    walker: &SchemaWalker,
+    root: &Schema,
+    document: &Document,
+    context: &CursorContext,
+) -> Vec<CompletionItem> {
+    let Some(schema) = walker.schema_at(root, &context.path) else {
+        return vec![];
+    };
+
+    let mut items = vec![];
+
+    if context.kind == CursorKind::Value {
+        let mut values = vec![];
+
+        collect_values(walker, schema, &mut values, 0);
+
+        for value in values {
+            let label = format_value(&value, document.format, context.quoted);
+
+            if items
+                .iter()
+                .any(|item: &CompletionItem| item.label == label)
+            {
+                continue;
+            }
+
+            items.push(CompletionItem {
+                label,
+                kind: Some(if matches!(value, LiteralValue::Bool(_)) {
+                    CompletionItemKind::VALUE
+                } else {
+                    CompletionItemKind::ENUM_MEMBER
+                }),
+                ..Default::default()
+            });
+        }
+    }
+
+    // Values of a list item or union may also be an object
+    for (name, field) in walker.fields(schema) {
+        items.push(CompletionItem {
+            label: name.to_owned(),
+            kind: Some(CompletionItemKind::PROPERTY),
+            detail: Some(field.schema.to_string()),
+            documentation: field
+                .comment
+                .as_ref()
+                .map(|_| Documentation::MarkupContent(field_documentation(name, field))),
+            tags: field
+                .deprecated
+                .as_ref()
+                .map(|_| vec![CompletionItemTag::DEPRECATED]),
+            insert_text: Some(match document.format {
+                DocumentFormat::Json if context.quoted => name.to_owned(),
+                DocumentFormat::Json => format!("\"{name}\": "),
+                DocumentFormat::Toml => format!("{name} = "),
+                DocumentFormat::Yaml => format!("{name}: "),
+            }),
+            ..Default::default()
+        });
+    }
+
+    items
+}
+
+fn collect_values(
+    walker: &SchemaWalker,
+    schema: &Schema,
+    values: &mut Vec<LiteralValue>,
+    depth: usize,
+) {
+    if depth > 10 {
+        return;
+    }
+
+    let Some(schema) = walker.resolve(schema) else {
+        return;
+    };
+
+    match &schema.ty {
+        SchemaType::Array(inner) => {
+            collect_values(walker, &inner.items_type, values, depth + 1);
+        }
+        SchemaType::Boolean(_) => {
+            values.extend([LiteralValue::Bool(true), LiteralValue::Bool(false)]);
+        }
+        SchemaType::Enum(inner) => {
+            values.extend(inner.values.iter().cloned());
+        }
+        SchemaType::Literal(inner) => {
+            values.push(inner.value.clone());
+        }
+        SchemaType::Union(inner) => {
+            for variant in &inner.variants_types {
+                collect_values(walker, variant, values, depth + 1);
+            }
+        }
+        _ => {}
+    };
+}
+
+fn format_value(value: &LiteralValue, format: DocumentFormat, quoted: bool) -> String {
+    match value {
+        LiteralValue::String(inner) if quoted || format == DocumentFormat::Yaml => inner.to_owned(),
+        other => other.to_string(),
+    }
+}
@@ -0,0 +1,202 @@
+use crate::document::Document;
+use crate::syntax::{Segment, parse_path};
+use lsp_types::{Diagnostic, DiagnosticSeverity, Range};
+use schematic::{Config, ConfigError, ConfigLoader, PathSegment, ValidateError, ValidateSeverity};
+use std::path::Path;
+
+/// A function that loads a document and returns all diagnostics.
+pub type Diagnoser = Box<dyn Fn(&Document) -> Vec<Diagnostic>>;
+
+/// Create a diagnoser that loads documents with a loader returned from the factory.
+pub fn create_diagnoser<T: Config + 'static>(
+    factory: impl Fn() -> ConfigLoader<T> + 'static,
+) -> Diagnoser {
+    Box::new(move |document| {
+        let mut loader = factory();
+        let name = T::schema_name().unwrap_or_else(|| "<unknown>".into());
+
+        load_diagnostics(&mut loader, &name, document)
+    })
+}
+
+/// Load the contents of the document as a code source, and convert
+/// all parse and validation failures into diagnostics. When the document
+/// exists on the file system, extended files are resolved relative to it.
+pub fn load_diagnostics<T: Config>(
+    loader: &mut ConfigLoader<T>,
+    name: &str,
+    document: &Document,
+) -> Vec<Diagnostic> {
+    let mut diagnostics = vec![];
+
+    loader.set_aggregate_errors(true);
+
+    if let Some(dir) = document.path.as_deref().and_then(|path| path.parent()) {
+        loader.set_base_dir(dir);
+    }
+
+    let result = loader
+        .code(document.text.as_str(), document.source_path())
+        .and_then(|loader| loader.load());
+
+    match result {
+        Ok(result) => {
+            for warning in &result.warnings {
+                diagnostics.push(create_validate_diagnostic(document, None, warning));
+            }
+        }
+        Err(error) => {
+            collect_diagnostics(document, name, &error, &mut diagnostics);
+        }
+    };
+
+    diagnostics
+}
+
+fn collect_diagnostics(
+    document: &Document,
+    name: &str,
+    error: &ConfigError,
+    diagnostics: &mut Vec<Diagnostic>,
+) {
+    match error {
+        ConfigError::Multiple { errors, .. } => {
+            for error in errors {
+                collect_diagnostics(document, name, error, diagnostics);
+            }
+        }
+        ConfigError::Parser {
+            location, error, ..
+        } => {
+            // Failed within an extended file
+            if location != name {
+                diagnostics.push(create_diagnostic(
+                    find_extends_range(document, location),
+                    format!("{location}: {}", error.message),
+                    DiagnosticSeverity::ERROR,
+                ));
+
+                return;
+            }
+
+            let range = match &error.span {
+                Some(span) => document.range_at(&(span.offset()..span.offset() + span.len())),
+                None => find_path_range(document, &parse_path(&error.path)),
+            };
+
+            diagnostics.push(create_diagnostic(
+                range,
+                error.message.clone(),
+                DiagnosticSeverity::ERROR,
+            ));
+        }
+        ConfigError::Validator {
+            location, error, ..
+        } => {
+            let location = if location == name {
+                None
+            } else {
+                Some(location.as_str())
+            };
+
+            for inner in &error.errors {
+                diagnostics.push(create_validate_diagnostic(document, location, inner));
+            }
+        }
+        other => {
+            diagnostics.push(create_diagnostic(
+                Range::default(),
+                strip_styles(&other.to_full_string()),
+                DiagnosticSeverity::ERROR,
+            ));
+        }
+    };
+}
+
+fn create_diagnostic(range: Range, message: String, severity: DiagnosticSeverity) -> Diagnostic {
+    Diagnostic {
+        range,
+        severity: Some(severity),
+        source: Some("schematic".into()),
+        message,
+        ..Default::default()
+    }
+}
+
+fn create_validate_diagnostic(
+    document: &Document,
+    location: Option<&str>,
+    error: &ValidateError,
+) -> Diagnostic {
+    let path = error
+        .path
+        .segments()
+        .iter()
+        .filter_map(|segment| match segment {
+            PathSegment::Index(index) => Some(Segment::Index(*index)),
+            PathSegment::Key(key) | PathSegment::Variant(key) => Some(Segment::key(key)),
+            PathSegment::Unknown => None,
+        })
+        .collect::<Vec<_>>();
+
+    let (range, message) = match location {
+        Some(location) => (
+            find_extends_range(document, location),
+            strip_styles(&format!("{location}: {error}")),
+        ),
+        None => (find_path_range(document, &path), error.message.clone()),
+    };
+
+    create_diagnostic(
+        range,
+        message,
+        match error.severity {
+            ValidateSeverity::Error => DiagnosticSeverity::ERROR,
+            ValidateSeverity::Warning => DiagnosticSeverity::WARNING,
+        },
+    )
+}
+
+/// Find the range of the key at the path, or the closest parent key.
+fn find_path_range(document: &Document, path: &[Segment]) -> Range {
+    for depth in (1..=path.len()).rev() {
+        if let Some(key) = document.keys.iter().find(|key| key.path == path[..depth]) {
+            return document.range_at(&key.span);
+        }
+    }
+
+    Range::default()
+}
+
+/// Find where an extended file is referenced within the document.
+fn find_extends_range(document: &Document, location: &str) -> Range {
+    let file_name = Path::new(location)
+        .file_name()
+        .and_then(|name| name.to_str())
+        .unwrap_or(location);
+
+    match document.text.find(file_name) {
+        Some(offset) => document.range_at(&(offset..offset + file_name.len())),
+        None => Range::default(),
+    }
+}
+
+/// Remove ANSI escape sequences that were added for terminal output.
+fn strip_styles(value: &str) -> String {
+    let mut output = String::with_capacity(value.len());
+    let mut chars = value.chars();
+
+    while let Some(ch) = chars.next() {
+        if ch == '\x1b' {
+            for next in chars.by_ref() {
+                if next.is_ascii_alphabetic() {
+                    break;
+                }
+            }
+        } else {
+            output.push(ch);
+        }
+    }
+
+    output
+}
@@ -0,0 +1,192 @@
+use crate::syntax::{self, CursorContext, KeyNode};
+use lsp_types::{Position, Range, Uri};
+use std::path::{Path, PathBuf};
+
+/// Formats of documents that the server can analyze.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum DocumentFormat {
+    Json,
+    Toml,
+    Yaml,
+}
+
+impl DocumentFormat {
+    /// Detect the format from a file extension (without period).
+    pub fn from_extension(ext: &str) -> Option<Self> {
+        match ext.to_lowercase().as_str() {
+            "json" | "jsonc" => Some(Self::Json),
+            "toml" => Some(Self::Toml),
+            "yaml" | "yml" => Some(Self::Yaml),
+            _ => None,
+        }
+    }
+
+    /// Detect the format from an editor language identifier.
+    pub fn from_language_id(id: &str) -> Option<Self> {
+        match id {
+            "json" | "jsonc" => Some(Self::Json),
+            "toml" => Some(Self::Toml),
+            "yaml" => Some(Self::Yaml),
+            _ => None,
+        }
+    }
+
+    /// Return the canonical file extension for the format.
+    pub fn extension(&self) -> &'static str {
+        match self {
+            Self::Json => "json",
+            Self::Toml => "toml",
+            Self::Yaml => "yaml",
+        }
+    }
+}
+
+/// An open text document, and its analyzed key structure.
+#[derive(Clone, Debug)]
+pub struct Document {
+    /// Format of the document contents.
+    pub format: DocumentFormat,
+
+    /// Keys declared within the document, in order of declaration.
+    pub keys: Vec<KeyNode>,
+
+    /// File system path, if the URI uses the `file` scheme.
+    pub path: Option<PathBuf>,
+
+    /// Current contents of the document.
+    pub text: String,
+
+    /// URI of the document, as provided by the client.
+    pub uri: Uri,
+
+    line_offsets: Vec<usize>,
+}
+
+impl Document {
+    /// Create a new document, or return `None` if the format could not be detected.
+    pub fn new(uri: Uri, language_id: &str, text: String) -> Option<Self> {
+        let path = uri_to_path(&uri);
+        let format = path
+            .as_deref()
+            .and_then(|path| path.extension())
+            .and_then(|ext| ext.to_str())
+            .and_then(DocumentFormat::from_extension)
+            .or_else(|| DocumentFormat::from_language_id(language_id))?;
+
+        let mut document = Self {
+            format,
+            keys: vec![],
+            path,
+            text: String::new(),
+            uri,
+            line_offsets: vec![],
+        };
+
+        document.update(text);
+
+        Some(document)
+    }
+
+    /// Replace the contents of the document and analyze it again.
+    pub fn update(&mut self, text: String) {
+        self.line_offsets = vec![0];
+        self.line_offsets.extend(
+            text.char_indices()
+                .filter(|(_, ch)| *ch == '\n')
+                .map(|(index, _)| index + 1),
+        );
+        self.keys = syntax::collect_keys(self.format, &text);
+        self.text = text;
+    }
+
+    /// Return the file name to use when loading the document as a code source.
+    pub fn source_path(&self) -> PathBuf {
+        self.path
+            .clone()
+            .unwrap_or_else(|| PathBuf::from(format!("untitled.{}", self.format.extension())))
+    }
+
+    /// Determine what is being edited at the provided position.
+    pub fn context_at(&self, position: Position) -> CursorContext {
+        syntax::cursor_context(self.format, &self.text, self.offset_at(position))
+    }
+
+    /// Return the key at the provided position, if there is one.
+    pub fn key_at(&self, position: Position) -> Option<&KeyNode> {
+        let offset = self.offset_at(position);
+
+        self.keys
+            .iter()
+            .find(|key| key.span.start <= offset && offset <= key.span.end)
+    }
+
+    /// Convert an LSP position (UTF-16 based) into a byte offset.
+    pub fn offset_at(&self, position: Position) -> usize {
+        let Some(start) = self.line_offsets.get(position.line as usize) else {
+            return self.text.len();
+        };
+
+        let mut units = 0;
+
+        for (index, ch) in self.text[*start..].char_indices() {
+            if units >= position.character as usize || ch == '\n' {
+                return start + index;
+            }
+
+            units += ch.len_utf16();
+        }
+
+        self.text.len()
+    }
+
+    /// Convert a byte offset into an LSP position (UTF-16 based).
+    pub fn position_at(&self, offset: usize) -> Position {
+        let offset = offset.min(self.text.len());
+        let line = self
+            .line_offsets
+            .partition_point(|start| *start <= offset)
+            .saturating_sub(1);
+        let start = self.line_offsets[line];
+
+        Position::new(
+            line as u32,
+            self.text[start..offset]
+                .chars()
+                .map(|ch| ch.len_utf16() as u32)
+                .sum(),
+        )
+    }
+
+    /// Convert a span of byte offsets into an LSP range.
+    pub fn range_at(&self, span: &std::ops::Range<usize>) -> Range {
+        Range::new(self.position_at(span.start), self.position_at(span.end))
+    }
+}
+
+/// Convert a `file://` URI into a file system path.
+pub fn uri_to_path(uri: &Uri) -> Option<PathBuf> {
+    let path = uri.as_str().strip_prefix("file://")?;
+
+    // Strip the authority, which is typically empty or `localhost`
+    let path = &path[path.find('/')?..];
+    let mut bytes = Vec::with_capacity(path.len());
+    let mut iter = path.bytes();
+
+    while let Some(byte) = iter.next() {
+        if byte == b'%' {
+            let hex = [iter.next()?, iter.next()?];
+            bytes.push(u8::from_str_radix(std::str::from_utf8(&hex).ok()?, 16).ok()?);
+        } else {
+            bytes.push(byte);
+        }
+    }
+
+    let path = String::from_utf8(bytes).ok()?;
+
+    // Windows drive letters are prefixed with a slash: `/C:/...`
+    if path.len() > 2 && path.as_bytes()[2] == b':' {
+        return Some(PathBuf::from(&path[1..]));
+    }
+
+    Some(Path::new(&path).to_path_buf())
+}
@@ -0,0 +1,23 @@
+use lsp_server::ProtocolError;
+use miette::Diagnostic;
+use thiserror::Error;
+
+/// All language server errors.
+#[derive(Error, Debug, Diagnostic)]
+pub enum LspError {
+    #[diagnostic(code(lsp::disconnected))]
+    #[error("Failed to send a message, the client has disconnected.")]
+    Disconnected,
+
+    #[diagnostic(code(lsp::io))]
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+
+    #[diagnostic(code(lsp::json))]
+    #[error(transparent)]
+    Json(#[from] serde_json::Error),
+
+    #[diagnostic(code(lsp::protocol))]
+    #[error(transparent)]
+    Protocol(#[from] ProtocolError),
+}
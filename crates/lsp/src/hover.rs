@@ -0,0 +1,32 @@
+use lsp_types::{MarkupContent, MarkupKind};
+use schematic::schema::SchemaField;
+
+/// Render the documentation of a field as markdown.
+pub fn field_documentation(name: &str, field: &SchemaField) -> MarkupContent {
+    let mut sections = vec![format!("```\n{name}: {}\n```", field.schema)];
+
+    if let Some(comment) = &field.comment {
+        sections.push(comment.to_owned());
+    }
+
+    if let Some(deprecated) = &field.deprecated {
+        sections.push(if deprecated.is_empty() {
+            "**Deprecated**".to_owned()
+        } else {
+            format!("**Deprecated:** {deprecated}")
+        });
+    }
+
+    if let Some(default) = field.schema.ty.get_default() {
+        sections.push(format!("**Default:** `{default}`"));
+    }
+
+    if let Some(env_var) = &field.env_var {
+        sections.push(format!("**Environment variable:** `{env_var}`"));
+    }
+
+    MarkupContent {
+        kind: MarkupKind::Markdown,
+        value: sections.join("\n\n"),
+    }
+}
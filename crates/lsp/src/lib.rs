@@ -0,0 +1,18 @@
+mod completion;
+mod diagnostics;
+mod document;
+mod error;
+mod hover;
+mod schema;
+mod server;
+mod syntax;
+
+pub use diagnostics::*;
+pub use document::*;
+pub use error::*;
+pub use schema::*;
+pub use server::*;
+pub use syntax::{CursorContext, CursorKind, KeyNode, Segment};
+
+pub use lsp_server;
+pub use lsp_types;
@@ -0,0 +1,122 @@
+use crate::syntax::Segment;
+use schematic::schema::{IndexMap, Schema, SchemaField, SchemaType};
+
+const MAX_REFERENCE_DEPTH: usize = 25;
+
+/// Walks a schema (and its references) to find the schema at a path.
+pub struct SchemaWalker<'a> {
+    references: &'a IndexMap<String, Schema>,
+}
+
+impl<'a> SchemaWalker<'a> {
+    pub fn new(references: &'a IndexMap<String, Schema>) -> Self {
+        Self { references }
+    }
+
+    /// Resolve references until a concrete schema is found.
+    pub fn resolve(&self, mut schema: &'a Schema) -> Option<&'a Schema> {
+        for _ in 0..MAX_REFERENCE_DEPTH {
+            match &schema.ty {
+                SchemaType::Reference(name) => {
+                    schema = self.references.get(name)?;
+                }
+                _ => return Some(schema),
+            };
+        }
+
+        None
+    }
+
+    /// Return all non-hidden fields of a struct, including flattened fields,
+    /// and the fields of struct variants within a union.
+    pub fn fields(&self, schema: &'a Schema) -> Vec<(&'a str, &'a SchemaField)> {
+        let mut fields = vec![];
+
+        self.collect_fields(schema, &mut fields, 0);
+
+        fields
+    }
+
+    fn collect_fields(
+        &self,
+        schema: &'a Schema,
+        fields: &mut Vec<(&'a str, &'a SchemaField)>,
+        depth: usize,
+    ) {
+        if depth > MAX_REFERENCE_DEPTH {
+            return;
+        }
+
+        let Some(schema) = self.resolve(schema) else {
+            return;
+        };
+
+        match &schema.ty {
+            SchemaType::Struct(inner) => {
+                for (name, field) in &inner.fields {
+                    if field.hidden {
+                        continue;
+                    }
+
+                    if field.flatten {
+                        self.collect_fields(&field.schema, fields, depth + 1);
+                    } else if !fields.iter().any(|(other, _)| *other == name) {
+                        fields.push((name, field));
+                    }
+                }
+            }
+            SchemaType::Union(inner) => {
+                for variant in &inner.variants_types {
+                    self.collect_fields(variant, fields, depth + 1);
+                }
+            }
+            _ => {}
+        };
+    }
+
+    /// Find a field by name or alias within a struct.
+    pub fn field(&self, schema: &'a Schema, key: &str) -> Option<&'a SchemaField> {
+        self.fields(schema)
+            .into_iter()
+            .find(|(name, field)| *name == key || field.aliases.iter().any(|alias| alias == key))
+            .map(|(_, field)| field)
+    }
+
+    /// Find the field for the last key in the path.
+    pub fn field_at(&self, root: &'a Schema, path: &[Segment]) -> Option<&'a SchemaField> {
+        let (Segment::Key(key), parent) = path.split_last()? else {
+            return None;
+        };
+
+        self.field(self.schema_at(root, parent)?, key)
+    }
+
+    /// Find the schema for the value at the path.
+    pub fn schema_at(&self, root: &'a Schema, path: &[Segment]) -> Option<&'a Schema> {
+        let mut schema = self.resolve(root)?;
+
+        for segment in path {
+            schema = self.resolve(self.step(schema, segment)?)?;
+        }
+
+        Some(schema)
+    }
+
+    fn step(&self, schema: &'a Schema, segment: &Segment) -> Option<&'a Schema> {
+        match (&schema.ty, segment) {
+            (SchemaType::Struct(_), Segment::Key(key)) => {
+                self.field(schema, key).map(|field| &field.schema)
+            }
+            (SchemaType::Object(inner), Segment::Key(_)) => Some(&inner.value_type),
+            (SchemaType::Array(inner), Segment::Index(_)) => Some(&inner.items_type),
+            (SchemaType::Tuple(inner), Segment::Index(index)) => {
+                inner.items_types.get(*index).map(|item| item.as_ref())
+            }
+            (SchemaType::Union(inner), _) => inner.variants_types.iter().find_map(|variant| {
+                self.resolve(variant)
+                    .and_then(|variant| self.step(variant, segment))
+            }),
+            _ => None,
+        }
+    }
+}
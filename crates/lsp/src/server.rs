@@ -0,0 +1,265 @@
+use crate::completion::complete;
+use crate::diagnostics::{Diagnoser, create_diagnoser};
+use crate::document::Document;
+use crate::error::LspError;
+use crate::hover::field_documentation;
+use crate::schema::SchemaWalker;
+use crate::syntax::Segment;
+use lsp_server::{Connection, ErrorCode, Message, Notification, Request, Response};
+use lsp_types::notification::{
+    DidChangeTextDocument, DidCloseTextDocument, DidOpenTextDocument, DidSaveTextDocument,
+    Notification as _, PublishDiagnostics,
+};
+use lsp_types::request::{Completion, HoverRequest, Request as _};
+use lsp_types::{
+    CompletionOptions, CompletionParams, CompletionResponse, DidChangeTextDocumentParams,
+    DidCloseTextDocumentParams, DidOpenTextDocumentParams, DidSaveTextDocumentParams, Hover,
+    HoverContents, HoverParams, HoverProviderCapability, PublishDiagnosticsParams, SaveOptions,
+    ServerCapabilities, TextDocumentSyncCapability, TextDocumentSyncKind, TextDocumentSyncOptions,
+    Uri,
+};
+use schematic::schema::{IndexMap, Schema, SchemaGenerator};
+use schematic::{Config, ConfigLoader, SchemaBuilder};
+use std::collections::HashMap;
+
+/// A language server for configuration files, that provides completions and
+/// hover documentation from the configuration's schema, and diagnostics from
+/// loading the file with a [`ConfigLoader`].
+pub struct ConfigLanguageServer {
+    diagnoser: Diagnoser,
+    documents: HashMap<Uri, Document>,
+    root: Schema,
+    schemas: IndexMap<String, Schema>,
+}
+
+impl ConfigLanguageServer {
+    /// Create a new language server for the provided configuration.
+    pub fn new<T: Config + 'static>() -> Self {
+        Self::with_loader(ConfigLoader::<T>::new)
+    }
+
+    /// Create a new language server for the provided configuration, with a factory
+    /// that returns a [`ConfigLoader`] for each document that is loaded. The factory
+    /// must not add sources, as the document is added as a source automatically.
+    pub fn with_loader<T: Config + 'static>(
+        factory: impl Fn() -> ConfigLoader<T> + 'static,
+    ) -> Self {
+        let mut generator = SchemaGenerator::default();
+        generator.add::<T>();
+
+        Self {
+            diagnoser: create_diagnoser(factory),
+            documents: HashMap::default(),
+            root: SchemaBuilder::build_root::<T>(),
+            schemas: generator.schemas,
+        }
+    }
+
+    /// Return the capabilities that the server supports.
+    pub fn capabilities() -> ServerCapabilities {
+        ServerCapabilities {
+            completion_provider: Some(CompletionOptions {
+                trigger_characters: Some(vec![
+                    "\"".into(),
+                    ":".into(),
+                    "=".into(),
+                    ".".into(),
+                    "-".into(),
+                ]),
+                ..Default::default()
+            }),
+            hover_provider: Some(HoverProviderCapability::Simple(true)),
+            text_document_sync: Some(TextDocumentSyncCapability::Options(
+                TextDocumentSyncOptions {
+                    open_close: Some(true),
+                    change: Some(TextDocumentSyncKind::FULL),
+                    save: Some(
+                        SaveOptions {
+                            include_text: Some(true),
+                        }
+                        .into(),
+                    ),
+                    ..Default::default()
+                },
+            )),
+            ..Default::default()
+        }
+    }
+
+    /// Return a document that is currently open.
+    pub fn get_document(&self, uri: &Uri) -> Option<&Document> {
+        self.documents.get(uri)
+    }
+
+    /// Run the server over standard input and output, until the client shuts it down.
+    pub fn run_stdio(self) -> Result<(), LspError> {
+        let (connection, io_threads) = Connection::stdio();
+
+        self.run(connection)?;
+        io_threads.join()?;
+
+        Ok(())
+    }
+
+    /// Run the server over the provided connection, until the client shuts it down.
+    pub fn run(mut self, connection: Connection) -> Result<(), LspError> {
+        connection.initialize(serde_json::to_value(Self::capabilities())?)?;
+
+        let send = |message: Message| {
+            connection
+                .sender
+                .send(message)
+                .map_err(|_| LspError::Disconnected)
+        };
+
+        for message in &connection.receiver {
+            match message {
+                Message::Request(request) => {
+                    if connection.handle_shutdown(&request)? {
+                        return Ok(());
+                    }
+
+                    send(Message::Response(self.handle_request(request)))?;
+                }
+                Message::Notification(notification) => {
+                    if let Some(params) = self.handle_notification(notification) {
+                        send(Message::Notification(Notification::new(
+                            PublishDiagnostics::METHOD.into(),
+                            params,
+                        )))?;
+                    }
+                }
+                Message::Response(_) => {}
+            };
+        }
+
+        Ok(())
+    }
+
+    /// Return completion items for the position within a document.
+    pub fn complete(&self, params: CompletionParams) -> Option<CompletionResponse> {
+        let position = params.text_document_position;
+        let document = self.documents.get(&position.text_document.uri)?;
+        let context = document.context_at(position.position);
+
+        Some(CompletionResponse::Array(complete(
+            &SchemaWalker::new(&self.schemas),
+            &self.root,
+            document,
+            &context,
+        )))
+    }
+
+    /// Return documentation for the key at the position within a document.
+    pub fn hover(&self, params: HoverParams) -> Option<Hover> {
+        let position = params.text_document_position_params;
+        let document = self.documents.get(&position.text_document.uri)?;
+        let key = document.key_at(position.position)?;
+        let Some(Segment::Key(name)) = key.path.last() else {
+            return None;
+        };
+        let field = SchemaWalker::new(&self.schemas).field_at(&self.root, &key.path)?;
+
+        Some(Hover {
+            contents: HoverContents::Markup(field_documentation(name, field)),
+            range: Some(document.range_at(&key.span)),
+        })
+    }
+
+    /// Load the document and return diagnostics for all failures.
+    pub fn diagnose(&self, uri: &Uri) -> Option<PublishDiagnosticsParams> {
+        let document = self.documents.get(uri)?;
+
+        Some(PublishDiagnosticsParams {
+            uri: uri.to_owned(),
+            diagnostics: (self.diagnoser)(document),
+            version: None,
+        })
+    }
+
+    fn handle_request(&self, request: Request) -> Response {
+        let id = request.id.clone();
+
+        let result = match request.method.as_str() {
+            Completion::METHOD => request
+                .extract::<CompletionParams>(Completion::METHOD)
+                .map(|(_, params)| serde_json::to_value(self.complete(params))),
+            HoverRequest::METHOD => request
+                .extract::<HoverParams>(HoverRequest::METHOD)
+                .map(|(_, params)| serde_json::to_value(self.hover(params))),
+            method => {
+                return Response::new_err(
+                    id,
+                    ErrorCode::MethodNotFound as i32,
+                    format!("Unsupported method {method}."),
+                );
+            }
+        };
+
+        match result {
+            Ok(Ok(value)) => Response::new_ok(id, value),
+            Ok(Err(error)) => {
+                Response::new_err(id, ErrorCode::InternalError as i32, error.to_string())
+            }
+            Err(error) => Response::new_err(id, ErrorCode::InvalidParams as i32, error.to_string()),
+        }
+    }
+
+    fn handle_notification(
+        &mut self,
+        notification: Notification,
+    ) -> Option<PublishDiagnosticsParams> {
+        match notification.method.as_str() {
+            DidOpenTextDocument::METHOD => {
+                let params = notification
+                    .extract::<DidOpenTextDocumentParams>(DidOpenTextDocument::METHOD)
+                    .ok()?;
+                let item = params.text_document;
+                let document = Document::new(item.uri.clone(), &item.language_id, item.text)?;
+
+                self.documents.insert(item.uri.clone(), document);
+                self.diagnose(&item.uri)
+            }
+            DidChangeTextDocument::METHOD => {
+                let params = notification
+                    .extract::<DidChangeTextDocumentParams>(DidChangeTextDocument::METHOD)
+                    .ok()?;
+                let uri = params.text_document.uri;
+
+                // Only full document syncing is supported
+                self.documents
+                    .get_mut(&uri)?
+                    .update(params.content_changes.into_iter().last()?.text);
+                self.diagnose(&uri)
+            }
+            DidSaveTextDocument::METHOD => {
+                let params = notification
+                    .extract::<DidSaveTextDocumentParams>(DidSaveTextDocument::METHOD)
+                    .ok()?;
+                let uri = params.text_document.uri;
+
+                if let Some(text) = params.text {
+                    self.documents.get_mut(&uri)?.update(text);
+                }
+
+                self.diagnose(&uri)
+            }
+            DidCloseTextDocument::METHOD => {
+                let params = notification
+                    .extract::<DidCloseTextDocumentParams>(DidCloseTextDocument::METHOD)
+                    .ok()?;
+                let uri = params.text_document.uri;
+
+                self.documents.remove(&uri)?;
+
+                // Clear diagnostics for the closed document
+                Some(PublishDiagnosticsParams {
+                    uri,
+                    diagnostics: vec![],
+                    version: None,
+                })
+            }
+            _ => None,
+        }
+    }
+}
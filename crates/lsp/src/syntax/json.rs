@@ -0,0 +1,187 @@
+use super::{CursorContext, KeyNode, Segment};
+
+enum Frame {
+    Array {
+        index: usize,
+    },
+    Object {
+        key: Option<String>,
+        expect_key: bool,
+    },
+}
+
+#[derive(Default)]
+struct Scanner {
+    frames: Vec<Frame>,
+    keys: Vec<KeyNode>,
+}
+
+impl Scanner {
+    fn path(&self, depth: usize) -> Vec<Segment> {
+        self.frames[..depth]
+            .iter()
+            .filter_map(|frame| match frame {
+                Frame::Array { index } => Some(Segment::Index(*index)),
+                Frame::Object { key, .. } => key.as_ref().map(Segment::key),
+            })
+            .collect()
+    }
+
+    fn record_item(&mut self, start: usize, end: usize) {
+        if let Some(Frame::Array { .. }) = self.frames.last() {
+            self.keys.push(KeyNode {
+                path: self.path(self.frames.len()),
+                span: start..end,
+            });
+        }
+    }
+
+    /// Scan tokens up to the limit. Returns true if the limit
+    /// is within a string that has not been fully scanned.
+    fn scan(&mut self, text: &str, limit: usize) -> bool {
+        let bytes = text.as_bytes();
+        let mut index = 0;
+
+        while index < bytes.len() && index < limit {
+            let start = index;
+
+            match bytes[index] {
+                b' ' | b'\t' | b'\r' | b'\n' => {
+                    index += 1;
+                }
+                b'/' if bytes.get(index + 1) == Some(&b'/') => {
+                    index = text[index..]
+                        .find('\n')
+                        .map(|end| index + end)
+                        .unwrap_or(bytes.len());
+                }
+                b'/' if bytes.get(index + 1) == Some(&b'*') => {
+                    index = text[index + 2..]
+                        .find("*/")
+                        .map(|end| index + end + 4)
+                        .unwrap_or(bytes.len());
+                }
+                b'{' => {
+                    self.record_item(start, start + 1);
+                    self.frames.push(Frame::Object {
+                        key: None,
+                        expect_key: true,
+                    });
+                    index += 1;
+                }
+                b'[' => {
+                    self.record_item(start, start + 1);
+                    self.frames.push(Frame::Array { index: 0 });
+                    index += 1;
+                }
+                b'}' | b']' => {
+                    self.frames.pop();
+                    index += 1;
+                }
+                b',' => {
+                    match self.frames.last_mut() {
+                        Some(Frame::Array { index }) => {
+                            *index += 1;
+                        }
+                        Some(Frame::Object { key, expect_key }) => {
+                            *key = None;
+                            *expect_key = true;
+                        }
+                        None => {}
+                    };
+                    index += 1;
+                }
+                b':' => {
+                    if let Some(Frame::Object { expect_key, .. }) = self.frames.last_mut() {
+                        *expect_key = false;
+                    }
+                    index += 1;
+                }
+                b'"' => {
+                    let mut end = index + 1;
+                    let mut terminated = false;
+
+                    while end < bytes.len() {
+                        match bytes[end] {
+                            b'\\' => end += 2,
+                            b'"' => {
+                                terminated = true;
+                                end += 1;
+                                break;
+                            }
+                            b'\n' => break,
+                            _ => end += 1,
+                        };
+                    }
+
+                    let end = end.min(bytes.len());
+
+                    // The cursor is within this string
+                    if limit < end || (!terminated && limit == end) {
+                        return true;
+                    }
+
+                    let inner_end = if terminated { end - 1 } else { end };
+
+                    match self.frames.last_mut() {
+                        Some(Frame::Object {
+                            key,
+                            expect_key: true,
+                        }) => {
+                            *key = Some(text[start + 1..inner_end].to_owned());
+
+                            self.keys.push(KeyNode {
+                                path: self.path(self.frames.len()),
+                                span: start..end,
+                            });
+                        }
+                        _ => {
+                            self.record_item(start, end);
+                        }
+                    };
+
+                    index = end;
+                }
+                _ => {
+                    while index < bytes.len()
+                        && !matches!(
+                            bytes[index],
+                            b' ' | b'\t' | b'\r' | b'\n' | b',' | b':' | b'}' | b']'
+                        )
+                    {
+                        index += 1;
+                    }
+
+                    self.record_item(start, index);
+                }
+            };
+        }
+
+        false
+    }
+}
+
+pub fn collect_keys(text: &str) -> Vec<KeyNode> {
+    let mut scanner = Scanner::default();
+    scanner.scan(text, text.len());
+    scanner.keys
+}
+
+pub fn cursor_context(text: &str, offset: usize) -> CursorContext {
+    let mut scanner = Scanner::default();
+    let quoted = scanner.scan(text, offset);
+    let depth = scanner.frames.len();
+
+    let mut context = match scanner.frames.last() {
+        Some(Frame::Object {
+            key: Some(_),
+            expect_key: false,
+        })
+        | Some(Frame::Array { .. }) => CursorContext::value(scanner.path(depth)),
+        Some(Frame::Object { .. }) => CursorContext::key(scanner.path(depth - 1)),
+        None => CursorContext::key(vec![]),
+    };
+
+    context.quoted = quoted;
+    context
+}
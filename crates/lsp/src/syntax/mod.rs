@@ -0,0 +1,213 @@
+mod json;
+mod toml;
+mod yaml;
+
+use crate::document::DocumentFormat;
+use std::fmt;
+use std::ops::Range;
+
+/// A segment within the path to a setting.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub enum Segment {
+    /// List index: `[0]`
+    Index(usize),
+    /// Map key: `name`
+    Key(String),
+}
+
+impl Segment {
+    pub fn key(value: impl AsRef<str>) -> Self {
+        Self::Key(value.as_ref().to_owned())
+    }
+}
+
+/// Format a list of segments into a dot-notated path.
+pub fn format_path(path: &[Segment]) -> String {
+    let mut output = String::new();
+
+    for segment in path {
+        match segment {
+            Segment::Index(index) => {
+                output.push_str(&format!("[{index}]"));
+            }
+            Segment::Key(key) => {
+                if !output.is_empty() {
+                    output.push('.');
+                }
+
+                output.push_str(key);
+            }
+        };
+    }
+
+    output
+}
+
+/// Parse a dot-notated path (`a.b[0].c`) into a list of segments.
+pub fn parse_path(value: &str) -> Vec<Segment> {
+    let mut path = vec![];
+
+    for part in value.split('.').filter(|part| !part.is_empty()) {
+        let (key, mut indexes) = match part.find('[') {
+            Some(index) => part.split_at(index),
+            None => (part, ""),
+        };
+
+        if !key.is_empty() {
+            path.push(Segment::key(key));
+        }
+
+        while let Some(rest) = indexes.strip_prefix('[') {
+            let Some(end) = rest.find(']') else {
+                break;
+            };
+
+            if let Ok(index) = rest[..end].parse() {
+                path.push(Segment::Index(index));
+            }
+
+            indexes = &rest[end + 1..];
+        }
+    }
+
+    path
+}
+
+/// A key (or list item) declared within a document.
+#[derive(Clone, Debug, PartialEq)]
+pub struct KeyNode {
+    /// Full path from the document root to the key.
+    pub path: Vec<Segment>,
+
+    /// Byte offsets of the key within the document.
+    pub span: Range<usize>,
+}
+
+impl fmt::Display for KeyNode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", format_path(&self.path))
+    }
+}
+
+/// What kind of token is being edited at the cursor.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum CursorKind {
+    /// A key within the object at the path.
+    Key,
+    /// The value of the setting at the path.
+    Value,
+}
+
+/// Information about the location of the cursor within a document.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CursorContext {
+    pub kind: CursorKind,
+
+    /// Path to the parent object (for keys), or to the setting (for values).
+    pub path: Vec<Segment>,
+
+    /// Whether the cursor is within a quoted string.
+    pub quoted: bool,
+}
+
+impl CursorContext {
+    pub fn key(path: Vec<Segment>) -> Self {
+        Self {
+            kind: CursorKind::Key,
+            path,
+            quoted: false,
+        }
+    }
+
+    pub fn value(path: Vec<Segment>) -> Self {
+        Self {
+            kind: CursorKind::Value,
+            path,
+            quoted: false,
+        }
+    }
+}
+
+/// Collect all keys declared within the document text.
+pub fn collect_keys(format: DocumentFormat, text: &str) -> Vec<KeyNode> {
+    match format {
+        DocumentFormat::Json => json::collect_keys(text),
+        DocumentFormat::Toml => toml::collect_keys(text),
+        DocumentFormat::Yaml => yaml::collect_keys(text),
+    }
+}
+
+/// Determine the context of the cursor at the provided byte offset.
+pub fn cursor_context(format: DocumentFormat, text: &str, offset: usize) -> CursorContext {
+    let offset = offset.min(text.len());
+
+    match format {
+        DocumentFormat::Json => json::cursor_context(text, offset),
+        DocumentFormat::Toml => toml::cursor_context(text, offset),
+        DocumentFormat::Yaml => yaml::cursor_context(text, offset),
+    }
+}
+
+/// Iterate over each line in the text, with the byte offset of its first character.
+/// Line endings are not included.
+fn lines_with_offsets(text: &str) -> impl Iterator<Item = (usize, &str)> {
+    let mut offset = 0;
+
+    text.split('\n').map(move |line| {
+        let start = offset;
+        offset += line.len() + 1;
+
+        (start, line.strip_suffix('\r').unwrap_or(line))
+    })
+}
+
+/// Return the byte length of leading whitespace.
+fn indent_of(line: &str) -> usize {
+    line.len() - line.trim_start().len()
+}
+
+/// Find the index of the provided character, ignoring quoted strings.
+fn find_unquoted(line: &str, needle: char) -> Option<usize> {
+    let mut quote: Option<char> = None;
+    let mut escaped = false;
+
+    for (index, ch) in line.char_indices() {
+        if let Some(q) = quote {
+            if escaped {
+                escaped = false;
+            } else if ch == '\\' && q == '"' {
+                escaped = true;
+            } else if ch == q {
+                quote = None;
+            }
+        } else if ch == '"' || ch == '\'' {
+            quote = Some(ch);
+        } else if ch == needle {
+            return Some(index);
+        }
+    }
+
+    None
+}
+
+/// Return true if the text ends within an unterminated quoted string.
+fn ends_in_quote(text: &str) -> bool {
+    let mut quote: Option<char> = None;
+    let mut escaped = false;
+
+    for ch in text.chars() {
+        if let Some(q) = quote {
+            if escaped {
+                escaped = false;
+            } else if ch == '\\' && q == '"' {
+                escaped = true;
+            } else if ch == q {
+                quote = None;
+            }
+        } else if ch == '"' || ch == '\'' {
+            quote = Some(ch);
+        }
+    }
+
+    quote.is_some()
+}
@@ -0,0 +1,226 @@
+use super::{CursorContext, KeyNode, Segment, ends_in_quote, find_unquoted, lines_with_offsets};
+use std::collections::HashMap;
+use std::ops::Range;
+
+/// Split a dotted key (`a."b.c".d`) into its parts, with the byte offsets of each part.
+/// Stops at the first `=` or `]` character. A trailing dot results in an empty part.
+fn split_key(line: &str, offset: usize) -> Vec<(String, Range<usize>)> {
+    let mut parts = vec![];
+    let mut rest = line;
+    let mut start = offset;
+
+    loop {
+        let trimmed = rest.trim_start();
+        start += rest.len() - trimmed.len();
+        rest = trimmed;
+
+        let (key, len) = if let Some(inner) = rest.strip_prefix(['"', '\'']) {
+            let quote = rest.as_bytes()[0] as char;
+
+            match inner.find(quote) {
+                Some(end) => (inner[..end].to_owned(), end + 2),
+                None => (inner.to_owned(), rest.len()),
+            }
+        } else {
+            let len = rest
+                .find(|ch: char| !(ch.is_ascii_alphanumeric() || ch == '-' || ch == '_'))
+                .unwrap_or(rest.len());
+
+            (rest[..len].to_owned(), len)
+        };
+
+        parts.push((key, start..start + len));
+
+        let after = &rest[len..];
+        let trimmed = after.trim_start();
+
+        match trimmed.strip_prefix('.') {
+            Some(next) => {
+                start += len + (after.len() - next.len());
+                rest = next;
+            }
+            None => break,
+        };
+    }
+
+    parts
+}
+
+/// Return the bracket depth change for a value, ignoring quoted strings.
+fn bracket_depth(value: &str) -> isize {
+    let mut depth = 0;
+    let mut rest = value;
+
+    while let Some(index) = rest.find(['[', ']', '{', '}', '"', '\'', '#']) {
+        let ch = rest.as_bytes()[index] as char;
+
+        match ch {
+            '[' | '{' => depth += 1,
+            ']' | '}' => depth -= 1,
+            '#' => break,
+            _ => {
+                let inner = &rest[index + 1..];
+
+                match inner.find(ch) {
+                    Some(end) => {
+                        rest = &inner[end + 1..];
+                        continue;
+                    }
+                    None => break,
+                };
+            }
+        };
+
+        rest = &rest[index + 1..];
+    }
+
+    depth
+}
+
+#[derive(Default)]
+struct Scanner {
+    array_counts: HashMap<Vec<Segment>, usize>,
+    continuation: Option<(Vec<Segment>, isize)>,
+    keys: Vec<KeyNode>,
+    table: Vec<Segment>,
+}
+
+impl Scanner {
+    fn record(&mut self, base: &[Segment], parts: &[(String, Range<usize>)]) -> Vec<Segment> {
+        let mut path = base.to_vec();
+
+        for (key, span) in parts {
+            path.push(Segment::key(key));
+
+            self.keys.push(KeyNode {
+                path: path.clone(),
+                span: span.clone(),
+            });
+        }
+
+        path
+    }
+
+    fn line(&mut self, offset: usize, line: &str) {
+        if let Some((path, depth)) = self.continuation.take() {
+            let depth = depth + bracket_depth(line);
+
+            if depth > 0 {
+                self.continuation = Some((path, depth));
+            }
+
+            return;
+        }
+
+        let indent = line.len() - line.trim_start().len();
+        let content = &line[indent..];
+        let offset = offset + indent;
+
+        if content.is_empty() || content.starts_with('#') {
+            return;
+        }
+
+        if let Some(header) = content.strip_prefix("[[") {
+            let parts = split_key(header, offset + 2);
+            let path = self.record(&[], &parts);
+            let count = self.array_counts.entry(path.clone()).or_insert(0);
+
+            self.table = path;
+            self.table.push(Segment::Index(*count));
+            *count += 1;
+
+            return;
+        }
+
+        if let Some(header) = content.strip_prefix('[') {
+            let parts = split_key(header, offset + 1);
+
+            self.table = self.record(&[], &parts);
+
+            return;
+        }
+
+        let Some(eq) = find_unquoted(content, '=') else {
+            return;
+        };
+
+        let parts = split_key(&content[..eq], offset);
+        let table = self.table.clone();
+        let path = self.record(&table, &parts);
+        let depth = bracket_depth(&content[eq + 1..]);
+
+        if depth > 0 {
+            self.continuation = Some((path, depth));
+        }
+    }
+}
+
+pub fn collect_keys(text: &str) -> Vec<KeyNode> {
+    let mut scanner = Scanner::default();
+
+    for (offset, line) in lines_with_offsets(text) {
+        scanner.line(offset, line);
+    }
+
+    scanner.keys
+}
+
+pub fn cursor_context(text: &str, offset: usize) -> CursorContext {
+    let mut scanner = Scanner::default();
+    let line_start = text[..offset]
+        .rfind('\n')
+        .map(|index| index + 1)
+        .unwrap_or(0);
+
+    for (start, line) in lines_with_offsets(&text[..line_start]) {
+        scanner.line(start, line);
+    }
+
+    let before = text[line_start..offset].trim_start();
+
+    // Within a multi-line array or inline table
+    if let Some((path, _)) = scanner.continuation {
+        let mut context = CursorContext::value(path);
+        context.quoted = ends_in_quote(before);
+
+        return context;
+    }
+
+    // Within a table header
+    if let Some(header) = before.strip_prefix('[') {
+        let mut parts = split_key(header.trim_start_matches('['), 0);
+        parts.pop();
+
+        return CursorContext::key(
+            parts
+                .into_iter()
+                .map(|(key, _)| Segment::Key(key))
+                .collect(),
+        );
+    }
+
+    let mut path = scanner.table;
+
+    if let Some(eq) = find_unquoted(before, '=') {
+        path.extend(
+            split_key(&before[..eq], 0)
+                .into_iter()
+                .map(|(key, _)| Segment::Key(key)),
+        );
+
+        let mut context = CursorContext::value(path);
+        context.quoted = ends_in_quote(&before[eq + 1..]);
+
+        return context;
+    }
+
+    let mut parts = if before.is_empty() {
+        vec![]
+    } else {
+        split_key(before, 0)
+    };
+    parts.pop();
+    path.extend(parts.into_iter().map(|(key, _)| Segment::Key(key)));
+
+    CursorContext::key(path)
+}
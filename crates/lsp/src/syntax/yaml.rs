@@ -0,0 +1,197 @@
+use super::{CursorContext, KeyNode, Segment, ends_in_quote, indent_of, lines_with_offsets};
+
+struct Entry {
+    indent: usize,
+    segment: Segment,
+}
+
+/// Parse a mapping key at the start of the content. Returns the key,
+/// the byte length of the key, and the value that follows the colon.
+fn parse_key(content: &str) -> Option<(String, usize, &str)> {
+    let (key, len) = if let Some(inner) = content.strip_prefix(['"', '\'']) {
+        let quote = content.as_bytes()[0] as char;
+        let end = inner.find(quote)?;
+
+        (inner[..end].to_owned(), end + 2)
+    } else {
+        if content.starts_with(['#', '{', '[', '&', '*', '!', '|', '>']) {
+            return None;
+        }
+
+        let index = content
+            .match_indices(':')
+            .map(|(index, _)| index)
+            .find(|index| {
+                content[index + 1..].is_empty() || content[index + 1..].starts_with([' ', '\t'])
+            })?;
+        let key = content[..index].trim_end();
+
+        if key.is_empty() || key.contains(" #") {
+            return None;
+        }
+
+        (key.to_owned(), key.len())
+    };
+
+    let rest = content[len..].trim_start_matches([' ', '\t']);
+    let value = rest.strip_prefix(':')?;
+
+    if !value.is_empty() && !value.starts_with([' ', '\t']) {
+        return None;
+    }
+
+    Some((key, len, value.trim()))
+}
+
+#[derive(Default)]
+struct Scanner {
+    block_scalar: Option<usize>,
+    keys: Vec<KeyNode>,
+    stack: Vec<Entry>,
+}
+
+impl Scanner {
+    fn path(&self) -> Vec<Segment> {
+        self.stack
+            .iter()
+            .map(|entry| entry.segment.clone())
+            .collect()
+    }
+
+    fn pop_to(&mut self, indent: usize, inclusive: bool) {
+        while self
+            .stack
+            .last()
+            .is_some_and(|entry| entry.indent > indent || (inclusive && entry.indent == indent))
+        {
+            self.stack.pop();
+        }
+    }
+
+    fn push_item(&mut self, indent: usize, offset: usize) {
+        self.pop_to(indent, false);
+
+        match self.stack.last_mut() {
+            Some(Entry {
+                indent: last_indent,
+                segment: Segment::Index(index),
+            }) if *last_indent == indent => {
+                *index += 1;
+            }
+            _ => {
+                self.stack.push(Entry {
+                    indent,
+                    segment: Segment::Index(0),
+                });
+            }
+        };
+
+        self.keys.push(KeyNode {
+            path: self.path(),
+            span: offset..offset + 1,
+        });
+    }
+
+    /// Process list item markers at the start of the content,
+    /// and return the column and content that follows them.
+    fn items<'l>(&mut self, offset: usize, line: &'l str) -> (usize, &'l str) {
+        let mut column = indent_of(line);
+        let mut content = &line[column..];
+
+        while content == "-" || content.starts_with("- ") {
+            self.push_item(column, offset + column);
+
+            let rest = content[1..].trim_start();
+            column += content.len() - rest.len();
+            content = rest;
+        }
+
+        (column, content)
+    }
+
+    fn line(&mut self, offset: usize, line: &str) {
+        let trimmed = line.trim();
+
+        if let Some(indent) = self.block_scalar {
+            if trimmed.is_empty() || indent_of(line) > indent {
+                return;
+            }
+
+            self.block_scalar = None;
+        }
+
+        if trimmed.is_empty() || trimmed.starts_with('#') || trimmed == "---" || trimmed == "..." {
+            return;
+        }
+
+        let (column, content) = self.items(offset, line);
+
+        if let Some((key, len, value)) = parse_key(content) {
+            self.pop_to(column, true);
+            self.stack.push(Entry {
+                indent: column,
+                segment: Segment::Key(key),
+            });
+            self.keys.push(KeyNode {
+                path: self.path(),
+                span: offset + column..offset + column + len,
+            });
+
+            if value.starts_with(['|', '>']) {
+                self.block_scalar = Some(column);
+            }
+        }
+    }
+}
+
+pub fn collect_keys(text: &str) -> Vec<KeyNode> {
+    let mut scanner = Scanner::default();
+
+    for (offset, line) in lines_with_offsets(text) {
+        scanner.line(offset, line);
+    }
+
+    scanner.keys
+}
+
+pub fn cursor_context(text: &str, offset: usize) -> CursorContext {
+    let mut scanner = Scanner::default();
+    let line_start = text[..offset]
+        .rfind('\n')
+        .map(|index| index + 1)
+        .unwrap_or(0);
+
+    for (start, line) in lines_with_offsets(&text[..line_start]) {
+        scanner.line(start, line);
+    }
+
+    let before = &text[line_start..offset];
+
+    // Only whitespace, so the indentation determines the parent
+    if before.trim().is_empty() {
+        scanner.pop_to(before.len(), true);
+
+        return CursorContext::key(scanner.path());
+    }
+
+    let (column, content) = scanner.items(line_start, before);
+
+    // After a list item marker
+    if content.is_empty() {
+        return CursorContext::value(scanner.path());
+    }
+
+    scanner.pop_to(column, true);
+
+    if let Some((key, _, value)) = parse_key(content) {
+        let mut path = scanner.path();
+        path.push(Segment::Key(key));
+
+        let mut context = CursorContext::value(path);
+        context.quoted = ends_in_quote(value);
+
+        return context;
+    }
+
+    CursorContext::key(scanner.path())
+}
@@ -0,0 +1,177 @@
+use schematic_lsp::lsp_types::{Position, Uri};
+use schematic_lsp::*;
+use std::path::PathBuf;
+
+fn create_document(name: &str, text: &str) -> Document {
+    let uri: Uri = format!("file:///root/{name}").parse().unwrap();
+
+    Document::new(uri, "", text.into()).unwrap()
+}
+
+fn key_paths(document: &Document) -> Vec<String> {
+    document.keys.iter().map(|key| key.to_string()).collect()
+}
+
+fn key(value: &str) -> Segment {
+    Segment::Key(value.into())
+}
+
+#[test]
+fn detects_format_and_path() {
+    let document = create_document("some%20dir/config.yml", "");
+
+    assert_eq!(document.format, DocumentFormat::Yaml);
+    assert_eq!(
+        document.path,
+        Some(PathBuf::from("/root/some dir/config.yml"))
+    );
+
+    let uri: Uri = "untitled:Untitled-1".parse().unwrap();
+
+    assert!(Document::new(uri.clone(), "plaintext", String::new()).is_none());
+    assert_eq!(
+        Document::new(uri, "toml", String::new()).unwrap().format,
+        DocumentFormat::Toml
+    );
+}
+
+#[test]
+fn converts_positions_with_utf16() {
+    let document = create_document("config.yml", "name: 🦀\nlevel: info\n");
+
+    assert_eq!(document.offset_at(Position::new(0, 8)), 10);
+    assert_eq!(document.position_at(10), Position::new(0, 8));
+    assert_eq!(document.offset_at(Position::new(1, 2)), 13);
+    assert_eq!(document.position_at(13), Position::new(1, 2));
+}
+
+#[test]
+fn collects_json_keys() {
+    let document = create_document(
+        "config.json",
+        r#"{
+  // comment
+  "name": "app",
+  "server": { "host": "localhost" },
+  "tags": ["a", { "b": true }]
+}"#,
+    );
+
+    assert_eq!(
+        key_paths(&document),
+        [
+            "name",
+            "server",
+            "server.host",
+            "tags",
+            "tags[0]",
+            "tags[1]",
+            "tags[1].b"
+        ]
+    );
+}
+
+#[test]
+fn collects_toml_keys() {
+    let document = create_document(
+        "config.toml",
+        r#"name = "app"
+server.host = "localhost"
+tags = [
+  "a",
+]
+
+[[plugins]]
+id = "a"
+
+[[plugins]]
+id = "b"
+"#,
+    );
+
+    assert_eq!(
+        key_paths(&document),
+        [
+            "name",
+            "server",
+            "server.host",
+            "tags",
+            "plugins",
+            "plugins[0].id",
+            "plugins",
+            "plugins[1].id"
+        ]
+    );
+}
+
+#[test]
+fn collects_yaml_keys() {
+    let document = create_document(
+        "config.yml",
+        r#"name: app
+description: |
+  not: a key
+server:
+  host: localhost
+plugins:
+  - id: a
+    enabled: true
+  - id: b
+"#,
+    );
+
+    assert_eq!(
+        key_paths(&document),
+        [
+            "name",
+            "description",
+            "server",
+            "server.host",
+            "plugins",
+            "plugins[0]",
+            "plugins[0].id",
+            "plugins[0].enabled",
+            "plugins[1]",
+            "plugins[1].id"
+        ]
+    );
+}
+
+#[test]
+fn determines_cursor_context() {
+    let document = create_document("config.yml", "server:\n  host: \nplugins:\n  - ");
+
+    assert_eq!(
+        document.context_at(Position::new(1, 8)),
+        CursorContext::value(vec![key("server"), key("host")])
+    );
+    assert_eq!(
+        document.context_at(Position::new(1, 0)),
+        CursorContext::key(vec![])
+    );
+    assert_eq!(
+        document.context_at(Position::new(3, 4)),
+        CursorContext::value(vec![key("plugins"), Segment::Index(0)])
+    );
+
+    let document = create_document("config.toml", "[server]\nhost = \"\"\n");
+    let mut context = CursorContext::value(vec![key("server"), key("host")]);
+    context.quoted = true;
+
+    assert_eq!(document.context_at(Position::new(1, 8)), context);
+    assert_eq!(
+        document.context_at(Position::new(0, 1)),
+        CursorContext::key(vec![])
+    );
+
+    let document = create_document("config.json", r#"{ "tags": [1, ], "server": {  } }"#);
+
+    assert_eq!(
+        document.context_at(Position::new(0, 14)),
+        CursorContext::value(vec![key("tags"), Segment::Index(1)])
+    );
+    assert_eq!(
+        document.context_at(Position::new(0, 29)),
+        CursorContext::key(vec![key("server")])
+    );
+}
@@ -0,0 +1,377 @@
+#![allow(dead_code)]
+
+use schematic::*;
+use schematic_lsp::ConfigLanguageServer;
+use schematic_lsp::lsp_server::{Connection, Message, Notification, Request, RequestId};
+use schematic_lsp::lsp_types::notification::{
+    DidChangeTextDocument, DidCloseTextDocument, DidOpenTextDocument, Exit, Initialized,
+    Notification as _, PublishDiagnostics,
+};
+use schematic_lsp::lsp_types::request::{Completion, HoverRequest, Initialize, Shutdown};
+use schematic_lsp::lsp_types::*;
+use starbase_sandbox::create_empty_sandbox;
+use std::path::Path;
+use std::thread::{self, JoinHandle};
+
+derive_enum!(
+    #[derive(ConfigEnum, Default)]
+    pub enum LogLevel {
+        #[default]
+        Info,
+        Debug,
+    }
+);
+
+#[derive(Config)]
+pub struct ServerConfig {
+    /// Host name to bind to.
+    host: String,
+    #[setting(default = 8080, validate(range(min = 1)))]
+    port: u16,
+}
+
+#[derive(Config)]
+pub struct AppConfig {
+    #[setting(extend)]
+    extends: Option<String>,
+    /// Name of the application.
+    #[setting(validate(length(min = 3)))]
+    name: String,
+    /// Level of logs to output.
+    #[deprecated]
+    level: LogLevel,
+    #[setting(nested)]
+    server: ServerConfig,
+    tags: Vec<String>,
+}
+
+struct TestClient {
+    connection: Connection,
+    handle: Option<JoinHandle<()>>,
+    next_id: i32,
+}
+
+impl TestClient {
+    fn new() -> Self {
+        let (server, connection) = Connection::memory();
+
+        let handle = thread::spawn(move || {
+            ConfigLanguageServer::new::<AppConfig>()
+                .run(server)
+                .unwrap();
+        });
+
+        let mut client = Self {
+            connection,
+            handle: Some(handle),
+            next_id: 0,
+        };
+
+        let result = client.request::<Initialize>(InitializeParams::default());
+
+        assert!(result.capabilities.completion_provider.is_some());
+        assert!(result.capabilities.hover_provider.is_some());
+
+        client.notify::<Initialized>(InitializedParams {});
+        client
+    }
+
+    fn request<R: request::Request>(&mut self, params: R::Params) -> R::Result {
+        self.next_id += 1;
+
+        let id = RequestId::from(self.next_id);
+
+        self.connection
+            .sender
+            .send(Message::Request(Request::new(
+                id.clone(),
+                R::METHOD.into(),
+                params,
+            )))
+            .unwrap();
+
+        loop {
+            if let Message::Response(response) = self.connection.receiver.recv().unwrap()
+                && response.id == id
+            {
+                assert!(response.error.is_none(), "{:?}", response.error);
+
+                return serde_json::from_value(response.result.unwrap_or_default()).unwrap();
+            }
+        }
+    }
+
+    fn notify<N: notification::Notification>(&self, params: N::Params) {
+        self.connection
+            .sender
+            .send(Message::Notification(Notification::new(
+                N::METHOD.into(),
+                params,
+            )))
+            .unwrap();
+    }
+
+    fn receive_diagnostics(&self) -> Vec<(u32, u32, String)> {
+        loop {
+            if let Message::Notification(notification) = self.connection.receiver.recv().unwrap()
+                && notification.method == PublishDiagnostics::METHOD
+            {
+                let params: PublishDiagnosticsParams =
+                    serde_json::from_value(notification.params).unwrap();
+
+                return params
+                    .diagnostics
+                    .into_iter()
+                    .map(|diag| {
+                        (
+                            diag.range.start.line,
+                            diag.range.start.character,
+                            diag.message,
+                        )
+                    })
+                    .collect();
+            }
+        }
+    }
+
+    fn open(&self, uri: &Uri, language_id: &str, text: &str) -> Vec<(u32, u32, String)> {
+        self.notify::<DidOpenTextDocument>(DidOpenTextDocumentParams {
+            text_document: TextDocumentItem::new(uri.clone(), language_id.into(), 1, text.into()),
+        });
+
+        self.receive_diagnostics()
+    }
+
+    fn complete(&mut self, uri: &Uri, line: u32, character: u32) -> Vec<String> {
+        let response = self.request::<Completion>(CompletionParams {
+            text_document_position: TextDocumentPositionParams::new(
+                TextDocumentIdentifier::new(uri.clone()),
+                Position::new(line, character),
+            ),
+            work_done_progress_params: Default::default(),
+            partial_result_params: Default::default(),
+            context: None,
+        });
+
+        match response {
+            Some(CompletionResponse::Array(items)) => {
+                items.into_iter().map(|item| item.label).collect()
+            }
+            _ => vec![],
+        }
+    }
+
+    fn hover(&mut self, uri: &Uri, line: u32, character: u32) -> Option<String> {
+        self.request::<HoverRequest>(HoverParams {
+            text_document_position_params: TextDocumentPositionParams::new(
+                TextDocumentIdentifier::new(uri.clone()),
+                Position::new(line, character),
+            ),
+            work_done_progress_params: Default::default(),
+        })
+        .map(|hover| match hover.contents {
+            HoverContents::Markup(markup) => markup.value,
+            _ => unimplemented!(),
+        })
+    }
+}
+
+impl Drop for TestClient {
+    fn drop(&mut self) {
+        self.request::<Shutdown>(());
+        self.notify::<Exit>(());
+
+        if let Some(handle) = self.handle.take() {
+            handle.join().unwrap();
+        }
+    }
+}
+
+fn untitled(name: &str) -> Uri {
+    format!("untitled:{name}").parse().unwrap()
+}
+
+fn file_uri(path: &Path) -> Uri {
+    format!("file://{}", path.display()).parse().unwrap()
+}
+
+mod completion {
+    use super::*;
+
+    #[test]
+    fn completes_keys() {
+        let mut client = TestClient::new();
+        let uri = untitled("config.yml");
+
+        client.open(&uri, "yaml", "name: app\nserver:\n  \n");
+
+        assert_eq!(
+            client.complete(&uri, 1, 0),
+            ["extends", "level", "name", "server", "tags"]
+        );
+        assert_eq!(client.complete(&uri, 2, 2), ["host", "port"]);
+    }
+
+    #[test]
+    fn completes_enum_values() {
+        let mut client = TestClient::new();
+
+        let uri = untitled("config.yml");
+        client.open(&uri, "yaml", "level: ");
+
+        assert_eq!(client.complete(&uri, 0, 7), ["info", "debug"]);
+
+        let uri = untitled("config.toml");
+        client.open(&uri, "toml", "level = ");
+
+        assert_eq!(client.complete(&uri, 0, 8), ["\"info\"", "\"debug\""]);
+
+        let uri = untitled("config.json");
+        client.open(&uri, "json", "{ \"level\": \"\" }");
+
+        assert_eq!(client.complete(&uri, 0, 12), ["info", "debug"]);
+    }
+
+    #[test]
+    fn completes_nested_keys_in_each_format() {
+        let mut client = TestClient::new();
+
+        let uri = untitled("config.json");
+        client.open(&uri, "json", "{ \"server\": { \"\" } }");
+
+        assert_eq!(client.complete(&uri, 0, 15), ["host", "port"]);
+
+        let uri = untitled("config.toml");
+        client.open(&uri, "toml", "name = \"app\"\n\n[server]\n");
+
+        assert_eq!(client.complete(&uri, 3, 0), ["host", "port"]);
+        assert_eq!(
+            client.complete(&uri, 1, 0),
+            ["extends", "level", "name", "server", "tags"]
+        );
+    }
+}
+
+mod hover {
+    use super::*;
+
+    #[test]
+    fn renders_field_docs() {
+        let mut client = TestClient::new();
+        let uri = untitled("config.yml");
+
+        client.open(
+            &uri,
+            "yaml",
+            "name: app\nlevel: info\nserver:\n  host: localhost\n",
+        );
+
+        assert_eq!(
+            client.hover(&uri, 0, 2).unwrap(),
+            "```\nname: string\n```\n\nName of the application."
+        );
+        assert_eq!(
+            client.hover(&uri, 3, 3).unwrap(),
+            "```\nhost: string\n```\n\nHost name to bind to."
+        );
+        assert!(client.hover(&uri, 1, 2).unwrap().contains("**Deprecated**"));
+        assert_eq!(client.hover(&uri, 0, 7), None);
+    }
+}
+
+mod diagnostics {
+    use super::*;
+
+    #[test]
+    fn reports_parse_errors() {
+        let client = TestClient::new();
+
+        assert_eq!(
+            client.open(
+                &untitled("config.yml"),
+                "yaml",
+                "name: app\nlevel: unknown\n"
+            ),
+            [(
+                1,
+                0,
+                "unknown variant `unknown`, expected `info` or `debug`".into()
+            )]
+        );
+    }
+
+    #[test]
+    fn reports_validation_errors_on_keys() {
+        let client = TestClient::new();
+        let uri = untitled("config.json");
+
+        assert_eq!(
+            client.open(
+                &uri,
+                "json",
+                "{\n  \"name\": \"a\",\n  \"server\": {\n    \"port\": 0\n  }\n}"
+            ),
+            [
+                (1, 2, "length is lower than 3".into()),
+                (3, 4, "lower than 1".into())
+            ]
+        );
+    }
+
+    #[test]
+    fn updates_on_change_and_clears_on_close() {
+        let client = TestClient::new();
+        let uri = untitled("config.toml");
+
+        assert_eq!(client.open(&uri, "toml", "name = \"a\"").len(), 1);
+
+        client.notify::<DidChangeTextDocument>(DidChangeTextDocumentParams {
+            text_document: VersionedTextDocumentIdentifier::new(uri.clone(), 2),
+            content_changes: vec![TextDocumentContentChangeEvent {
+                range: None,
+                range_length: None,
+                text: "name = \"app\"".into(),
+            }],
+        });
+
+        assert_eq!(client.receive_diagnostics(), []);
+
+        client.notify::<DidCloseTextDocument>(DidCloseTextDocumentParams {
+            text_document: TextDocumentIdentifier::new(uri),
+        });
+
+        assert_eq!(client.receive_diagnostics(), []);
+    }
+
+    #[test]
+    fn loads_extended_files_relative_to_document() {
+        let sandbox = create_empty_sandbox();
+        sandbox.create_file("base.yml", "server:\n  port: 0\n");
+        sandbox.create_file("broken.yml", "server: [\n");
+
+        let client = TestClient::new();
+        let path = sandbox.path().join("config.yml");
+
+        // Document does not exist on disk yet, and errors within extended
+        // files are reported where the file is referenced
+        let diagnostics = client.open(&file_uri(&path), "yaml", "extends: ./base.yml\nname: app\n");
+
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!((diagnostics[0].0, diagnostics[0].1), (0, 11));
+        assert!(
+            diagnostics[0]
+                .2
+                .ends_with("base.yml: server.port: lower than 1")
+        );
+
+        let diagnostics = client.open(
+            &file_uri(&path),
+            "yaml",
+            "extends: ./broken.yml\nname: app\n",
+        );
+
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!((diagnostics[0].0, diagnostics[0].1), (0, 11));
+        assert!(diagnostics[0].2.contains("broken.yml"));
+    }
+}
@@ -1,6 +1,6 @@
-use crate::common::{Field, Variant};
+use crate::common::{Field, FieldValue, Variant};
 use proc_macro2::TokenStream;
-use quote::{ToTokens, quote};
+use quote::{ToTokens, format_ident, quote};
 
 pub enum Container<'l> {
     NamedStruct { fields: Vec<Field<'l>> },
@@ -17,6 +17,14 @@ impl Container<'_> {
         }
     }
 
+    pub fn has_secrets(&self) -> bool {
+        match self {
+            Self::NamedStruct { fields } => fields.iter().any(|v| v.is_secret()),
+            Self::UnnamedStruct { fields } => fields.iter().any(|v| v.is_secret()),
+            Self::Enum { variants } => variants.iter().any(|v| v.is_secret()),
+        }
+    }
+
     pub fn generate_settings_metadata(&self) -> TokenStream {
         let mut settings = vec![];
 
@@ -49,12 +57,40 @@ impl Container<'_> {
                     } else {
                         quote!(None)
                     };
+                    let nested_collection = matches!(
+                        field.value_type,
+                        FieldValue::NestedList { .. } | FieldValue::NestedMap { .. }
+                    );
+                    let merge_key = match &field.args.merge_key {
+                        Some(key) => {
+                            let value = field.value_type.get_config_type();
+                            let key = format_ident!("{key}");
+
+                            quote! {
+                                Some({
+                                    fn get_merge_key(value: &schematic::serde_content::Value) -> Option<String> {
+                                        schematic::internal::get_merge_key::<<#value as schematic::Config>::Partial>(
+                                            value,
+                                            |item| item.#key.as_ref().map(|value| value.to_string()),
+                                        )
+                                    }
+
+                                    get_merge_key
+                                })
+                            }
+                        }
+                        None => quote!(None),
+                    };
+                    let skip_interpolation = !field.is_interpolated();
                     let type_alias = format_alias(field.value.to_token_stream());
 
                     settings.push(quote! {
                         (#name.into(), schematic::ConfigSetting {
                             env_key: #env_key,
+                            merge_key: #merge_key,
                             nested: #nested,
+                            nested_collection: #nested_collection,
+                            skip_interpolation: #skip_interpolation,
                             type_alias: #type_alias.into(),
                         }),
                     });
@@ -40,21 +40,26 @@ pub struct FieldArgs {
     pub exclude: bool,
 
     // config
+    pub clear_on_null: bool,
     #[darling(with = preserve_str_literal, map = "Some")]
     pub default: Option<Expr>,
     #[cfg(feature = "env")]
     pub env: Option<String>,
     #[cfg(feature = "extends")]
     pub extend: bool,
+    pub interpolate: Option<bool>,
     pub merge: Option<ExprPath>,
+    pub merge_key: Option<String>,
     pub nested: bool,
     #[cfg(feature = "env")]
     pub parse_env: Option<ExprPath>,
     pub required: bool,
+    pub secret: bool,
     pub transform: Option<ExprPath>,
     #[cfg(feature = "validate")]
-    pub validate: Option<Expr>,
+    pub validate: Option<FieldValidate>,
     pub partial: PartialAttr,
+    pub relative_path: bool,
 
     // serde
     pub alias: Option<String>,
@@ -65,6 +70,128 @@ pub struct FieldArgs {
     pub skip_serializing: bool,
 }
 
+// #[setting(validate(length(min = 1, max = 10), range(min = 1, max = 10)))]
+#[cfg(feature = "validate")]
+#[derive(darling::FromMeta, Default)]
+#[darling(default)]
+pub struct ValidateBounds {
+    pub min: Option<Expr>,
+    pub max: Option<Expr>,
+}
+
+// #[setting(validate = func)], #[setting(validate(length(min = 1), format = "...", func))]
+#[cfg(feature = "validate")]
+#[derive(Default)]
+pub struct FieldValidate {
+    pub format: Option<syn::LitStr>,
+    pub funcs: Vec<Expr>,
+    pub length: Option<ValidateBounds>,
+    pub pattern: Option<syn::LitStr>,
+    pub range: Option<ValidateBounds>,
+}
+
+#[cfg(feature = "validate")]
+impl FieldValidate {
+    #[cfg(feature = "schema")]
+    pub fn has_schema_constraints(&self) -> bool {
+        self.get_format().is_some()
+            || self.length.is_some()
+            || self.pattern.is_some()
+            || self.range.is_some()
+    }
+
+    // Explicit formats take precedence over those inferred from the built-in
+    // `validate::fs` and `validate::net` functions. Macros can't resolve imports,
+    // so only the full `schematic::validate::*` paths are inferred.
+    #[cfg(feature = "schema")]
+    pub fn get_format(&self) -> Option<String> {
+        if let Some(format) = &self.format {
+            return Some(format.value());
+        }
+
+        self.funcs.iter().find_map(|expr| {
+            let path = match expr {
+                Expr::Call(call) => match call.func.as_ref() {
+                    Expr::Path(path) => &path.path,
+                    _ => return None,
+                },
+                Expr::Path(path) => &path.path,
+                _ => return None,
+            };
+
+            let segments = path
+                .segments
+                .iter()
+                .map(|segment| segment.ident.to_string())
+                .collect::<Vec<_>>();
+
+            let [krate, validate, module, func] = segments.as_slice() else {
+                return None;
+            };
+
+            if krate != "schematic" || validate != "validate" {
+                return None;
+            }
+
+            let format = match (module.as_str(), func.as_str()) {
+                ("fs", "is_absolute") => "absolute-path",
+                ("fs", "is_dir") => "dir-path",
+                ("fs", "is_file") => "file-path",
+                ("fs", "exists" | "extension_in" | "is_readable") => "path",
+                ("net", "cidr") => "cidr",
+                ("net", "hostname") => "hostname",
+                ("net", "port" | "port_in_range") => "port",
+                ("net", "socket_addr") => "socket-address",
+                _ => return None,
+            };
+
+            Some(format.to_owned())
+        })
+    }
+}
+
+#[cfg(feature = "validate")]
+impl darling::FromMeta for FieldValidate {
+    fn from_expr(expr: &Expr) -> darling::Result<Self> {
+        Ok(Self {
+            funcs: vec![expr.clone()],
+            ..Default::default()
+        })
+    }
+
+    fn from_list(items: &[darling::ast::NestedMeta]) -> darling::Result<Self> {
+        use darling::ast::NestedMeta;
+        use syn::Meta;
+
+        let mut validate = Self::default();
+
+        for item in items {
+            let meta = match item {
+                NestedMeta::Meta(meta) => meta,
+                NestedMeta::Lit(lit) => return Err(darling::Error::unexpected_lit_type(lit)),
+            };
+
+            if meta.path().is_ident("length") {
+                validate.length = Some(ValidateBounds::from_meta(meta)?);
+            } else if meta.path().is_ident("range") {
+                validate.range = Some(ValidateBounds::from_meta(meta)?);
+            } else if meta.path().is_ident("format") {
+                validate.format = Some(syn::LitStr::from_meta(meta)?);
+            } else if meta.path().is_ident("pattern") {
+                validate.pattern = Some(syn::LitStr::from_meta(meta)?);
+            } else if let Meta::NameValue(_) = meta {
+                return Err(darling::Error::unknown_field_path(meta.path()));
+            } else {
+                validate
+                    .funcs
+                    .push(syn::parse2::<Expr>(meta.to_token_stream())?);
+            }
+        }
+
+        Ok(validate)
+    }
+}
+
 pub struct Field<'l> {
     pub args: FieldArgs,
     pub serde_args: FieldSerdeArgs,
@@ -75,6 +202,7 @@ pub struct Field<'l> {
     pub value: &'l Type,
     pub value_type: FieldValue<'l>,
     pub env_prefix: Option<String>,
+    pub env_nested_delimiter: Option<String>,
 }
 
 impl Field<'_> {
@@ -96,6 +224,7 @@ impl Field<'_> {
             args,
             serde_args,
             env_prefix: None,
+            env_nested_delimiter: None,
         };
 
         if field.args.default.is_some() && field.is_nested() {
@@ -106,9 +235,52 @@ impl Field<'_> {
             panic!("Cannot use required with non-optional settings.");
         }
 
+        if field.args.relative_path && field.is_nested() {
+            panic!("Cannot use relative_path with `nested` configs.");
+        }
+
+        if field.args.merge_key.is_some() && field.args.merge.is_some() {
+            panic!("Cannot use `merge_key` and `merge` together.");
+        }
+
+        if field.is_secret() && field.is_nested() {
+            panic!("Cannot use secret with `nested` configs.");
+        }
+
+        #[cfg(feature = "validate")]
+        if let Some(validate) = &field.args.validate {
+            for (name, bounds) in [("length", &validate.length), ("range", &validate.range)] {
+                if bounds
+                    .as_ref()
+                    .is_some_and(|bounds| bounds.min.is_none() && bounds.max.is_none())
+                {
+                    panic!("The `{name}` validation rule requires a `min` or `max`.");
+                }
+            }
+        }
+
+        if field.is_clearable() {
+            if !field.is_nullable() {
+                panic!("Cannot use `clear_on_null` with non-optional settings.");
+            }
+
+            if field.is_nested() {
+                panic!("Cannot use `clear_on_null` with `nested` configs.");
+            }
+
+            #[cfg(feature = "extends")]
+            if field.is_extendable() {
+                panic!("Cannot use `clear_on_null` with `extend` settings.");
+            }
+        }
+
         field
     }
 
+    pub fn is_clearable(&self) -> bool {
+        self.args.clear_on_null
+    }
+
     #[cfg(feature = "schema")]
     pub fn is_excluded(&self) -> bool {
         self.args.exclude
@@ -124,6 +296,10 @@ impl Field<'_> {
         self.serde_args.flatten || self.args.flatten
     }
 
+    pub fn is_interpolated(&self) -> bool {
+        self.args.interpolate.unwrap_or(true)
+    }
+
     pub fn is_nested(&self) -> bool {
         self.args.nested
     }
@@ -141,6 +317,10 @@ impl Field<'_> {
         self.args.required
     }
 
+    pub fn is_secret(&self) -> bool {
+        self.args.secret
+    }
+
     #[cfg(feature = "schema")]
     pub fn is_skipped(&self) -> bool {
         self.args.skip || self.serde_args.skip
@@ -239,10 +419,19 @@ impl Field<'_> {
                 meta.push(quote! { skip_serializing });
             } else {
                 meta.push(quote! { skip_serializing_if = "Option::is_none" });
+
+                if self.is_secret() {
+                    meta.push(quote! { serialize_with = "schematic::internal::serialize_secret" });
+                }
             }
 
             if self.args.skip_deserializing || self.serde_args.skip_deserializing {
                 meta.push(quote! { skip_deserializing });
+            } else if self.is_clearable() {
+                meta.push(quote! { default });
+                meta.push(
+                    quote! { deserialize_with = "schematic::internal::deserialize_clearable" },
+                );
             }
         }
 
@@ -268,6 +457,7 @@ impl Field<'_> {
         let flatten = map_bool_field_quote("flatten", self.is_flatten());
         let nullable = map_bool_field_quote("nullable", self.is_nullable());
         let optional = map_bool_field_quote("optional", self.is_optional());
+        let secret = map_bool_field_quote("secret", self.is_secret());
         let comment = map_option_field_quote("comment", extract_comment(&self.attrs));
         let description = map_option_field_quote("description", extract_comment(&self.attrs));
         let deprecated = map_option_field_quote("deprecated", extract_deprecated(&self.attrs));
@@ -304,6 +494,52 @@ impl Field<'_> {
             inner_schema = quote! { schema.infer_with_default::<#value>(#lit_value) };
         }
 
+        // Declarative validation rules also constrain the schema
+        #[cfg(feature = "validate")]
+        if let Some(validate) = &self.args.validate
+            && validate.has_schema_constraints()
+        {
+            let mut constraints = vec![];
+
+            if let Some(length) = &validate.length {
+                let min = map_option_quote(length.min.as_ref(), |min| quote! { #min });
+                let max = map_option_quote(length.max.as_ref(), |max| quote! { #max });
+
+                constraints.push(quote! {
+                    schema.set_length_constraints(#min, #max);
+                });
+            }
+
+            if let Some(range) = &validate.range {
+                let min = map_option_quote(range.min.as_ref(), |min| quote! { (#min) as f64 });
+                let max = map_option_quote(range.max.as_ref(), |max| quote! { (#max) as f64 });
+
+                constraints.push(quote! {
+                    schema.set_range_constraints(#min, #max);
+                });
+            }
+
+            if let Some(pattern) = &validate.pattern {
+                constraints.push(quote! {
+                    schema.set_pattern_constraint(#pattern);
+                });
+            }
+
+            if let Some(format) = validate.get_format() {
+                constraints.push(quote! {
+                    schema.set_format(#format);
+                });
+            }
+
+            inner_schema = quote! {
+                {
+                    let mut schema = #inner_schema;
+                    #(#constraints)*
+                    schema
+                }
+            };
+        }
+
         // Struct field (named)
         if as_field {
             let name = self.get_name(Some(&self.casing_format));
@@ -315,6 +551,7 @@ impl Field<'_> {
                 && hidden.is_none()
                 && nullable.is_none()
                 && optional.is_none()
+                && secret.is_none()
             {
                 quote! {
                     SchemaField::new(#inner_schema)
@@ -331,6 +568,7 @@ impl Field<'_> {
                         #hidden
                         #nullable
                         #optional
+                        #secret
                         field
                     }
                 }
@@ -358,6 +596,17 @@ impl Field<'_> {
     }
 }
 
+#[cfg(all(feature = "schema", feature = "validate"))]
+fn map_option_quote(value: Option<&Expr>, op: impl Fn(&Expr) -> TokenStream) -> TokenStream {
+    match value {
+        Some(value) => {
+            let value = op(value);
+            quote! { Some(#value) }
+        }
+        None => quote! { None },
+    }
+}
+
 impl ToTokens for Field<'_> {
     fn to_tokens(&self, tokens: &mut TokenStream) {
         let value = &self.value_type;
@@ -375,6 +624,13 @@ impl ToTokens for Field<'_> {
         let partial = &self.args.partial;
         attrs.push(quote! {#partial});
 
+        // Clearable settings are tri-state: unset, explicitly null, or a value
+        let value = if self.is_clearable() {
+            quote! { Option<#value> }
+        } else {
+            quote! { #value }
+        };
+
         if let Some(name) = &self.name {
             tokens.extend(quote! {
                 #(#attrs)*
@@ -4,6 +4,8 @@ use darling::ast::NestedMeta;
 use darling::{FromDeriveInput, FromMeta};
 use proc_macro2::{Ident, TokenStream};
 use quote::{ToTokens, quote};
+#[cfg(feature = "validate")]
+use syn::Expr;
 use syn::{Attribute, Data, DeriveInput, ExprPath, Fields, Generics};
 
 // #[serde()]
@@ -39,6 +41,10 @@ pub struct MacroArgs {
     pub partial: PartialAttr,
     #[cfg(feature = "env")]
     pub env_prefix: Option<String>,
+    #[cfg(feature = "env")]
+    pub env_nested_delimiter: Option<String>,
+    #[cfg(feature = "validate")]
+    pub validate: Option<ContainerValidate>,
 
     // serde
     pub rename: Option<String>,
@@ -69,6 +75,30 @@ impl FromMeta for PartialAttr {
     }
 }
 
+// #[config(validate = func)], #[config(validate(func, func))]
+#[cfg(feature = "validate")]
+pub struct ContainerValidate(pub Vec<Expr>);
+
+#[cfg(feature = "validate")]
+impl FromMeta for ContainerValidate {
+    fn from_expr(expr: &Expr) -> darling::Result<Self> {
+        Ok(Self(vec![expr.clone()]))
+    }
+
+    fn from_list(items: &[NestedMeta]) -> darling::Result<Self> {
+        items
+            .iter()
+            .map(|item| match item {
+                NestedMeta::Meta(meta) => {
+                    syn::parse2::<Expr>(meta.to_token_stream()).map_err(darling::Error::from)
+                }
+                NestedMeta::Lit(lit) => Err(darling::Error::unexpected_lit_type(lit)),
+            })
+            .collect::<darling::Result<Vec<_>>>()
+            .map(Self)
+    }
+}
+
 pub struct Macro<'l> {
     pub args: MacroArgs,
     pub serde_args: ContainerSerdeArgs,
@@ -109,6 +139,10 @@ impl<'l> Macro<'l> {
                                 field.casing_format.clone_from(&casing_format);
                                 #[cfg(feature = "env")]
                                 field.env_prefix.clone_from(&args.env_prefix);
+                                #[cfg(feature = "env")]
+                                field
+                                    .env_nested_delimiter
+                                    .clone_from(&args.env_nested_delimiter);
                                 field
                             })
                             .collect::<Vec<_>>(),
@@ -131,6 +165,10 @@ impl<'l> Macro<'l> {
                                 field.casing_format.clone_from(&casing_format);
                                 #[cfg(feature = "env")]
                                 field.env_prefix.clone_from(&args.env_prefix);
+                                #[cfg(feature = "env")]
+                                field
+                                    .env_nested_delimiter
+                                    .clone_from(&args.env_nested_delimiter);
                                 field
                             })
                             .collect::<Vec<_>>(),
@@ -29,6 +29,7 @@ pub struct VariantArgs {
     pub merge: Option<ExprPath>,
     pub nested: bool,
     pub required: bool,
+    pub secret: bool,
     #[cfg(feature = "validate")]
     pub validate: Option<syn::Expr>,
 
@@ -52,7 +53,7 @@ pub struct Variant<'l> {
 
 impl Variant<'_> {
     pub fn from(var: &NativeVariant) -> Variant<'_> {
-        Variant {
+        let variant = Variant {
             args: VariantArgs::from_attributes(&var.attrs).unwrap_or_default(),
             serde_args: FieldSerdeArgs::from_attributes(&var.attrs).unwrap_or_default(),
             attrs: extract_common_attrs(&var.attrs),
@@ -60,7 +61,13 @@ impl Variant<'_> {
             tagged_format: TaggedFormat::Unit,
             name: &var.ident,
             value: var,
+        };
+
+        if variant.is_secret() && variant.is_nested() {
+            panic!("Cannot use secret with `nested` configs.");
         }
+
+        variant
     }
 
     pub fn is_default(&self) -> bool {
@@ -80,6 +87,10 @@ impl Variant<'_> {
         self.args.required
     }
 
+    pub fn is_secret(&self) -> bool {
+        self.args.secret
+    }
+
     pub fn get_name(&self, casing_format: Option<&str>) -> String {
         match &self.args.rename {
             Some(local) => local.to_owned(),
@@ -287,6 +298,11 @@ impl ToTokens for Variant<'_> {
 
                         if self.is_nested() {
                             quote! { #vis <#ty as schematic::Config>::Partial }
+                        } else if self.is_secret() {
+                            quote! {
+                                #[serde(serialize_with = "schematic::internal::serialize_secret_value")]
+                                #vis #ty
+                            }
                         } else {
                             quote! { #vis #ty }
                         }
@@ -55,7 +55,7 @@ impl Container<'_> {
         }
     }
 
-    pub fn generate_env_values(&self) -> TokenStream {
+    pub fn generate_env_values(&self, nested_stmt: Option<TokenStream>) -> TokenStream {
         match self {
             Self::NamedStruct {
                 fields: settings, ..
@@ -66,6 +66,7 @@ impl Container<'_> {
                 let env_stmts = settings
                     .iter()
                     .filter_map(|s| s.generate_env_statement())
+                    .chain(nested_stmt)
                     .collect::<Vec<_>>();
 
                 if env_stmts.is_empty() {
@@ -93,6 +94,22 @@ impl Container<'_> {
         }
     }
 
+    pub fn get_explicit_env_vars(&self) -> Vec<String> {
+        match self {
+            Self::NamedStruct {
+                fields: settings, ..
+            }
+            | Self::UnnamedStruct {
+                fields: settings, ..
+            } => settings
+                .iter()
+                .filter(|s| !s.is_nested() && s.generate_env_statement().is_some())
+                .filter_map(|s| s.get_env_var())
+                .collect(),
+            Self::Enum { .. } => vec![],
+        }
+    }
+
     pub fn generate_extends_from(&self) -> TokenStream {
         #[cfg(feature = "extends")]
         match self {
@@ -187,7 +204,7 @@ impl Container<'_> {
 
                 let env_statement = if cfg!(feature = "env") {
                     quote! {
-                        if let Some(data) = Self::env_values()? {
+                        if let Some(data) = Self::env_values_with(context, env)? {
                             partial.merge(context, data)?;
                         }
                     }
@@ -277,6 +294,47 @@ impl Container<'_> {
         }
     }
 
+    pub fn generate_resolve_paths(&self) -> Option<TokenStream> {
+        match self {
+            Self::NamedStruct {
+                fields: settings, ..
+            }
+            | Self::UnnamedStruct {
+                fields: settings, ..
+            } => {
+                let resolve_stmts = settings
+                    .iter()
+                    .filter_map(|s| s.generate_resolve_paths_statement())
+                    .collect::<Vec<_>>();
+
+                if resolve_stmts.is_empty() {
+                    None
+                } else {
+                    Some(quote! {
+                        #(#resolve_stmts)*
+                    })
+                }
+            }
+            Self::Enum { variants } => {
+                let resolve_stmts = variants
+                    .iter()
+                    .filter_map(|s| s.generate_resolve_paths_statement())
+                    .collect::<Vec<_>>();
+
+                if resolve_stmts.is_empty() {
+                    None
+                } else {
+                    Some(quote! {
+                        match self {
+                            #(#resolve_stmts)*
+                            _ => {}
+                        };
+                    })
+                }
+            }
+        }
+    }
+
     pub fn generate_validate(&self) -> TokenStream {
         match self {
             Self::NamedStruct {
@@ -363,33 +421,103 @@ impl Container<'_> {
         }
     }
 
+    pub fn generate_debug(&self, name: &Ident, partial: bool) -> Option<TokenStream> {
+        if !self.has_secrets() {
+            return None;
+        }
+
+        let name_quoted = name.to_string();
+        let inner = match self {
+            Self::NamedStruct {
+                fields: settings, ..
+            } => {
+                let stmts = settings
+                    .iter()
+                    .map(|s| s.generate_debug_statement(partial))
+                    .collect::<Vec<_>>();
+
+                quote! {
+                    f.debug_struct(#name_quoted)
+                        #(#stmts)*
+                        .finish()
+                }
+            }
+            Self::UnnamedStruct {
+                fields: settings, ..
+            } => {
+                let stmts = settings
+                    .iter()
+                    .map(|s| s.generate_debug_statement(partial))
+                    .collect::<Vec<_>>();
+
+                quote! {
+                    f.debug_tuple(#name_quoted)
+                        #(#stmts)*
+                        .finish()
+                }
+            }
+            Self::Enum { variants } => {
+                let stmts = variants
+                    .iter()
+                    .map(|v| v.generate_debug_statement())
+                    .collect::<Vec<_>>();
+
+                quote! {
+                    match self {
+                        #(#stmts)*
+                    }
+                }
+            }
+        };
+
+        Some(quote! {
+            impl std::fmt::Debug for #name {
+                fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                    #inner
+                }
+            }
+        })
+    }
+
     pub fn generate_partial(
         &self,
         partial_name: &Ident,
         partial_attrs: &[TokenStream],
         is_untagged: bool,
     ) -> TokenStream {
+        // Secrets must be redacted, so implement `Debug` manually
+        let debug_impl = self.generate_debug(partial_name, true);
+        let debug = if debug_impl.is_some() {
+            quote! {}
+        } else {
+            quote! { Debug, }
+        };
+
         match self {
             Self::NamedStruct {
                 fields: settings, ..
             } => {
                 quote! {
-                    #[derive(Clone, Debug, Default, PartialEq, serde::Deserialize, serde::Serialize)]
+                    #[derive(Clone, #debug Default, PartialEq, serde::Deserialize, serde::Serialize)]
                     #(#partial_attrs)*
                     pub struct #partial_name {
                         #(#settings)*
                     }
+
+                    #debug_impl
                 }
             }
             Self::UnnamedStruct {
                 fields: settings, ..
             } => {
                 quote! {
-                    #[derive(Clone, Debug, Default, PartialEq, serde::Deserialize, serde::Serialize)]
+                    #[derive(Clone, #debug Default, PartialEq, serde::Deserialize, serde::Serialize)]
                     #(#partial_attrs)*
                     pub struct #partial_name(
                         #(#settings)*
                     );
+
+                    #debug_impl
                 }
             }
             Self::Enum { variants } => {
@@ -412,12 +540,14 @@ impl Container<'_> {
                         self.generate_untagged_deserialize(partial_name, variants);
 
                     quote! {
-                        #[derive(Clone, Debug, PartialEq, serde::Serialize)]
+                        #[derive(Clone, #debug PartialEq, serde::Serialize)]
                         #(#partial_attrs)*
                         pub enum #partial_name {
                             #(#variants)*
                         }
 
+                        #debug_impl
+
                         impl Default for #partial_name {
                             fn default() -> Self {
                                 #default_impl
@@ -428,12 +558,14 @@ impl Container<'_> {
                     }
                 } else {
                     quote! {
-                        #[derive(Clone, Debug, PartialEq, serde::Deserialize, serde::Serialize)]
+                        #[derive(Clone, #debug PartialEq, serde::Deserialize, serde::Serialize)]
                         #(#partial_attrs)*
                         pub enum #partial_name {
                             #(#variants)*
                         }
 
+                        #debug_impl
+
                         impl Default for #partial_name {
                             fn default() -> Self {
                                 #default_impl
@@ -4,8 +4,15 @@ use quote::{ToTokens, TokenStreamExt, quote};
 
 impl Field<'_> {
     pub fn generate_default_value(&self) -> TokenStream {
-        self.value_type
-            .generate_default_value(&self.args, self.is_nullable())
+        let value = self
+            .value_type
+            .generate_default_value(&self.args, self.is_nullable());
+
+        if self.is_clearable() && self.args.default.is_some() {
+            quote! { #value.map(Some) }
+        } else {
+            value
+        }
     }
 
     #[cfg(not(feature = "env"))]
@@ -32,9 +39,26 @@ impl Field<'_> {
             return None;
         };
 
+        // Prefixed values without a parser are loaded by the nested mapping instead,
+        // as it supports lists and maps
+        if self.env_nested_delimiter.is_some()
+            && self.args.env.is_none()
+            && self.args.parse_env.is_none()
+        {
+            return None;
+        }
+
+        let clearable = self.is_clearable();
+
         self.value_type
             .generate_env_value(&self.args, &env_key)
-            .map(|value| quote! { partial.#key = #value; })
+            .map(|value| {
+                if clearable {
+                    quote! { partial.#key = #value.map(Some); }
+                } else {
+                    quote! { partial.#key = #value; }
+                }
+            })
     }
 
     pub fn generate_finalize_statement(&self) -> TokenStream {
@@ -56,9 +80,17 @@ impl Field<'_> {
                 }
             }
             (None, Some(func)) => {
-                quote! {
-                    if let Some(data) = partial.#key {
-                        partial.#key = Some(#func(data, context)?);
+                if self.is_clearable() {
+                    quote! {
+                        if let Some(Some(data)) = partial.#key {
+                            partial.#key = Some(Some(#func(data, context)?));
+                        }
+                    }
+                } else {
+                    quote! {
+                        if let Some(data) = partial.#key {
+                            partial.#key = Some(#func(data, context)?);
+                        }
                     }
                 }
             }
@@ -77,6 +109,15 @@ impl Field<'_> {
                 return quote! { Default::default() };
             }
 
+            // Explicitly null values are flattened into `None`
+            if self.is_clearable() {
+                return if self.value_type.is_outer_boxed() {
+                    quote! { partial.#key.flatten().map(Box::new) }
+                } else {
+                    quote! { partial.#key.flatten() }
+                };
+            }
+
             if self.value_type.is_outer_boxed() {
                 let mut value = quote! { Box::new(partial.#key.unwrap_or_default()) };
 
@@ -121,8 +162,22 @@ impl Field<'_> {
     }
 
     pub fn generate_merge_statement(&self) -> TokenStream {
-        self.value_type
-            .get_merge_statement(self.get_field_key(), &self.args)
+        let key = self.get_field_key();
+
+        if self.is_clearable()
+            && let Some(func) = &self.args.merge
+        {
+            return quote! {
+                self.#key = merge_clearable_setting(
+                    self.#key.take(),
+                    next.#key.take(),
+                    context,
+                    #func,
+                )?;
+            };
+        }
+
+        self.value_type.get_merge_statement(key, &self.args)
     }
 
     pub fn generate_validate_statement(&self) -> TokenStream {
@@ -131,35 +186,78 @@ impl Field<'_> {
         let mut stmts = vec![];
 
         #[cfg(feature = "validate")]
-        if let Some(expr) = self.args.validate.as_ref() {
+        if let Some(validate) = self.args.validate.as_ref() {
             use syn::Expr;
 
-            let func = match expr {
-                // func(arg)()
-                Expr::Call(func) => quote! { #func },
-                // func()
-                Expr::Path(func) => quote! { #func },
-                _ => {
-                    panic!("Unsupported `validate` syntax.");
-                }
+            let mut funcs = vec![];
+
+            if let Some(length) = &validate.length {
+                funcs.push(match (&length.min, &length.max) {
+                    (Some(min), Some(max)) => {
+                        quote! { schematic::validate::in_length(#min, #max) }
+                    }
+                    (Some(min), None) => quote! { schematic::validate::min_length(#min) },
+                    (None, Some(max)) => quote! { schematic::validate::max_length(#max) },
+                    (None, None) => unreachable!(),
+                });
+            }
+
+            if let Some(range) = &validate.range {
+                funcs.push(match (&range.min, &range.max) {
+                    (Some(min), Some(max)) => quote! { schematic::validate::in_range(#min, #max) },
+                    (Some(min), None) => quote! { schematic::validate::min_value(#min) },
+                    (None, Some(max)) => quote! { schematic::validate::max_value(#max) },
+                    (None, None) => unreachable!(),
+                });
+            }
+
+            if let Some(pattern) = &validate.pattern {
+                funcs.push(quote! { schematic::validate::regex(#pattern) });
+            }
+
+            for expr in &validate.funcs {
+                funcs.push(match expr {
+                    // func(arg)()
+                    Expr::Call(func) => quote! { #func },
+                    // func()
+                    Expr::Path(func) => quote! { #func },
+                    _ => {
+                        panic!("Unsupported `validate` syntax.");
+                    }
+                });
+            }
+
+            let error = if self.is_secret() {
+                quote! { schematic::internal::redact_validate_error(error, setting) }
+            } else {
+                quote! { error }
             };
 
-            stmts.push(quote! {
-                if let Err(mut error) = #func(setting, self, context, finalize) {
-                    errors.push(error.prepend_path(path.join_key(#key_quoted)));
-                }
-            });
+            for func in funcs {
+                stmts.push(quote! {
+                    if let Err(mut error) = #func(setting, self, context, finalize) {
+                        errors.push(#error.prepend_path(path.join_key(#key_quoted)));
+                    }
+                });
+            }
         }
 
         if let Some(validator) = self.value_type.get_validate_statement(&key_quoted) {
             stmts.push(validator);
         }
 
+        // Explicitly null values are treated as unset
+        let value = if self.is_clearable() {
+            quote! { self.#key.as_ref().and_then(Option::as_ref) }
+        } else {
+            quote! { self.#key.as_ref() }
+        };
+
         let first = if stmts.is_empty() {
             quote! {}
         } else {
             quote! {
-                if let Some(setting) = self.#key.as_ref() {
+                if let Some(setting) = #value {
                     #(#stmts)*
                 }
             }
@@ -167,7 +265,7 @@ impl Field<'_> {
 
         let second = if self.is_required() {
             quote! {
-                if finalize && self.#key.is_none() {
+                if finalize && #value.is_none() {
                     errors.push(schematic::ValidateError::required().prepend_path(
                         path.join_key(#key_quoted)
                     ));
@@ -183,6 +281,45 @@ impl Field<'_> {
         }
     }
 
+    pub fn generate_resolve_paths_statement(&self) -> Option<TokenStream> {
+        let key = self.get_field_key();
+
+        if self.args.relative_path {
+            return Some(quote! {
+                if let Some(value) = self.#key.as_mut() {
+                    value.resolve_relative_path(base_dir);
+                }
+            });
+        }
+
+        self.value_type.get_resolve_paths_statement().map(|stmt| {
+            quote! {
+                if let Some(data) = self.#key.as_mut() {
+                    #stmt
+                }
+            }
+        })
+    }
+
+    pub fn generate_debug_statement(&self, partial: bool) -> TokenStream {
+        let key = self.get_field_key();
+        let value = if !self.is_secret() {
+            quote! { &self.#key }
+        } else if partial {
+            quote! { &self.#key.as_ref().map(schematic::internal::DebugSecret) }
+        } else {
+            quote! { &schematic::internal::DebugSecret(&self.#key) }
+        };
+
+        if self.name.is_some() {
+            let key_quoted = self.get_field_key_string();
+
+            quote! { .field(#key_quoted, #value) }
+        } else {
+            quote! { .field(#value) }
+        }
+    }
+
     fn get_field_key(&self) -> TokenStream {
         self.name
             .as_ref()
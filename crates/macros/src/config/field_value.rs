@@ -1,6 +1,7 @@
 use crate::common::{FieldArgs, FieldValue, TypeInfo};
+use crate::utils::finalize_quote;
 use proc_macro2::TokenStream;
-use quote::{format_ident, quote};
+use quote::{ToTokens, format_ident, quote};
 use syn::{Expr, Lit};
 
 impl FieldValue<'_> {
@@ -60,17 +61,19 @@ impl FieldValue<'_> {
             Self::NestedValue { info, .. } => {
                 let partial_name = format_ident!("Partial{}", info.config.as_ref().unwrap());
 
-                Some(quote! { track_env(#partial_name::env_values()?, &mut tracker) })
+                Some(
+                    quote! { track_env(#partial_name::env_values_with(context, env)?, &mut tracker) },
+                )
             }
             Self::Value { .. } => Some(match &args.parse_env {
                 Some(parse_env) => {
                     quote! {
-                        track_env(parse_env_value(#env_key, #parse_env)?, &mut tracker)
+                        track_env(parse_env_value(env, #env_key, #parse_env)?, &mut tracker)
                     }
                 }
                 _ => {
                     quote! {
-                        track_env(default_env_value(#env_key)?, &mut tracker)
+                        track_env(default_env_value(env, #env_key)?, &mut tracker)
                     }
                 }
             }),
@@ -78,11 +81,13 @@ impl FieldValue<'_> {
         }
     }
     pub fn get_finalize_value(&self) -> Option<TokenStream> {
+        let finalize = finalize_quote();
+
         match self {
             Self::NestedList { .. } | Self::NestedMap { .. } => {
-                Some(self.map_data(quote! { value.finalize(context)? }))
+                Some(self.map_data(quote! { value.#finalize? }))
             }
-            Self::NestedValue { .. } => Some(self.map_data(quote! { data.finalize(context)? })),
+            Self::NestedValue { .. } => Some(self.map_data(quote! { data.#finalize? })),
             Self::Value { .. } => None,
         }
     }
@@ -116,9 +121,52 @@ impl FieldValue<'_> {
         }
     }
 
+    fn get_deep_merge_func(&self) -> TokenStream {
+        let collection = match self {
+            Self::NestedList { collection, .. } | Self::NestedMap { collection, .. } => {
+                collection.to_string()
+            }
+            _ => panic!("Only nested collections support `merge = deep`."),
+        };
+
+        match collection.as_str() {
+            "Vec" => quote! { schematic::merge::merge_nested_vec },
+            "BTreeMap" => quote! { schematic::merge::merge_nested_btreemap },
+            "HashMap" => quote! { schematic::merge::merge_nested_hashmap },
+            _ => panic!(
+                "Unsupported collection `{collection}` for `merge = deep`, only `Vec`, `BTreeMap`, and `HashMap` are supported."
+            ),
+        }
+    }
+
     pub fn get_merge_statement(&self, key: TokenStream, args: &FieldArgs) -> TokenStream {
+        let is_deep = args
+            .merge
+            .as_ref()
+            .is_some_and(|func| func.path.is_ident("deep"));
+
+        if let Some(merge_key) = &args.merge_key {
+            if !matches!(self, Self::NestedList { collection, .. } if *collection == "Vec") {
+                panic!("Only nested `Vec` lists support `merge_key`.");
+            }
+
+            let merge_key = format_ident!("{merge_key}");
+
+            return quote! {
+                if let Some(next_items) = next.#key.take() {
+                    self.#key = schematic::merge::merge_nested_vec_by_key(
+                        self.#key.take().unwrap_or_default(),
+                        next_items,
+                        context,
+                        |item| item.#merge_key.as_ref().map(|value| value.to_string()),
+                    )?;
+                }
+            };
+        }
+
         if let Self::NestedValue { .. } = self {
-            if args.merge.is_some() {
+            // Nested configs are always deep merged
+            if args.merge.is_some() && !is_deep {
                 panic!("Nested configs do not support `merge` unless wrapped in a collection.");
             }
 
@@ -133,6 +181,12 @@ impl FieldValue<'_> {
 
         match args.merge.as_ref() {
             Some(func) => {
+                let func = if is_deep {
+                    self.get_deep_merge_func()
+                } else {
+                    func.to_token_stream()
+                };
+
                 quote! {
                     self.#key = merge_setting(
                         self.#key.take(),
@@ -152,6 +206,25 @@ impl FieldValue<'_> {
         }
     }
 
+    pub fn get_resolve_paths_statement(&self) -> Option<TokenStream> {
+        match self {
+            Self::NestedList { .. } => Some(quote! {
+                for item in data.iter_mut() {
+                    item.resolve_relative_paths(base_dir);
+                }
+            }),
+            Self::NestedMap { .. } => Some(quote! {
+                for value in data.values_mut() {
+                    value.resolve_relative_paths(base_dir);
+                }
+            }),
+            Self::NestedValue { .. } => Some(quote! {
+                data.resolve_relative_paths(base_dir);
+            }),
+            Self::Value { .. } => None,
+        }
+    }
+
     pub fn get_validate_statement(&self, key: &str) -> Option<TokenStream> {
         match self {
             Self::NestedList { .. } => Some(quote! {
@@ -10,6 +10,82 @@ use quote::{ToTokens, format_ident, quote};
 
 pub struct ConfigMacro<'l>(pub Macro<'l>);
 
+impl ConfigMacro<'_> {
+    #[cfg(not(feature = "env"))]
+    fn generate_nested_env_statement(&self) -> Option<TokenStream> {
+        None
+    }
+
+    #[cfg(feature = "env")]
+    fn generate_nested_env_statement(&self) -> Option<TokenStream> {
+        let cfg = &self.0;
+        let name = cfg.name;
+        let delimiter = cfg.args.env_nested_delimiter.as_ref()?;
+
+        let Some(prefix) = cfg.args.env_prefix.as_ref() else {
+            panic!("Cannot use `env_nested_delimiter` without `env_prefix`.");
+        };
+
+        let explicit_vars = cfg.type_of.get_explicit_env_vars();
+
+        Some(quote! {
+            if let Some(mut data) = nested_env_values::<Self>(
+                env,
+                #prefix,
+                #delimiter,
+                &<#name as schematic::Config>::settings(),
+                &[#(#explicit_vars),*],
+            )? {
+                data.merge(context, partial)?;
+                partial = data;
+                tracker.insert(true);
+            }
+        })
+    }
+}
+
+impl ConfigMacro<'_> {
+    #[cfg(not(feature = "validate"))]
+    fn generate_container_validate_statement(&self) -> TokenStream {
+        quote! {}
+    }
+
+    #[cfg(feature = "validate")]
+    fn generate_container_validate_statement(&self) -> TokenStream {
+        use syn::Expr;
+
+        let Some(validate) = &self.0.args.validate else {
+            return quote! {};
+        };
+
+        let stmts = validate.0.iter().map(|expr| {
+            let func = match expr {
+                // func(arg)()
+                Expr::Call(func) => quote! { #func },
+                // func()
+                Expr::Path(func) => quote! { #func },
+                _ => {
+                    panic!("Unsupported `validate` syntax.");
+                }
+            };
+
+            quote! {
+                if let Err(container_errors) = #func(self, context, finalize) {
+                    errors.extend(
+                        container_errors
+                            .into_iter()
+                            .map(|error| error.prepend_path(path.clone())),
+                    );
+                }
+            }
+        });
+
+        quote! {
+            #(#stmts)*
+        }
+    }
+}
+
 impl ToTokens for ConfigMacro<'_> {
     fn to_tokens(&self, tokens: &mut TokenStream) {
         let cfg = &self.0;
@@ -35,17 +111,31 @@ impl ToTokens for ConfigMacro<'_> {
         let settings_metadata = cfg.type_of.generate_settings_metadata();
         let instrument = instrument_quote();
 
+        // Secrets must also be redacted from the final config
+        let debug_impl = cfg
+            .type_of
+            .generate_debug(name, false)
+            .map(|debug| quote! { #[automatically_derived] #debug });
+
         let context = match cfg.args.context.as_ref() {
             Some(ctx) => quote! { #ctx },
             None => quote! { () },
         };
 
         let env_method = if cfg!(feature = "env") {
-            let env_values = cfg.type_of.generate_env_values();
+            let nested_env = self.generate_nested_env_statement();
+            let env_values = cfg.type_of.generate_env_values(nested_env);
 
             quote! {
-                #instrument
                 fn env_values() -> std::result::Result<Option<Self>, schematic::ConfigError> {
+                    Self::env_values_with(&Default::default(), &schematic::ProcessEnv)
+                }
+
+                #instrument
+                fn env_values_with(
+                    context: &Self::Context,
+                    env: &dyn schematic::EnvProvider,
+                ) -> std::result::Result<Option<Self>, schematic::ConfigError> {
                     use schematic::internal::*;
                     #env_values
                 }
@@ -54,6 +144,30 @@ impl ToTokens for ConfigMacro<'_> {
             quote! {}
         };
 
+        let finalize_method = if cfg!(feature = "env") {
+            quote! {
+                fn finalize(self, context: &Self::Context) -> std::result::Result<Self, schematic::ConfigError> {
+                    self.finalize_with_env(context, &schematic::ProcessEnv)
+                }
+
+                #instrument
+                fn finalize_with_env(
+                    self,
+                    context: &Self::Context,
+                    env: &dyn schematic::EnvProvider,
+                ) -> std::result::Result<Self, schematic::ConfigError> {
+                    #finalize
+                }
+            }
+        } else {
+            quote! {
+                #instrument
+                fn finalize(self, context: &Self::Context) -> std::result::Result<Self, schematic::ConfigError> {
+                    #finalize
+                }
+            }
+        };
+
         let extends_method = if cfg!(feature = "extends") {
             let extends_from = cfg.type_of.generate_extends_from();
 
@@ -67,8 +181,20 @@ impl ToTokens for ConfigMacro<'_> {
             quote! {}
         };
 
+        let resolve_paths_method = match cfg.type_of.generate_resolve_paths() {
+            Some(resolve_paths) => quote! {
+                #instrument
+                fn resolve_relative_paths(&mut self, base_dir: &std::path::Path) {
+                    use schematic::internal::*;
+                    #resolve_paths
+                }
+            },
+            None => quote! {},
+        };
+
         let validate_method = if cfg!(feature = "validate") {
             let validate = cfg.type_of.generate_validate();
+            let container_validate = self.generate_container_validate_statement();
 
             quote! {
                 #instrument
@@ -82,6 +208,9 @@ impl ToTokens for ConfigMacro<'_> {
 
                     #validate
 
+                    // Cross-field rules run after all fields have been validated
+                    #container_validate
+
                     if !errors.is_empty() {
                         return Err(errors);
                     }
@@ -108,10 +237,7 @@ impl ToTokens for ConfigMacro<'_> {
 
                 #extends_method
 
-                #instrument
-                fn finalize(self, context: &Self::Context) -> std::result::Result<Self, schematic::ConfigError> {
-                    #finalize
-                }
+                #finalize_method
 
                 #instrument
                 fn merge(
@@ -123,6 +249,8 @@ impl ToTokens for ConfigMacro<'_> {
                     #merge
                 }
 
+                #resolve_paths_method
+
                 #validate_method
             }
 
@@ -152,12 +280,30 @@ impl ToTokens for ConfigMacro<'_> {
                     #settings_metadata
                 }
             }
+
+            #debug_impl
         });
 
         #[cfg(feature = "schema")]
         {
             let schema_name = cfg.get_name();
-            let schema_impl = cfg.type_of.generate_schema(&cfg.attrs);
+            let mut schema_impl = cfg.type_of.generate_schema(&cfg.attrs);
+
+            #[cfg(feature = "env")]
+            if let Some(delimiter) = &cfg.args.env_nested_delimiter {
+                let prefix = cfg.args.env_prefix.as_deref().unwrap_or_default();
+
+                schema_impl = quote! {
+                    let mut schema = { #schema_impl };
+
+                    if let SchemaType::Struct(structure) = &mut schema.ty {
+                        structure.env_nested_delimiter = Some(#delimiter.into());
+                        structure.env_prefix = Some(#prefix.into());
+                    }
+
+                    schema
+                };
+            }
 
             let partial_schema_name = partial_name.to_string();
             let partial_schema_impl = cfg.type_of.generate_partial_schema(name, &partial_name);
@@ -1,4 +1,5 @@
 use crate::common::Variant;
+use crate::utils::finalize_quote;
 use proc_macro2::{Ident, TokenStream};
 use quote::{format_ident, quote};
 use syn::{Fields, FieldsUnnamed};
@@ -34,11 +35,13 @@ impl Variant<'_> {
                     return None;
                 }
 
+                let finalize = finalize_quote();
+
                 Some(self.map_unnamed_match(self.name, fields, |outer_names, _| {
                     let stmts = outer_names
                         .iter()
                         .map(|o| {
-                            quote! { #o.finalize(context)? }
+                            quote! { #o.#finalize? }
                         })
                         .collect::<Vec<_>>();
 
@@ -142,6 +145,22 @@ impl Variant<'_> {
         }
     }
 
+    pub fn generate_resolve_paths_statement(&self) -> Option<TokenStream> {
+        let Fields::Unnamed(fields) = &self.value.fields else {
+            return None;
+        };
+
+        if !self.is_nested() {
+            return None;
+        }
+
+        Some(self.map_unnamed_match(self.name, fields, |outer_names, _| {
+            quote! {
+                #(#outer_names.resolve_relative_paths(base_dir);)*
+            }
+        }))
+    }
+
     pub fn generate_validate_statement(&self) -> Option<TokenStream> {
         let Fields::Unnamed(fields) = &self.value.fields else {
             return None;
@@ -171,9 +190,15 @@ impl Variant<'_> {
                     quote! { (#(#outer_names),*) }
                 };
 
+                let error = if self.is_secret() {
+                    quote! { schematic::internal::redact_validate_error(error, &#value) }
+                } else {
+                    quote! { error }
+                };
+
                 stmts.push(quote! {
                     if let Err(mut error) = #func(#value, self, context, finalize) {
-                        errors.push(error.prepend_path(path.join_key(#name)));
+                        errors.push(#error.prepend_path(path.join_key(#name)));
                     }
                 });
             }
@@ -208,6 +233,38 @@ impl Variant<'_> {
         }))
     }
 
+    pub fn generate_debug_statement(&self) -> TokenStream {
+        let name = &self.name;
+        let name_quoted = name.to_string();
+
+        match &self.value.fields {
+            Fields::Named(_) => unreachable!(),
+            Fields::Unnamed(fields) => self.map_unnamed_match(name, fields, |outer_names, _| {
+                let stmts = outer_names
+                    .iter()
+                    .map(|o| {
+                        if self.is_secret() {
+                            quote! { .field(&schematic::internal::DebugSecret(#o)) }
+                        } else {
+                            quote! { .field(#o) }
+                        }
+                    })
+                    .collect::<Vec<_>>();
+
+                quote! {
+                    f.debug_tuple(#name_quoted)
+                        #(#stmts)*
+                        .finish()
+                }
+            }),
+            Fields::Unit => {
+                quote! {
+                    Self::#name => f.write_str(#name_quoted),
+                }
+            }
+        }
+    }
+
     pub fn generate_from_partial_value(&self, partial_name: &Ident) -> TokenStream {
         let name = &self.name;
 
@@ -196,6 +196,14 @@ pub fn map_option_argument_quote<T: ToTokens>(value: Option<T>) -> proc_macro2::
     }
 }
 
+pub fn finalize_quote() -> proc_macro2::TokenStream {
+    #[cfg(feature = "env")]
+    quote! { finalize_with_env(context, env) }
+
+    #[cfg(not(feature = "env"))]
+    quote! { finalize(context) }
+}
+
 pub fn instrument_quote() -> proc_macro2::TokenStream {
     #[cfg(feature = "tracing")]
     quote! { #[tracing::instrument(skip_all)] }
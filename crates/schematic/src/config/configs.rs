@@ -1,3 +1,5 @@
+#[cfg(feature = "env")]
+use super::env_provider::EnvProvider;
 use super::error::ConfigError;
 #[cfg(feature = "extends")]
 use super::extender::ExtendsFrom;
@@ -25,9 +27,27 @@ pub trait PartialConfig:
     ///
     /// If an environment variable does not exist, the value will be [`None`]. If
     /// the variable fails to parse or cast into the correct type, an error is returned.
+    ///
+    /// Nested environment variables are merged with a default context, use
+    /// [`PartialConfig::env_values_with`] to provide a context.
     #[cfg(feature = "env")]
     fn env_values() -> Result<Option<Self>, ConfigError>;
 
+    /// Like [`PartialConfig::env_values`], but reads environment variables
+    /// from the provided [`EnvProvider`] instead of the current process, and
+    /// merges nested environment variables with the provided context.
+    ///
+    /// The default implementation ignores the provider and context, and
+    /// calls [`PartialConfig::env_values`]. Derived implementations
+    /// override this method.
+    #[cfg(feature = "env")]
+    fn env_values_with(
+        _context: &Self::Context,
+        _env: &dyn EnvProvider,
+    ) -> Result<Option<Self>, ConfigError> {
+        Self::env_values()
+    }
+
     /// When a setting is marked as extendable with `#[setting(extend)]`, this returns
     /// [`ExtendsFrom`] with the extended sources, either a list of strings or a single string.
     /// When no setting is extendable, this returns [`None`].
@@ -40,6 +60,21 @@ pub trait PartialConfig:
     /// [`PartialConfig::env_values`].
     fn finalize(self, context: &Self::Context) -> Result<Self, ConfigError>;
 
+    /// Like [`PartialConfig::finalize`], but reads environment variables
+    /// from the provided [`EnvProvider`] instead of the current process.
+    ///
+    /// The default implementation ignores the provider, and calls
+    /// [`PartialConfig::finalize`]. Derived implementations override
+    /// this method.
+    #[cfg(feature = "env")]
+    fn finalize_with_env(
+        self,
+        context: &Self::Context,
+        _env: &dyn EnvProvider,
+    ) -> Result<Self, ConfigError> {
+        self.finalize(context)
+    }
+
     /// Merge another partial configuration into this one and clone values when applicable. The
     /// following merge strategies are applied:
     ///
@@ -48,13 +83,38 @@ pub trait PartialConfig:
     ///   using the merge function from `#[setting(merge)]`.
     fn merge(&mut self, context: &Self::Context, next: Self) -> Result<(), ConfigError>;
 
+    /// Recursively resolve relative paths for settings marked with `#[setting(relative_path)]`,
+    /// by joining them with the provided base directory. Absolute paths are left as-is.
+    fn resolve_relative_paths(&mut self, _base_dir: &std::path::Path) {}
+
     /// Recursively validate the configuration with the provided context.
     /// Validation should be done on the final state, after merging partials.
+    /// Failures with a warning severity do not cause validation to fail.
     #[cfg(feature = "validate")]
     fn validate(&self, context: &Self::Context, finalize: bool) -> Result<(), ConfigError> {
-        if let Err(errors) =
+        self.validate_with_warnings(context, finalize, false)
+            .map(|_| ())
+    }
+
+    /// Recursively validate the configuration with the provided context,
+    /// and return all failures with a warning severity on success. If
+    /// `promote_warnings` is true, warnings will fail validation instead.
+    #[cfg(feature = "validate")]
+    fn validate_with_warnings(
+        &self,
+        context: &Self::Context,
+        finalize: bool,
+        promote_warnings: bool,
+    ) -> Result<Vec<ValidateError>, ConfigError> {
+        let Err(failures) =
             self.validate_with_path(context, finalize, super::path::Path::default())
-        {
+        else {
+            return Ok(vec![]);
+        };
+
+        let (errors, warnings) = split_warnings(failures, promote_warnings);
+
+        if !errors.is_empty() {
             return Err(ConfigError::Validator {
                 location: String::new(),
                 error: Box::new(ValidatorError { errors }),
@@ -62,7 +122,7 @@ pub trait PartialConfig:
             });
         }
 
-        Ok(())
+        Ok(warnings)
     }
 
     /// Internal use only, use [`validate`] instead.
@@ -101,7 +161,12 @@ pub trait ConfigEnum: Sized + Schematic {
 #[derive(Clone, Debug, Default)]
 pub struct ConfigSetting {
     pub env_key: Option<String>,
+    /// Returns the key of a serialized list item, for settings
+    /// marked with `#[setting(nested, merge_key)]`.
+    pub merge_key: Option<fn(&serde_content::Value) -> Option<String>>,
     pub nested: Option<ConfigSettingMap>,
+    pub nested_collection: bool,
+    pub skip_interpolation: bool,
     pub type_alias: String,
 }
 
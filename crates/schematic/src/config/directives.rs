@@ -0,0 +1,538 @@
+use super::configs::{ConfigSetting, ConfigSettingMap};
+use super::error::ConfigError;
+use super::merger::MergeError;
+use super::parser::ParserError;
+use super::secret::reveal_secrets;
+use miette::{NamedSource, SourceSpan};
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use serde_content::{Data, Deserializer, Serializer, Value};
+use std::sync::Arc;
+
+/// Key that marks a map as a merge directive, for example,
+/// `{ "$merge": "replace", "value": [] }`.
+pub const MERGE_DIRECTIVE_KEY: &str = "$merge";
+
+/// Names of all supported merge directives.
+pub const MERGE_DIRECTIVE_NAMES: [&str; 5] = ["append", "prepend", "remove", "replace", "reset"];
+
+/// Operation to apply to an inherited value when merging layers.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum MergeOp {
+    /// Append list items after the inherited items.
+    Append,
+    /// Prepend list items before the inherited items.
+    Prepend,
+    /// Remove the provided keys from an inherited map,
+    /// or the provided items from an inherited list.
+    Remove(Vec<String>),
+    /// Replace the inherited value, regardless of the merge strategy.
+    Replace,
+    /// Reset the inherited value, and unset it if no value was provided.
+    Reset,
+}
+
+/// A directive declared within a configuration document, that controls how
+/// a value is merged with the value inherited from previous layers.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MergeDirective {
+    /// The operation to apply.
+    pub op: MergeOp,
+
+    /// List of keys from the document root to the value.
+    pub path: Vec<String>,
+
+    /// Span to the directive within the document.
+    pub span: Option<SourceSpan>,
+
+    content: Arc<String>,
+    file_name: String,
+}
+
+impl MergeDirective {
+    fn create_error(&self, message: String) -> ParserError {
+        ParserError {
+            content: NamedSource::new(&self.file_name, self.content.as_ref().to_owned()),
+            message,
+            path: self.path.join("."),
+            span: self.span,
+        }
+    }
+}
+
+/// Return true if the content may contain merge directives. This is a cheap
+/// check used to avoid the extra parsing work for documents without directives.
+pub(crate) fn has_merge_directives(content: &str, with_tags: bool) -> bool {
+    content.contains(MERGE_DIRECTIVE_KEY)
+        || with_tags
+            && MERGE_DIRECTIVE_NAMES
+                .iter()
+                .any(|name| content.contains(&format!("!{name}")))
+}
+
+/// Extract all merge directives from the provided document value, strip them from
+/// the value, and then deserialize the remaining value into the target type.
+pub(crate) fn parse_with_merge_directives<T: DeserializeOwned>(
+    value: impl Serialize,
+    file_name: &str,
+    content: &str,
+) -> Result<(T, Vec<MergeDirective>), ConfigError> {
+    let mut value = Serializer::new()
+        .human_readable()
+        .serialize(value)
+        .map_err(|error| ParserError {
+            content: NamedSource::new(file_name, content.to_owned()),
+            message: error.to_string(),
+            path: String::new(),
+            span: None,
+        })?;
+
+    let mut extractor = DirectiveExtractor {
+        content: Arc::new(content.to_owned()),
+        directives: vec![],
+        file_name: file_name.to_owned(),
+    };
+
+    if is_directive(&value) {
+        return Err(extractor
+            .create_error(
+                &[],
+                "Merge directives are not supported at the document root.".into(),
+            )
+            .into());
+    }
+
+    extractor.extract(&mut value, &mut vec![], false)?;
+
+    let de = Deserializer::new(value).human_readable().coerce_numbers();
+
+    let result: T = serde_path_to_error::deserialize(de).map_err(|error| {
+        let path = error
+            .path()
+            .iter()
+            .filter_map(|segment| match segment {
+                serde_path_to_error::Segment::Map { key } => Some(key.to_owned()),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+
+        ParserError {
+            content: NamedSource::new(file_name, content.to_owned()),
+            path: error.path().to_string(),
+            span: locate_span(content, &path),
+            message: error.inner().to_string(),
+        }
+    })?;
+
+    Ok((result, extractor.directives))
+}
+
+/// Apply the merge directives of the next layer to the previously merged (inherited)
+/// partial and the next partial, returning both so that they can be merged as usual.
+pub(crate) fn apply_merge_directives<T: Serialize + DeserializeOwned>(
+    prev: &T,
+    next: &T,
+    directives: &[MergeDirective],
+    settings: &ConfigSettingMap,
+) -> Result<(T, T), ConfigError> {
+    let handle_error = |error: serde_content::Error| ConfigError::from(MergeError::new(error));
+
+    let (mut prev_value, mut next_value) = reveal_secrets(|| {
+        Ok::<_, ConfigError>((
+            Serializer::new().serialize(prev).map_err(handle_error)?,
+            Serializer::new().serialize(next).map_err(handle_error)?,
+        ))
+    })?;
+
+    for directive in directives {
+        let path = &directive.path;
+
+        match &directive.op {
+            MergeOp::Replace | MergeOp::Reset => {
+                take_value(&mut prev_value, path);
+            }
+            MergeOp::Append | MergeOp::Prepend => {
+                let Some(inherited) = take_value(&mut prev_value, path) else {
+                    continue;
+                };
+
+                let Some(target) = get_value_mut(&mut next_value, path) else {
+                    continue;
+                };
+
+                let (Value::Seq(inherited_items), Value::Seq(items)) =
+                    (unwrap_option(inherited), unwrap_option_mut(target))
+                else {
+                    return Err(directive
+                        .create_error(format!(
+                            "Unable to {} items, the inherited value is not a list.",
+                            if directive.op == MergeOp::Append {
+                                "append"
+                            } else {
+                                "prepend"
+                            }
+                        ))
+                        .into());
+                };
+
+                if directive.op == MergeOp::Append {
+                    items.splice(0..0, inherited_items);
+                } else {
+                    items.extend(inherited_items);
+                }
+            }
+            MergeOp::Remove(keys) => {
+                let Some(target) = get_value_mut(&mut prev_value, path) else {
+                    continue;
+                };
+
+                match unwrap_option_mut(target) {
+                    Value::Map(entries) => {
+                        entries.retain(|(key, _)| {
+                            value_to_key(key).is_none_or(|key| !keys.contains(&key))
+                        });
+                    }
+                    Value::Seq(items) => {
+                        // Items of keyed lists are matched on their merge key
+                        let get_key = find_setting(settings, path)
+                            .and_then(|setting| setting.merge_key)
+                            .unwrap_or(value_to_key);
+
+                        items.retain(|item| get_key(item).is_none_or(|item| !keys.contains(&item)));
+                    }
+                    _ => {
+                        return Err(directive
+                            .create_error(
+                                "Unable to remove items, the inherited value is not a list or map."
+                                    .into(),
+                            )
+                            .into());
+                    }
+                };
+            }
+        }
+    }
+
+    let prev = Deserializer::new(prev_value)
+        .coerce_numbers()
+        .deserialize()
+        .map_err(handle_error)?;
+
+    let next = Deserializer::new(next_value)
+        .coerce_numbers()
+        .deserialize()
+        .map_err(handle_error)?;
+
+    Ok((prev, next))
+}
+
+struct DirectiveExtractor {
+    content: Arc<String>,
+    directives: Vec<MergeDirective>,
+    file_name: String,
+}
+
+impl DirectiveExtractor {
+    fn create_error(&self, path: &[String], message: String) -> ParserError {
+        ParserError {
+            content: NamedSource::new(&self.file_name, self.content.as_ref().to_owned()),
+            message,
+            path: path.join("."),
+            span: locate_span(&self.content, path),
+        }
+    }
+
+    fn extract(
+        &mut self,
+        value: &mut Value<'static>,
+        path: &mut Vec<String>,
+        in_list: bool,
+    ) -> Result<(), ParserError> {
+        match value {
+            Value::Map(entries) => {
+                let mut index = 0;
+
+                while index < entries.len() {
+                    path.push(value_to_key(&entries[index].0).unwrap_or_default());
+
+                    let entry = &mut entries[index].1;
+
+                    if !is_directive(entry) {
+                        self.extract(entry, path, in_list)?;
+                        index += 1;
+                    } else if in_list {
+                        return Err(self.create_error(
+                            path,
+                            "Merge directives are not supported within lists.".into(),
+                        ));
+                    } else {
+                        match self.create_directive(std::mem::replace(entry, Value::Unit), path)? {
+                            Some(mut replacement) => {
+                                self.extract(&mut replacement, path, in_list)?;
+                                entries[index].1 = replacement;
+                                index += 1;
+                            }
+                            None => {
+                                entries.remove(index);
+                            }
+                        };
+                    }
+
+                    path.pop();
+                }
+            }
+            Value::Seq(items) => {
+                for item in items {
+                    if is_directive(item) {
+                        return Err(self.create_error(
+                            path,
+                            "Merge directives are not supported within lists.".into(),
+                        ));
+                    }
+
+                    self.extract(item, path, true)?;
+                }
+            }
+            _ => {}
+        };
+
+        Ok(())
+    }
+
+    fn create_directive(
+        &mut self,
+        directive: Value<'static>,
+        path: &[String],
+    ) -> Result<Option<Value<'static>>, ParserError> {
+        let Value::Map(entries) = directive else {
+            unreachable!();
+        };
+
+        let mut name = None;
+        let mut value = None;
+
+        for (key, inner) in entries {
+            match value_to_key(&key).as_deref() {
+                Some(MERGE_DIRECTIVE_KEY) => {
+                    name = value_to_key(&inner);
+                }
+                Some("value") => {
+                    value = match inner {
+                        Value::Unit | Value::Option(None) => None,
+                        other => Some(other),
+                    };
+                }
+                other => {
+                    return Err(self.create_error(
+                        path,
+                        format!(
+                            "Unknown field `{}` in merge directive, expected `{MERGE_DIRECTIVE_KEY}` or `value`.",
+                            other.unwrap_or_default()
+                        ),
+                    ));
+                }
+            };
+        }
+
+        let name = name.unwrap_or_default();
+
+        let op = match name.as_str() {
+            "append" | "prepend" => {
+                if !matches!(value, Some(Value::Seq(_))) {
+                    return Err(self.create_error(
+                        path,
+                        format!("The `{name}` merge directive requires a list value."),
+                    ));
+                }
+
+                if name == "append" {
+                    MergeOp::Append
+                } else {
+                    MergeOp::Prepend
+                }
+            }
+            "remove" => {
+                let keys = match value.take() {
+                    Some(Value::Seq(items)) => items.iter().map(value_to_key).collect(),
+                    Some(item) => value_to_key(&item).map(|key| vec![key]),
+                    None => None,
+                };
+
+                let Some(keys) = keys else {
+                    return Err(self.create_error(
+                        path,
+                        "The `remove` merge directive requires a list of keys or items.".into(),
+                    ));
+                };
+
+                MergeOp::Remove(keys)
+            }
+            "replace" => {
+                if value.is_none() {
+                    return Err(self.create_error(
+                        path,
+                        "The `replace` merge directive requires a value.".into(),
+                    ));
+                }
+
+                MergeOp::Replace
+            }
+            "reset" => MergeOp::Reset,
+            _ => {
+                return Err(self.create_error(
+                    path,
+                    format!(
+                        "Unknown merge directive `{name}`, expected one of {}.",
+                        MERGE_DIRECTIVE_NAMES.join(", ")
+                    ),
+                ));
+            }
+        };
+
+        self.directives.push(MergeDirective {
+            op,
+            path: path.to_vec(),
+            span: locate_span(&self.content, path),
+            content: Arc::clone(&self.content),
+            file_name: self.file_name.clone(),
+        });
+
+        Ok(value)
+    }
+}
+
+fn is_directive(value: &Value) -> bool {
+    match value {
+        Value::Map(entries) => entries
+            .iter()
+            .any(|(key, _)| value_to_key(key).is_some_and(|key| key == MERGE_DIRECTIVE_KEY)),
+        _ => false,
+    }
+}
+
+fn value_to_key(value: &Value) -> Option<String> {
+    match value {
+        Value::Bool(inner) => Some(inner.to_string()),
+        Value::Char(inner) => Some(inner.to_string()),
+        Value::Number(inner) => Some(
+            Deserializer::new(Value::Number(*inner))
+                .deserialize::<f64>()
+                .ok()?
+                .to_string(),
+        ),
+        Value::String(inner) => Some(inner.to_string()),
+        Value::Option(Some(inner)) => value_to_key(inner),
+        _ => None,
+    }
+}
+
+fn find_setting<'s>(settings: &'s ConfigSettingMap, path: &[String]) -> Option<&'s ConfigSetting> {
+    let (key, rest) = path.split_first()?;
+    let setting = settings.get(key)?;
+
+    if rest.is_empty() {
+        return Some(setting);
+    }
+
+    // Skip the key of the collection item
+    let rest = if setting.nested_collection {
+        rest.get(1..)?
+    } else {
+        rest
+    };
+
+    find_setting(setting.nested.as_ref()?, rest)
+}
+
+fn unwrap_option(value: Value<'static>) -> Value<'static> {
+    match value {
+        Value::Option(Some(inner)) => unwrap_option(*inner),
+        other => other,
+    }
+}
+
+fn unwrap_option_mut<'v>(value: &'v mut Value<'static>) -> &'v mut Value<'static> {
+    match value {
+        Value::Option(Some(inner)) => unwrap_option_mut(inner),
+        other => other,
+    }
+}
+
+fn get_child_mut<'v>(value: &'v mut Value<'static>, key: &str) -> Option<&'v mut Value<'static>> {
+    match unwrap_option_mut(value) {
+        Value::Struct(inner) => match &mut inner.data {
+            Data::Struct { fields } => fields
+                .iter_mut()
+                .find(|(name, _)| name == key)
+                .map(|(_, value)| value),
+            _ => None,
+        },
+        Value::Map(entries) => entries
+            .iter_mut()
+            .find(|(name, _)| value_to_key(name).is_some_and(|name| name == key))
+            .map(|(_, value)| value),
+        _ => None,
+    }
+}
+
+fn get_value_mut<'v>(
+    value: &'v mut Value<'static>,
+    path: &[String],
+) -> Option<&'v mut Value<'static>> {
+    path.iter()
+        .try_fold(value, |current, key| get_child_mut(current, key))
+}
+
+fn take_value(value: &mut Value<'static>, path: &[String]) -> Option<Value<'static>> {
+    let (key, parent_path) = path.split_last()?;
+
+    match unwrap_option_mut(get_value_mut(value, parent_path)?) {
+        Value::Struct(inner) => match &mut inner.data {
+            Data::Struct { fields } => {
+                let index = fields.iter().position(|(name, _)| name == key)?;
+
+                Some(fields.remove(index).1)
+            }
+            _ => None,
+        },
+        Value::Map(entries) => {
+            let index = entries
+                .iter()
+                .position(|(name, _)| value_to_key(name).is_some_and(|name| &name == key))?;
+
+            Some(entries.remove(index).1)
+        }
+        _ => None,
+    }
+}
+
+fn is_key_boundary(ch: Option<char>) -> bool {
+    ch.is_none_or(|ch| !ch.is_alphanumeric() && ch != '_' && ch != '-')
+}
+
+/// Find the span of the last key in the path, by searching for each key
+/// in order, starting from the position of the previous key.
+fn locate_span(content: &str, path: &[String]) -> Option<SourceSpan> {
+    let mut offset = 0;
+    let mut span = None;
+
+    for key in path {
+        if key.is_empty() {
+            return span;
+        }
+
+        let (index, _) = content[offset..]
+            .match_indices(key.as_str())
+            .find(|(index, _)| {
+                let start = offset + index;
+
+                is_key_boundary(content[..start].chars().next_back())
+                    && is_key_boundary(content[start + key.len()..].chars().next())
+            })?;
+
+        span = Some((offset + index, key.len()).into());
+        offset += index + key.len();
+    }
+
+    span
+}
@@ -0,0 +1,339 @@
+use super::configs::ConfigSettingMap;
+use super::error::{ConfigError, HandlerError};
+use crate::helpers::to_env_name;
+use serde::de::{
+    self, DeserializeOwned, DeserializeSeed, Deserializer, IntoDeserializer, MapAccess, SeqAccess,
+    Visitor,
+};
+use std::collections::BTreeMap;
+use std::fmt::{self, Display};
+
+/// Load values from environment variables that target nested settings, by splitting
+/// the variable name on a delimiter. For example, with a prefix of `APP_` and a
+/// delimiter of `__`, `APP_SERVER__PORT` will target the `server.port` setting,
+/// `APP_HOSTS__0` will target the first item of the `hosts` list, and
+/// `APP_LABELS__team` will target the `team` key of the `labels` map.
+pub fn parse_nested_env_vars<T: DeserializeOwned>(
+    vars: impl IntoIterator<Item = (String, String)>,
+    prefix: &str,
+    delimiter: &str,
+    settings: &ConfigSettingMap,
+) -> Result<Option<T>, ConfigError> {
+    let mut root = BTreeMap::new();
+
+    for (key, value) in vars {
+        let Some(name) = key.strip_prefix(prefix) else {
+            continue;
+        };
+
+        let name = name.to_owned();
+        let segments = name.split(delimiter).collect::<Vec<_>>();
+
+        if segments.iter().any(|segment| segment.is_empty()) {
+            continue;
+        }
+
+        insert_node(
+            &mut root,
+            &segments,
+            Some(settings),
+            EnvNode::Value { key, value },
+        );
+    }
+
+    if root.is_empty() {
+        return Ok(None);
+    }
+
+    T::deserialize(EnvNode::Map(root))
+        .map(Some)
+        .map_err(|error| ConfigError::Handler(Box::new(HandlerError(error.0))))
+}
+
+fn insert_node(
+    map: &mut BTreeMap<String, EnvNode>,
+    segments: &[&str],
+    settings: Option<&ConfigSettingMap>,
+    node: EnvNode,
+) {
+    let Some((segment, rest)) = segments.split_first() else {
+        return;
+    };
+
+    // Determine the setting name from the segment, otherwise
+    // use it as-is, as it's a list index or map key
+    let (key, nested, collection) = match settings {
+        Some(settings) => {
+            let segment = segment.to_uppercase();
+
+            match settings
+                .iter()
+                .find(|(name, _)| to_env_name(name) == segment)
+            {
+                Some((name, setting)) => (
+                    name.to_owned(),
+                    setting.nested.as_ref(),
+                    setting.nested_collection,
+                ),
+                // Unknown setting, ignore it
+                None => return,
+            }
+        }
+        None => (segment.to_string(), None, false),
+    };
+
+    if rest.is_empty() {
+        // Nested configs can't be represented by a single value
+        if nested.is_none() {
+            map.entry(key).or_insert(node);
+        }
+
+        return;
+    }
+
+    let EnvNode::Map(child) = map
+        .entry(key)
+        .and_modify(|existing| {
+            if matches!(existing, EnvNode::Value { .. }) {
+                *existing = EnvNode::Map(BTreeMap::new());
+            }
+        })
+        .or_insert_with(|| EnvNode::Map(BTreeMap::new()))
+    else {
+        unreachable!();
+    };
+
+    if collection {
+        // Index or key of the collection, followed by the item's settings
+        let (item, rest) = rest.split_first().unwrap();
+
+        if rest.is_empty() {
+            child.entry(item.to_string()).or_insert(node);
+
+            return;
+        }
+
+        let EnvNode::Map(item_child) = child
+            .entry(item.to_string())
+            .or_insert_with(|| EnvNode::Map(BTreeMap::new()))
+        else {
+            return;
+        };
+
+        insert_node(item_child, rest, nested, node);
+    } else {
+        insert_node(child, rest, nested, node);
+    }
+}
+
+#[derive(Debug)]
+struct EnvError(String);
+
+impl Display for EnvError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl std::error::Error for EnvError {}
+
+impl de::Error for EnvError {
+    fn custom<T: Display>(msg: T) -> Self {
+        EnvError(msg.to_string())
+    }
+}
+
+enum EnvNode {
+    Map(BTreeMap<String, EnvNode>),
+    Value { key: String, value: String },
+}
+
+impl EnvNode {
+    fn parse<T: std::str::FromStr>(&self) -> Result<T, EnvError> {
+        match self {
+            EnvNode::Value { key, value } => value.trim().parse::<T>().map_err(|_| {
+                EnvError(format!(
+                    "Invalid environment variable {key}. Failed to parse \"{value}\" into the correct type."
+                ))
+            }),
+            EnvNode::Map(_) => Err(EnvError(
+                "Expected a value, found nested environment variables.".into(),
+            )),
+        }
+    }
+}
+
+macro_rules! deserialize_parsed {
+    ($method:ident, $visit:ident) => {
+        fn $method<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+            visitor.$visit(self.parse()?)
+        }
+    };
+}
+
+impl<'de> Deserializer<'de> for EnvNode {
+    type Error = EnvError;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        match self {
+            EnvNode::Map(map) => visitor.visit_map(EnvMapAccess {
+                iter: map.into_iter(),
+                value: None,
+            }),
+            EnvNode::Value { value, .. } => visitor.visit_string(value),
+        }
+    }
+
+    deserialize_parsed!(deserialize_bool, visit_bool);
+    deserialize_parsed!(deserialize_i8, visit_i8);
+    deserialize_parsed!(deserialize_i16, visit_i16);
+    deserialize_parsed!(deserialize_i32, visit_i32);
+    deserialize_parsed!(deserialize_i64, visit_i64);
+    deserialize_parsed!(deserialize_i128, visit_i128);
+    deserialize_parsed!(deserialize_u8, visit_u8);
+    deserialize_parsed!(deserialize_u16, visit_u16);
+    deserialize_parsed!(deserialize_u32, visit_u32);
+    deserialize_parsed!(deserialize_u64, visit_u64);
+    deserialize_parsed!(deserialize_u128, visit_u128);
+    deserialize_parsed!(deserialize_f32, visit_f32);
+    deserialize_parsed!(deserialize_f64, visit_f64);
+    deserialize_parsed!(deserialize_char, visit_char);
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        visitor.visit_some(self)
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_seq<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        match self {
+            // Indexed items, sorted numerically
+            EnvNode::Map(map) => {
+                let mut items = vec![];
+
+                for (index, node) in map {
+                    let index = index.parse::<usize>().map_err(|_| {
+                        EnvError(format!("Expected a list index, received \"{index}\"."))
+                    })?;
+
+                    items.push((index, node));
+                }
+
+                items.sort_by_key(|(index, _)| *index);
+
+                visitor.visit_seq(EnvSeqAccess {
+                    iter: items
+                        .into_iter()
+                        .map(|(_, node)| node)
+                        .collect::<Vec<_>>()
+                        .into_iter(),
+                })
+            }
+            // Comma separated items
+            EnvNode::Value { key, value } => visitor.visit_seq(EnvSeqAccess {
+                iter: value
+                    .split(',')
+                    .map(|item| item.trim())
+                    .filter(|item| !item.is_empty())
+                    .map(|item| EnvNode::Value {
+                        key: key.clone(),
+                        value: item.to_owned(),
+                    })
+                    .collect::<Vec<_>>()
+                    .into_iter(),
+            }),
+        }
+    }
+
+    fn deserialize_tuple<V: Visitor<'de>>(
+        self,
+        _len: usize,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        self.deserialize_seq(visitor)
+    }
+
+    fn deserialize_tuple_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _len: usize,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        self.deserialize_seq(visitor)
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        match self {
+            EnvNode::Value { value, .. } => {
+                visitor.visit_enum(IntoDeserializer::<EnvError>::into_deserializer(value))
+            }
+            EnvNode::Map(_) => Err(EnvError(
+                "Only unit enums are supported for nested environment variables.".into(),
+            )),
+        }
+    }
+
+    serde::forward_to_deserialize_any! {
+        str string bytes byte_buf unit unit_struct map struct identifier ignored_any
+    }
+}
+
+struct EnvMapAccess {
+    iter: std::collections::btree_map::IntoIter<String, EnvNode>,
+    value: Option<EnvNode>,
+}
+
+impl<'de> MapAccess<'de> for EnvMapAccess {
+    type Error = EnvError;
+
+    fn next_key_seed<K: DeserializeSeed<'de>>(
+        &mut self,
+        seed: K,
+    ) -> Result<Option<K::Value>, Self::Error> {
+        match self.iter.next() {
+            Some((key, value)) => {
+                self.value = Some(value);
+
+                seed.deserialize(IntoDeserializer::<EnvError>::into_deserializer(key))
+                    .map(Some)
+            }
+            None => Ok(None),
+        }
+    }
+
+    fn next_value_seed<V: DeserializeSeed<'de>>(
+        &mut self,
+        seed: V,
+    ) -> Result<V::Value, Self::Error> {
+        seed.deserialize(self.value.take().unwrap())
+    }
+}
+
+struct EnvSeqAccess {
+    iter: std::vec::IntoIter<EnvNode>,
+}
+
+impl<'de> SeqAccess<'de> for EnvSeqAccess {
+    type Error = EnvError;
+
+    fn next_element_seed<T: DeserializeSeed<'de>>(
+        &mut self,
+        seed: T,
+    ) -> Result<Option<T::Value>, Self::Error> {
+        match self.iter.next() {
+            Some(node) => seed.deserialize(node).map(Some),
+            None => Ok(None),
+        }
+    }
+}
@@ -0,0 +1,79 @@
+use std::collections::HashMap;
+
+/// A system for reading environment variables, used when loading
+/// settings marked with `#[setting(env)]` or `#[config(env_prefix)]`.
+pub trait EnvProvider {
+    /// Return the value of the environment variable, if it exists.
+    fn get(&self, key: &str) -> Option<String>;
+
+    /// Return all environment variables as key-value pairs.
+    fn vars(&self) -> Vec<(String, String)>;
+}
+
+pub type BoxedEnvProvider = Box<dyn EnvProvider>;
+
+/// Reads environment variables from the current process.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct ProcessEnv;
+
+impl EnvProvider for ProcessEnv {
+    fn get(&self, key: &str) -> Option<String> {
+        std::env::var(key).ok()
+    }
+
+    fn vars(&self) -> Vec<(String, String)> {
+        std::env::vars().collect()
+    }
+}
+
+/// Reads environment variables from an in-memory map, which is
+/// useful for testing, or for isolating from the current process.
+#[derive(Clone, Debug, Default)]
+pub struct MemoryEnv {
+    vars: HashMap<String, String>,
+}
+
+impl MemoryEnv {
+    /// Create a new empty environment.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set an environment variable.
+    pub fn set(&mut self, key: impl AsRef<str>, value: impl AsRef<str>) -> &mut Self {
+        self.vars
+            .insert(key.as_ref().to_owned(), value.as_ref().to_owned());
+        self
+    }
+
+    /// Remove an environment variable.
+    pub fn remove(&mut self, key: impl AsRef<str>) -> &mut Self {
+        self.vars.remove(key.as_ref());
+        self
+    }
+}
+
+impl<K: AsRef<str>, V: AsRef<str>> FromIterator<(K, V)> for MemoryEnv {
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        let mut env = Self::new();
+
+        for (key, value) in iter {
+            env.set(key, value);
+        }
+
+        env
+    }
+}
+
+impl EnvProvider for MemoryEnv {
+    fn get(&self, key: &str) -> Option<String> {
+        self.vars.get(key).cloned()
+    }
+
+    fn vars(&self) -> Vec<(String, String)> {
+        self.vars
+            .iter()
+            .map(|(key, value)| (key.to_owned(), value.to_owned()))
+            .collect()
+    }
+}
@@ -1,5 +1,6 @@
 use super::merger::MergeError;
 use super::parser::ParserError;
+use super::path::Path;
 #[cfg(feature = "validate")]
 use super::validator::ValidatorError;
 use miette::Diagnostic;
@@ -117,6 +118,41 @@ pub enum ConfigError {
     )]
     PklRequired,
 
+    // Interpolation
+    #[diagnostic(code(config::interpolate::cycle))]
+    #[error(
+        "Failed to interpolate setting {}, detected a reference cycle: {}.",
+        .path.to_string().style(Style::Id),
+        .chain,
+    )]
+    InterpolateCycle { path: Path, chain: String },
+
+    #[diagnostic(code(config::interpolate::failed))]
+    #[error("Failed to interpolate setting {}. {message}", .path.to_string().style(Style::Id))]
+    InterpolateFailed { path: Path, message: String },
+
+    #[diagnostic(code(config::interpolate::unknown_reference))]
+    #[error(
+        "Failed to interpolate setting {}, unknown reference {}.",
+        .path.to_string().style(Style::Id),
+        .reference.style(Style::Symbol),
+    )]
+    InterpolateUnknownReference { path: Path, reference: String },
+
+    // Loader
+    #[diagnostic(code(config::load::failed))]
+    #[error(
+        "Failed to load {}, encountered {} errors.",
+        .location.style(Style::File),
+        .errors.len()
+    )]
+    Multiple {
+        location: String,
+
+        #[related]
+        errors: Vec<ConfigError>,
+    },
+
     // Parser
     #[diagnostic(code(config::parse::failed))]
     #[error("Failed to parse {}.", .location.style(Style::File))]
@@ -177,6 +213,11 @@ impl ConfigError {
                 push_end();
                 message.push_str(&inner.to_string());
             }
+            ConfigError::Multiple { errors, .. } => {
+                for error in errors {
+                    message.push_str(format!("\n{}", error.to_full_string()).as_str());
+                }
+            }
             ConfigError::Parser { error: inner, .. } => {
                 push_end();
                 message.push_str(&inner.to_string());
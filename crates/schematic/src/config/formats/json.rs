@@ -1,4 +1,5 @@
 use super::create_span;
+use crate::config::directives::*;
 use crate::config::error::ConfigError;
 use crate::config::parser::ParserError;
 use crate::config::source::*;
@@ -9,6 +10,21 @@ use std::path::Path;
 #[derive(Default)]
 pub struct JsonFormat {}
 
+impl JsonFormat {
+    fn strip_comments(&self, source: &Source, content: &str) -> Result<String, ConfigError> {
+        let mut content = String::from(if content.is_empty() { "{}" } else { content });
+
+        json_strip_comments::strip(&mut content).map_err(|error| {
+            ConfigError::JsonStripCommentsFailed {
+                file: source.get_file_name().to_owned(),
+                error: Box::new(error),
+            }
+        })?;
+
+        Ok(content)
+    }
+}
+
 impl<T: DeserializeOwned> SourceFormat<T> for JsonFormat {
     fn should_parse(&self, source: &Source) -> bool {
         source
@@ -22,28 +38,44 @@ impl<T: DeserializeOwned> SourceFormat<T> for JsonFormat {
         content: &str,
         _cache_path: Option<&Path>,
     ) -> Result<T, ConfigError> {
-        let mut content = String::from(if content.is_empty() { "{}" } else { content });
+        let content = self.strip_comments(source, content)?;
 
-        json_strip_comments::strip(&mut content).map_err(|error| {
-            ConfigError::JsonStripCommentsFailed {
-                file: source.get_file_name().to_owned(),
-                error: Box::new(error),
-            }
-        })?;
+        deserialize(source, &content)
+    }
 
-        let de = &mut serde_json::Deserializer::from_str(&content);
-
-        let result: T = serde_path_to_error::deserialize(de).map_err(|error| ParserError {
-            content: NamedSource::new(source.get_file_name(), content.to_owned()),
-            path: error.path().to_string(),
-            span: Some(create_span(
-                &content,
-                error.inner().line(),
-                error.inner().column(),
-            )),
-            message: error.inner().to_string(),
-        })?;
+    fn parse_with_directives(
+        &self,
+        source: &Source,
+        content: &str,
+        cache_path: Option<&Path>,
+    ) -> Result<(T, Vec<MergeDirective>), ConfigError> {
+        if !has_merge_directives(content, false) {
+            return Ok((self.parse(source, content, cache_path)?, vec![]));
+        }
+
+        let content = self.strip_comments(source, content)?;
 
-        Ok(result)
+        // First pass, convert string to value
+        let value: serde_json::Value = deserialize(source, &content)?;
+
+        // Second pass, extract directives and convert value to struct
+        parse_with_merge_directives(value, source.get_file_name(), &content)
     }
 }
+
+fn deserialize<T: DeserializeOwned>(source: &Source, content: &str) -> Result<T, ConfigError> {
+    let de = &mut serde_json::Deserializer::from_str(content);
+
+    let result: T = serde_path_to_error::deserialize(de).map_err(|error| ParserError {
+        content: NamedSource::new(source.get_file_name(), content.to_owned()),
+        path: error.path().to_string(),
+        span: Some(create_span(
+            content,
+            error.inner().line(),
+            error.inner().column(),
+        )),
+        message: error.inner().to_string(),
+    })?;
+
+    Ok(result)
+}
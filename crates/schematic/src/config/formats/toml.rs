@@ -1,3 +1,4 @@
+use crate::config::directives::*;
 use crate::config::error::{ConfigError, HandlerError};
 use crate::config::parser::ParserError;
 use crate::config::source::*;
@@ -19,17 +20,37 @@ impl<T: DeserializeOwned> SourceFormat<T> for TomlFormat {
         content: &str,
         _cache_path: Option<&Path>,
     ) -> Result<T, ConfigError> {
-        let de = toml::Deserializer::parse(content).map_err(|error| {
-            ConfigError::Handler(Box::new(HandlerError::new(error.to_string())))
-        })?;
-
-        let result: T = serde_path_to_error::deserialize(de).map_err(|error| ParserError {
-            content: NamedSource::new(source.get_file_name(), content.to_owned()),
-            path: error.path().to_string(),
-            span: error.inner().span().map(|s| s.into()),
-            message: error.inner().message().to_owned(),
-        })?;
-
-        Ok(result)
+        deserialize(source, content)
     }
+
+    fn parse_with_directives(
+        &self,
+        source: &Source,
+        content: &str,
+        cache_path: Option<&Path>,
+    ) -> Result<(T, Vec<MergeDirective>), ConfigError> {
+        if !has_merge_directives(content, false) {
+            return Ok((self.parse(source, content, cache_path)?, vec![]));
+        }
+
+        // First pass, convert string to value
+        let value: toml::Table = deserialize(source, content)?;
+
+        // Second pass, extract directives and convert value to struct
+        parse_with_merge_directives(value, source.get_file_name(), content)
+    }
+}
+
+fn deserialize<T: DeserializeOwned>(source: &Source, content: &str) -> Result<T, ConfigError> {
+    let de = toml::Deserializer::parse(content)
+        .map_err(|error| ConfigError::Handler(Box::new(HandlerError::new(error.to_string()))))?;
+
+    let result: T = serde_path_to_error::deserialize(de).map_err(|error| ParserError {
+        content: NamedSource::new(source.get_file_name(), content.to_owned()),
+        path: error.path().to_string(),
+        span: error.inner().span().map(|s| s.into()),
+        message: error.inner().message().to_owned(),
+    })?;
+
+    Ok(result)
 }
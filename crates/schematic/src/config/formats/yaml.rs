@@ -1,10 +1,11 @@
 use super::create_span;
+use crate::config::directives::*;
 use crate::config::error::ConfigError;
 use crate::config::parser::ParserError;
 use crate::config::source::*;
 use miette::NamedSource;
 use serde::de::{DeserializeOwned, IntoDeserializer};
-use serde_norway::{Deserializer, Error, Value};
+use serde_norway::{Deserializer, Error, Mapping, Value};
 use std::path::Path;
 
 #[derive(Default)]
@@ -25,33 +26,8 @@ impl<T: DeserializeOwned> SourceFormat<T> for YamlFormat {
     ) -> Result<T, ConfigError> {
         let name = source.get_file_name();
 
-        fn create_parser_error(
-            name: &str,
-            content: &str,
-            path: String,
-            error: Error,
-        ) -> ParserError {
-            ParserError {
-                content: NamedSource::new(name, content.to_owned()),
-                path,
-                span: error
-                    .location()
-                    .map(|s| create_span(content, s.line(), s.column())),
-                message: error.to_string(),
-            }
-        }
-
         // First pass, convert string to value
-        let de = Deserializer::from_str(content);
-
-        let mut result: Value = serde_path_to_error::deserialize(de).map_err(|error| {
-            create_parser_error(name, content, error.path().to_string(), error.into_inner())
-        })?;
-
-        // Applies anchors/aliases/references
-        result
-            .apply_merge()
-            .map_err(|error| create_parser_error(name, content, String::new(), error))?;
+        let result = parse_value(name, content)?;
 
         // Second pass, convert value to struct
         let de = result.into_deserializer();
@@ -62,4 +38,85 @@ impl<T: DeserializeOwned> SourceFormat<T> for YamlFormat {
 
         Ok(result)
     }
+
+    fn parse_with_directives(
+        &self,
+        source: &Source,
+        content: &str,
+        cache_path: Option<&Path>,
+    ) -> Result<(T, Vec<MergeDirective>), ConfigError> {
+        if !has_merge_directives(content, true) {
+            return Ok((self.parse(source, content, cache_path)?, vec![]));
+        }
+
+        let name = source.get_file_name();
+
+        // First pass, convert string to value
+        let mut result = parse_value(name, content)?;
+
+        // Convert tags (`!reset`, etc) into directive maps
+        convert_directive_tags(&mut result);
+
+        // Second pass, extract directives and convert value to struct
+        parse_with_merge_directives(result, name, content)
+    }
+}
+
+fn create_parser_error(name: &str, content: &str, path: String, error: Error) -> ParserError {
+    ParserError {
+        content: NamedSource::new(name, content.to_owned()),
+        path,
+        span: error
+            .location()
+            .map(|s| create_span(content, s.line(), s.column())),
+        message: error.to_string(),
+    }
+}
+
+fn parse_value(name: &str, content: &str) -> Result<Value, ParserError> {
+    let de = Deserializer::from_str(content);
+
+    let mut result: Value = serde_path_to_error::deserialize(de).map_err(|error| {
+        create_parser_error(name, content, error.path().to_string(), error.into_inner())
+    })?;
+
+    // Applies anchors/aliases/references
+    result
+        .apply_merge()
+        .map_err(|error| create_parser_error(name, content, String::new(), error))?;
+
+    Ok(result)
+}
+
+fn convert_directive_tags(value: &mut Value) {
+    match value {
+        Value::Tagged(tagged) => {
+            let tag = tagged.tag.to_string();
+            let tag = tag.trim_start_matches('!');
+
+            convert_directive_tags(&mut tagged.value);
+
+            if MERGE_DIRECTIVE_NAMES.contains(&tag) {
+                let mut map = Mapping::new();
+                map.insert(MERGE_DIRECTIVE_KEY.into(), tag.into());
+
+                if !tagged.value.is_null() {
+                    map.insert("value".into(), std::mem::take(&mut tagged.value));
+                }
+
+                *value = Value::Mapping(map);
+            }
+        }
+        Value::Mapping(map) => {
+            for (_, item) in map.iter_mut() {
+                convert_directive_tags(item);
+            }
+        }
+        Value::Sequence(items) => {
+            for item in items {
+                convert_directive_tags(item);
+            }
+        }
+        _ => {}
+    };
 }
@@ -0,0 +1,408 @@
+use super::configs::ConfigSettingMap;
+use super::error::ConfigError;
+use super::path::{Path, PathSegment};
+use serde_content::{Data, Number, Value};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path as FsPath, PathBuf};
+
+/// Interpolates `${...}` tokens within string settings of a serialized
+/// partial configuration. Supports environment variables (`${env:NAME}`),
+/// file contents (`${file:path}`), and references to other settings
+/// (`${server.host}`). A token can be escaped with `$${`.
+pub(crate) struct Interpolator<'a> {
+    /// Directory to resolve relative `${file:}` paths against.
+    pub base_dir: Option<PathBuf>,
+
+    /// Lookup function for `${env:}` tokens.
+    pub env: &'a dyn Fn(&str) -> Option<String>,
+
+    /// Tree of all settings (including defaults) for resolving references.
+    pub lookup: &'a Value<'static>,
+
+    /// Setting metadata, for determining which settings to skip.
+    pub settings: &'a ConfigSettingMap,
+
+    /// Only rewrite relative `${file:}` paths to be absolute against this
+    /// directory, and leave all other tokens as-is.
+    files_only: Option<&'a FsPath>,
+
+    resolved: HashMap<String, String>,
+    stack: Vec<String>,
+}
+
+impl<'a> Interpolator<'a> {
+    pub fn new(lookup: &'a Value<'static>, settings: &'a ConfigSettingMap) -> Self {
+        Self {
+            base_dir: None,
+            env: &|name| std::env::var(name).ok(),
+            lookup,
+            settings,
+            files_only: None,
+            resolved: HashMap::default(),
+            stack: vec![],
+        }
+    }
+
+    /// Create an instance that only resolves relative `${file:}` paths against
+    /// the provided directory, so that they are relative to the source they
+    /// were declared in, instead of the directory they are interpolated from.
+    pub fn for_files(settings: &'a ConfigSettingMap, base_dir: &'a FsPath) -> Self {
+        static EMPTY: Value<'static> = Value::Unit;
+
+        let mut instance = Self::new(&EMPTY, settings);
+        instance.files_only = Some(base_dir);
+        instance
+    }
+
+    /// Interpolate all string values within the provided tree, and return
+    /// true if any value was changed.
+    pub fn interpolate(&mut self, value: &mut Value<'static>) -> Result<bool, ConfigError> {
+        let settings = self.settings;
+
+        self.interpolate_value(value, Path::default(), Some(settings))
+    }
+
+    fn interpolate_value(
+        &mut self,
+        value: &mut Value<'static>,
+        path: Path,
+        settings: Option<&ConfigSettingMap>,
+    ) -> Result<bool, ConfigError> {
+        let mut changed = false;
+
+        match value {
+            Value::String(string) => {
+                if string.contains("${") {
+                    self.stack.push(path.to_string());
+
+                    let result = self.interpolate_string(string, &path);
+
+                    self.stack.pop();
+
+                    *string = result?.into();
+                    changed = true;
+                }
+            }
+            Value::Option(Some(inner)) => {
+                changed = self.interpolate_value(inner, path, settings)?;
+            }
+            Value::Seq(items) | Value::Tuple(items) => {
+                for (index, item) in items.iter_mut().enumerate() {
+                    changed |= self.interpolate_value(item, path.join_index(index), settings)?;
+                }
+            }
+            Value::Map(entries) => {
+                for (key, item) in entries.iter_mut() {
+                    let key = match key {
+                        Value::String(key) => key.to_string(),
+                        other => format!("{other:?}"),
+                    };
+
+                    changed |= self.interpolate_value(item, path.join_key(key), settings)?;
+                }
+            }
+            Value::Struct(inner) => {
+                changed = self.interpolate_data(&mut inner.data, path, settings)?;
+            }
+            Value::Enum(inner) => {
+                let path = path.join_variant(&inner.variant);
+
+                changed = self.interpolate_data(&mut inner.data, path, settings)?;
+            }
+            _ => {}
+        };
+
+        Ok(changed)
+    }
+
+    fn interpolate_data(
+        &mut self,
+        data: &mut Data<'static>,
+        path: Path,
+        settings: Option<&ConfigSettingMap>,
+    ) -> Result<bool, ConfigError> {
+        let mut changed = false;
+
+        match data {
+            Data::NewType { value } => {
+                changed = self.interpolate_value(value, path, settings)?;
+            }
+            Data::Tuple { values } => {
+                for (index, item) in values.iter_mut().enumerate() {
+                    changed |= self.interpolate_value(item, path.join_index(index), settings)?;
+                }
+            }
+            Data::Struct { fields } => {
+                for (key, item) in fields.iter_mut() {
+                    let setting = settings.and_then(|map| map.get(key.as_ref()));
+
+                    if setting.is_some_and(|setting| setting.skip_interpolation) {
+                        continue;
+                    }
+
+                    changed |= self.interpolate_value(
+                        item,
+                        path.join_key(key),
+                        setting.and_then(|setting| setting.nested.as_ref()),
+                    )?;
+                }
+            }
+            Data::Unit => {}
+        };
+
+        Ok(changed)
+    }
+
+    fn interpolate_string(&mut self, value: &str, path: &Path) -> Result<String, ConfigError> {
+        let mut result = String::with_capacity(value.len());
+        let mut rest = value;
+
+        while let Some(index) = rest.find('$') {
+            result.push_str(&rest[0..index]);
+            rest = &rest[index..];
+
+            // Escaped: $${ -> ${
+            if rest.starts_with("$${") {
+                result.push_str(if self.files_only.is_some() {
+                    "$${"
+                } else {
+                    "${"
+                });
+                rest = &rest[3..];
+                continue;
+            }
+
+            if !rest.starts_with("${") {
+                result.push('$');
+                rest = &rest[1..];
+                continue;
+            }
+
+            let Some(end) = rest.find('}') else {
+                // Reported when all tokens are interpolated
+                if self.files_only.is_some() {
+                    break;
+                }
+
+                return Err(ConfigError::InterpolateFailed {
+                    path: path.to_owned(),
+                    message: format!("Unterminated token in \"{value}\"."),
+                });
+            };
+
+            let token = rest[2..end].trim();
+
+            if let Some(base_dir) = self.files_only {
+                match token.strip_prefix("file:") {
+                    Some(file) if FsPath::new(file).is_relative() => {
+                        result.push_str("${file:");
+                        result.push_str(&base_dir.join(file).to_string_lossy());
+                        result.push('}');
+                    }
+                    _ => result.push_str(&rest[0..=end]),
+                };
+            } else {
+                result.push_str(&self.resolve_token(token, path)?);
+            }
+
+            rest = &rest[end + 1..];
+        }
+
+        result.push_str(rest);
+
+        Ok(result)
+    }
+
+    fn resolve_token(&mut self, token: &str, path: &Path) -> Result<String, ConfigError> {
+        if let Some(name) = token.strip_prefix("env:") {
+            return (self.env)(name).ok_or_else(|| ConfigError::InterpolateFailed {
+                path: path.to_owned(),
+                message: format!("Environment variable {name} is not set."),
+            });
+        }
+
+        if let Some(file) = token.strip_prefix("file:") {
+            let file_path = match &self.base_dir {
+                Some(dir) => dir.join(file),
+                None => PathBuf::from(file),
+            };
+
+            return fs::read_to_string(&file_path)
+                .map(|content| content.trim_end_matches(['\r', '\n']).to_owned())
+                .map_err(|error| ConfigError::InterpolateFailed {
+                    path: path.to_owned(),
+                    message: format!("Failed to read file {}: {error}", file_path.display()),
+                });
+        }
+
+        self.resolve_reference(token, path)
+    }
+
+    fn resolve_reference(&mut self, reference: &str, path: &Path) -> Result<String, ConfigError> {
+        let segments = parse_reference(reference);
+        let key = Path::new(segments.clone()).to_string();
+
+        if let Some(value) = self.resolved.get(&key) {
+            return Ok(value.to_owned());
+        }
+
+        if self.stack.contains(&key) {
+            let mut chain = self.stack.clone();
+            chain.push(key);
+
+            return Err(ConfigError::InterpolateCycle {
+                path: path.to_owned(),
+                chain: chain.join(" -> "),
+            });
+        }
+
+        let unknown = || ConfigError::InterpolateUnknownReference {
+            path: path.to_owned(),
+            reference: reference.to_owned(),
+        };
+
+        let value = match lookup_value(self.lookup, &segments).ok_or_else(unknown)? {
+            // Settings that opt out of interpolation are referenced as-is
+            Value::String(string) if self.is_skipped(&segments) => string.to_string(),
+            Value::String(string) => {
+                self.stack.push(key.clone());
+
+                let result = self.interpolate_string(string, &Path::new(segments));
+
+                self.stack.pop();
+
+                result?
+            }
+            Value::Bool(inner) => inner.to_string(),
+            Value::Char(inner) => inner.to_string(),
+            Value::Number(inner) => number_to_string(inner),
+            Value::Unit | Value::Option(None) => return Err(unknown()),
+            _ => {
+                return Err(ConfigError::InterpolateFailed {
+                    path: path.to_owned(),
+                    message: format!(
+                        "Reference {reference} must point to a string, number, or boolean setting."
+                    ),
+                });
+            }
+        };
+
+        self.resolved.insert(key, value.clone());
+
+        Ok(value)
+    }
+
+    fn is_skipped(&self, segments: &[PathSegment]) -> bool {
+        let mut settings = Some(self.settings);
+        let mut in_collection = false;
+
+        for segment in segments {
+            // Items of a nested list or map share the same settings
+            if in_collection {
+                in_collection = false;
+                continue;
+            }
+
+            let PathSegment::Key(key) = segment else {
+                continue;
+            };
+
+            let Some(setting) = settings.and_then(|map| map.get(key)) else {
+                return false;
+            };
+
+            if setting.skip_interpolation {
+                return true;
+            }
+
+            in_collection = setting.nested_collection;
+            settings = setting.nested.as_ref();
+        }
+
+        false
+    }
+}
+
+fn parse_reference(reference: &str) -> Vec<PathSegment> {
+    let mut segments = vec![];
+
+    for part in reference.split('.') {
+        let (key, mut indexes) = match part.find('[') {
+            Some(index) => (&part[0..index], &part[index..]),
+            None => (part, ""),
+        };
+
+        if !key.is_empty() {
+            segments.push(match key.parse::<usize>() {
+                Ok(index) => PathSegment::Index(index),
+                Err(_) => PathSegment::Key(key.to_owned()),
+            });
+        }
+
+        while let Some(end) = indexes.find(']') {
+            segments.push(match indexes[1..end].parse::<usize>() {
+                Ok(index) => PathSegment::Index(index),
+                Err(_) => PathSegment::Unknown,
+            });
+
+            indexes = &indexes[end + 1..];
+        }
+    }
+
+    segments
+}
+
+fn lookup_value<'v>(
+    value: &'v Value<'static>,
+    segments: &[PathSegment],
+) -> Option<&'v Value<'static>> {
+    let Some((segment, rest)) = segments.split_first() else {
+        return match value {
+            Value::Option(Some(inner)) => lookup_value(inner, segments),
+            _ => Some(value),
+        };
+    };
+
+    let next = match (value, segment) {
+        (Value::Option(Some(inner)), _) => return lookup_value(inner, segments),
+        (Value::Seq(items) | Value::Tuple(items), PathSegment::Index(index)) => items.get(*index),
+        (Value::Map(entries), PathSegment::Key(key)) => entries.iter().find_map(|(k, v)| match k {
+            Value::String(k) if k == key => Some(v),
+            _ => None,
+        }),
+        (Value::Struct(inner), _) => lookup_data(&inner.data, segment),
+        (Value::Enum(inner), _) => lookup_data(&inner.data, segment),
+        _ => None,
+    };
+
+    lookup_value(next?, rest)
+}
+
+fn lookup_data<'v>(data: &'v Data<'static>, segment: &PathSegment) -> Option<&'v Value<'static>> {
+    match (data, segment) {
+        (Data::Struct { fields }, PathSegment::Key(key)) => fields
+            .iter()
+            .find_map(|(k, v)| if k == key { Some(v) } else { None }),
+        (Data::Tuple { values }, PathSegment::Index(index)) => values.get(*index),
+        _ => None,
+    }
+}
+
+fn number_to_string(number: &Number) -> String {
+    match number {
+        Number::I8(n) => n.to_string(),
+        Number::U8(n) => n.to_string(),
+        Number::I16(n) => n.to_string(),
+        Number::U16(n) => n.to_string(),
+        Number::I32(n) => n.to_string(),
+        Number::U32(n) => n.to_string(),
+        Number::F32(n) => n.to_string(),
+        Number::I64(n) => n.to_string(),
+        Number::U64(n) => n.to_string(),
+        Number::F64(n) => n.to_string(),
+        Number::I128(n) => n.to_string(),
+        Number::U128(n) => n.to_string(),
+        other => format!("{other:?}"),
+    }
+}
@@ -1,13 +1,30 @@
 use super::configs::Config;
+use super::directives::MergeDirective;
 use super::source::Source;
 use serde::{Deserialize, Serialize};
 
 /// A layer of configuration that was loaded and used to create the final state.
 #[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[non_exhaustive]
 pub struct Layer<T: Config> {
     /// The partial configuration that was loaded.
     pub partial: T::Partial,
 
     /// The source location of the partial.
     pub source: Source,
+
+    /// Merge directives declared within the source.
+    #[serde(skip)]
+    pub directives: Vec<MergeDirective>,
+}
+
+impl<T: Config> Layer<T> {
+    /// Create a new layer for the partial configuration loaded from the source.
+    pub fn new(partial: T::Partial, source: Source) -> Self {
+        Self {
+            partial,
+            source,
+            directives: vec![],
+        }
+    }
 }
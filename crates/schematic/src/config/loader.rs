@@ -2,12 +2,21 @@ use crate::helpers::strip_bom;
 
 use super::cacher::{BoxedCacher, Cacher, MemoryCache};
 use super::configs::{Config, PartialConfig};
+use super::directives::{MergeDirective, apply_merge_directives};
+#[cfg(feature = "env")]
+use super::env_provider::{BoxedEnvProvider, EnvProvider, ProcessEnv};
 use super::error::ConfigError;
 #[cfg(feature = "extends")]
 use super::extender::ExtendsFrom;
+use super::interpolator::Interpolator;
 use super::layer::Layer;
+use super::path::{Path as ConfigPath, PathSegment};
+use super::secret::reveal_secrets;
 use super::source::{Source, SourceFormat};
+#[cfg(feature = "validate")]
+use super::validator::ValidateError;
 use serde::Serialize;
+use serde::de::DeserializeOwned;
 use std::borrow::Cow;
 use std::fs;
 use std::marker::PhantomData;
@@ -18,36 +27,57 @@ use tracing::{instrument, trace};
 /// The result of loading a configuration. Includes the final configuration,
 /// and all layers that were loaded.
 #[derive(Serialize)]
+#[non_exhaustive]
 pub struct ConfigLoadResult<T: Config> {
     /// Final configuration, after all layers are merged.
     pub config: T,
 
     /// Partial layers, in order of declaration and extension.
     pub layers: Vec<Layer<T>>,
+
+    /// Validation failures with a warning severity, for the final configuration.
+    /// Warnings for individual layers are not collected.
+    #[cfg(feature = "validate")]
+    #[serde(skip)]
+    pub warnings: Vec<ValidateError>,
 }
 
 /// A system for loading configuration from multiple sources in multiple formats,
 /// and generating a final result after merging and validating layers.
 pub struct ConfigLoader<T: Config> {
     _config: PhantomData<T>,
+    aggregate_errors: bool,
+    base_dir: Option<PathBuf>,
     cacher: Mutex<BoxedCacher>,
+    #[cfg(feature = "env")]
+    env: BoxedEnvProvider,
     formats: Vec<Arc<dyn SourceFormat<T::Partial>>>,
     help: Option<String>,
+    interpolate: bool,
     name: String,
     sources: Vec<Source>,
     root: Option<PathBuf>,
+    #[cfg(feature = "validate")]
+    warnings_as_errors: bool,
 }
 
 impl<T: Config> Default for ConfigLoader<T> {
     fn default() -> Self {
         ConfigLoader {
             _config: PhantomData,
+            aggregate_errors: false,
+            base_dir: None,
             cacher: Mutex::new(Box::<MemoryCache>::default()),
+            #[cfg(feature = "env")]
+            env: Box::new(ProcessEnv),
             formats: vec![],
             help: None,
+            interpolate: false,
             name: T::schema_name().unwrap_or_else(|| "<unknown>".into()),
             sources: vec![],
             root: None,
+            #[cfg(feature = "validate")]
+            warnings_as_errors: false,
         }
     }
 }
@@ -133,20 +163,32 @@ impl<T: Config> ConfigLoader<T> {
     ) -> Result<ConfigLoadResult<T>, ConfigError> {
         trace!(config = &self.name, "Loading configuration");
 
-        let layers = self.parse_into_layers(&self.sources, context)?;
-        let partial = self.merge_layers(&layers, context)?.finalize(context)?;
+        let layers = self.parse_all_layers(context)?;
+        let mut partial = self.merge_layers(&layers, context)?;
+
+        if self.interpolate {
+            partial = self.interpolate_partial(partial, context)?;
+        }
+
+        #[cfg(feature = "env")]
+        let partial = partial.finalize_with_env(context, self.env.as_ref())?;
+
+        #[cfg(not(feature = "env"))]
+        let partial = partial.finalize(context)?;
 
         // Validate the final result before moving on
         #[cfg(feature = "validate")]
-        {
-            partial.validate(context, true).map_err(|error| {
+        let warnings = partial
+            .validate_with_warnings(context, true, self.warnings_as_errors)
+            .map_err(|error| {
                 self.map_validator_error(error, layers.last().map(|layer| &layer.source))
             })?;
-        }
 
         Ok(ConfigLoadResult {
             config: T::from_partial(partial),
             layers,
+            #[cfg(feature = "validate")]
+            warnings,
         })
     }
 
@@ -162,18 +204,56 @@ impl<T: Config> ConfigLoader<T> {
     ) -> Result<T::Partial, ConfigError> {
         trace!(config = &self.name, "Loading partial configuration");
 
-        let layers = self.parse_into_layers(&self.sources, context)?;
-        let partial = self.merge_layers(&layers, context)?;
+        let layers = self.parse_all_layers(context)?;
+        let mut partial = self.merge_layers(&layers, context)?;
+
+        if self.interpolate {
+            partial = self.interpolate_partial(partial, context)?;
+        }
 
         Ok(partial)
     }
 
+    /// Continue parsing and validating all sources when an error is encountered,
+    /// and return all errors at once as a [`ConfigError::Multiple`], instead of
+    /// failing on the first error.
+    pub fn set_aggregate_errors(&mut self, state: bool) -> &mut Self {
+        self.aggregate_errors = state;
+        self
+    }
+
+    /// Set the base directory to resolve settings marked with `#[setting(relative_path)]`
+    /// against, for code and URL sources. File sources will always resolve against
+    /// the directory of the file. If not set, the root directory will be used.
+    pub fn set_base_dir<P: AsRef<Path>>(&mut self, dir: P) -> &mut Self {
+        self.base_dir = Some(dir.as_ref().to_path_buf());
+        self
+    }
+
     /// Set a cacher instance that'll read and write the cache for URL requests.
     pub fn set_cacher(&mut self, cacher: impl Cacher + 'static) -> &mut Self {
         self.cacher = Mutex::new(Box::new(cacher));
         self
     }
 
+    /// Set an environment provider that'll be used for reading environment variables,
+    /// for settings marked with `#[setting(env)]` and `${env:NAME}` interpolation.
+    /// Defaults to the current process.
+    #[cfg(feature = "env")]
+    pub fn set_env_provider(&mut self, env: impl EnvProvider + 'static) -> &mut Self {
+        self.env = Box::new(env);
+        self
+    }
+
+    /// Enable interpolation of `${...}` tokens within string settings. Interpolation
+    /// runs after all layers have been merged, but before the partial is finalized.
+    /// Supports environment variables (`${env:NAME}`), file contents (`${file:path}`),
+    /// and references to other settings (`${server.host}`).
+    pub fn set_interpolate(&mut self, state: bool) -> &mut Self {
+        self.interpolate = state;
+        self
+    }
+
     /// Set a string of help text to include in validation errors.
     pub fn set_help<H: AsRef<str>>(&mut self, help: H) -> &mut Self {
         self.help = Some(help.as_ref().to_owned());
@@ -186,6 +266,14 @@ impl<T: Config> ConfigLoader<T> {
         self
     }
 
+    /// Treat validation failures with a warning severity as errors,
+    /// failing the load instead of collecting them as warnings.
+    #[cfg(feature = "validate")]
+    pub fn set_warnings_as_errors(&mut self, state: bool) -> &mut Self {
+        self.warnings_as_errors = state;
+        self
+    }
+
     #[cfg(feature = "extends")]
     #[instrument(skip_all)]
     fn extend_additional_layers(
@@ -193,6 +281,7 @@ impl<T: Config> ConfigLoader<T> {
         context: &<T::Partial as PartialConfig>::Context,
         parent_source: &Source,
         extends_from: &ExtendsFrom,
+        errors: &mut Vec<ConfigError>,
     ) -> Result<Vec<Layer<T>>, ConfigError> {
         let mut sources = vec![];
 
@@ -217,16 +306,27 @@ impl<T: Config> ConfigLoader<T> {
 
         match extends_from {
             ExtendsFrom::String(value) => {
-                extend_source(value)?;
+                if let Err(error) = extend_source(value) {
+                    self.handle_error(error, errors)?;
+                }
             }
             ExtendsFrom::List(values) => {
                 for value in values.iter() {
-                    extend_source(value)?;
+                    if let Err(error) = extend_source(value) {
+                        self.handle_error(error, errors)?;
+                    }
                 }
             }
         };
 
-        self.parse_into_layers(&sources, context)
+        self.parse_into_layers(&sources, context, errors)
+    }
+
+    fn get_base_dir<'l>(&'l self, source: &'l Source) -> Option<&'l Path> {
+        match source {
+            Source::File { path, .. } => path.parent(),
+            _ => self.base_dir.as_deref().or(self.root.as_deref()),
+        }
     }
 
     fn get_location<'l>(&'l self, source: &'l Source) -> &'l str {
@@ -246,6 +346,61 @@ impl<T: Config> ConfigLoader<T> {
         }
     }
 
+    #[instrument(skip_all)]
+    fn interpolate_partial(
+        &self,
+        partial: T::Partial,
+        context: &<T::Partial as PartialConfig>::Context,
+    ) -> Result<T::Partial, ConfigError> {
+        trace!(config = &self.name, "Interpolating settings");
+
+        // References may point to settings that have not been configured,
+        // so include the default values when looking them up
+        let mut lookup = T::Partial::default_values(context)?.unwrap_or_default();
+        lookup.merge(context, partial.clone())?;
+
+        let lookup = serialize_partial(&lookup)?;
+        let mut value = serialize_partial(&partial)?;
+
+        let settings = T::settings();
+        let mut interpolator = Interpolator::new(&lookup, &settings);
+        interpolator.base_dir = self.base_dir.clone().or_else(|| self.root.clone());
+
+        #[cfg(feature = "env")]
+        let env = |name: &str| self.env.get(name);
+
+        #[cfg(feature = "env")]
+        {
+            interpolator.env = &env;
+        }
+
+        // Avoid a round-trip if nothing has changed
+        if !interpolator.interpolate(&mut value)? {
+            return Ok(partial);
+        }
+
+        deserialize_partial(value)
+    }
+
+    fn interpolate_files(
+        &self,
+        partial: T::Partial,
+        source: &Source,
+    ) -> Result<T::Partial, ConfigError> {
+        let Some(base_dir) = self.get_base_dir(source) else {
+            return Ok(partial);
+        };
+
+        let mut value = serialize_partial(&partial)?;
+        let settings = T::settings();
+
+        if !Interpolator::for_files(&settings, base_dir).interpolate(&mut value)? {
+            return Ok(partial);
+        }
+
+        deserialize_partial(value)
+    }
+
     #[instrument(skip_all)]
     fn merge_layers(
         &self,
@@ -259,20 +414,67 @@ impl<T: Config> ConfigLoader<T> {
 
         // All `None` by default
         let mut merged = T::Partial::default();
+        let settings = T::settings();
 
         // Then apply other layers in order
         for layer in layers {
-            merged.merge(context, layer.partial.clone())?;
+            if layer.directives.is_empty() {
+                merged.merge(context, layer.partial.clone())?;
+                continue;
+            }
+
+            let (prev, next) =
+                apply_merge_directives(&merged, &layer.partial, &layer.directives, &settings)
+                    .map_err(|error| self.map_parser_error(error, &layer.source))?;
+
+            merged = prev;
+            merged.merge(context, next)?;
         }
 
         Ok(merged)
     }
 
+    fn handle_error(
+        &self,
+        error: ConfigError,
+        errors: &mut Vec<ConfigError>,
+    ) -> Result<(), ConfigError> {
+        if !self.aggregate_errors {
+            return Err(error);
+        }
+
+        // Expand nested aggregates so that all errors are at the same level
+        match error {
+            ConfigError::Multiple { errors: inner, .. } => errors.extend(inner),
+            _ => errors.push(error),
+        };
+
+        Ok(())
+    }
+
+    fn parse_all_layers(
+        &self,
+        context: &<T::Partial as PartialConfig>::Context,
+    ) -> Result<Vec<Layer<T>>, ConfigError> {
+        let mut errors = vec![];
+        let layers = self.parse_into_layers(&self.sources, context, &mut errors)?;
+
+        if !errors.is_empty() {
+            return Err(ConfigError::Multiple {
+                location: self.name.clone(),
+                errors,
+            });
+        }
+
+        Ok(layers)
+    }
+
     #[instrument(skip_all)]
     fn parse_into_layers(
         &self,
         sources_to_parse: &[Source],
         #[allow(unused_variables)] context: &<T::Partial as PartialConfig>::Context,
+        errors: &mut Vec<ConfigError>,
     ) -> Result<Vec<Layer<T>>, ConfigError> {
         let mut layers: Vec<Layer<T>> = vec![];
 
@@ -284,26 +486,56 @@ impl<T: Config> ConfigLoader<T> {
             );
 
             // Parse the source into a partial
-            let partial: T::Partial = self
-                .parse_source(source)
-                .map_err(|error| self.map_parser_error(error, source))?;
+            let (mut partial, directives) = match self.parse_source(source) {
+                Ok(result) => result,
+                Err(error) => {
+                    self.handle_error(self.map_parser_error(error, source), errors)?;
+
+                    // Unable to continue with this source
+                    continue;
+                }
+            };
+
+            // Resolve relative paths against the source before merging
+            if let Some(base_dir) = self.get_base_dir(source) {
+                partial.resolve_relative_paths(base_dir);
+            }
+
+            // Files are also relative to the source, but are read when interpolating
+            if self.interpolate {
+                partial = match self.interpolate_files(partial, source) {
+                    Ok(result) => result,
+                    Err(error) => {
+                        self.handle_error(error, errors)?;
 
-            // Validate before continuing so we ensure the values are correct
+                        continue;
+                    }
+                };
+            }
+
+            // Validate before continuing so we ensure the values are correct,
+            // while warnings are only collected from the final configuration
             #[cfg(feature = "validate")]
+            if let Err(error) =
+                partial.validate_with_warnings(context, false, self.warnings_as_errors)
             {
-                partial
-                    .validate(context, false)
-                    .map_err(|error| self.map_validator_error(error, Some(source)))?;
+                self.handle_error(self.map_validator_error(error, Some(source)), errors)?;
             }
 
             #[cfg(feature = "extends")]
             if let Some(extends_from) = partial.extends_from() {
-                layers.extend(self.extend_additional_layers(context, source, &extends_from)?);
+                layers.extend(self.extend_additional_layers(
+                    context,
+                    source,
+                    &extends_from,
+                    errors,
+                )?);
             }
 
             layers.push(Layer {
                 partial,
                 source: source.clone(),
+                directives,
             });
         }
 
@@ -311,7 +543,10 @@ impl<T: Config> ConfigLoader<T> {
     }
 
     #[instrument(skip_all)]
-    fn parse_source(&self, source: &Source) -> Result<T::Partial, ConfigError> {
+    fn parse_source(
+        &self,
+        source: &Source,
+    ) -> Result<(T::Partial, Vec<MergeDirective>), ConfigError> {
         let (content, cache_path): (Cow<'_, str>, Option<PathBuf>) = match source {
             Source::Code { code, .. } => (Cow::Borrowed(strip_bom(code)), None),
             Source::File { path, required } => {
@@ -325,7 +560,7 @@ impl<T: Config> ConfigLoader<T> {
                         return Err(ConfigError::MissingFile(path.to_path_buf()));
                     }
 
-                    return Ok(T::Partial::default());
+                    return Ok((T::Partial::default(), vec![]));
                 };
 
                 (Cow::Owned(strip_bom(&content).to_owned()), None)
@@ -367,7 +602,7 @@ impl<T: Config> ConfigLoader<T> {
 
         for format in &self.formats {
             if format.should_parse(source) {
-                return format.parse(source, &content, cache_path.as_deref());
+                return format.parse_with_directives(source, &content, cache_path.as_deref());
             }
         }
 
@@ -403,3 +638,41 @@ impl<T: Config> ConfigLoader<T> {
         }
     }
 }
+
+fn serialize_partial<P: Serialize>(
+    partial: &P,
+) -> Result<serde_content::Value<'static>, ConfigError> {
+    // Secrets are redacted when serialized, which would change their value
+    reveal_secrets(|| serde_content::Serializer::new().serialize(partial)).map_err(|error| {
+        ConfigError::InterpolateFailed {
+            path: ConfigPath::default(),
+            message: error.to_string(),
+        }
+    })
+}
+
+fn deserialize_partial<P: DeserializeOwned>(
+    value: serde_content::Value<'static>,
+) -> Result<P, ConfigError> {
+    let de = serde_content::Deserializer::new(value).coerce_numbers();
+
+    serde_path_to_error::deserialize(de).map_err(|error| ConfigError::InterpolateFailed {
+        path: ConfigPath::new(
+            error
+                .path()
+                .iter()
+                .filter_map(|segment| match segment {
+                    serde_path_to_error::Segment::Seq { index } => Some(PathSegment::Index(*index)),
+                    serde_path_to_error::Segment::Map { key } => {
+                        Some(PathSegment::Key(key.to_owned()))
+                    }
+                    serde_path_to_error::Segment::Enum { variant } => {
+                        Some(PathSegment::Variant(variant.to_owned()))
+                    }
+                    serde_path_to_error::Segment::Unknown => None,
+                })
+                .collect(),
+        ),
+        message: error.into_inner().to_string(),
+    })
+}
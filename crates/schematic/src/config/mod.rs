@@ -1,14 +1,25 @@
 mod cacher;
 mod configs;
+#[cfg_attr(
+    not(any(feature = "json", feature = "toml", feature = "yaml")),
+    allow(dead_code)
+)]
+mod directives;
+#[cfg(feature = "env")]
+pub(crate) mod env_nested;
+#[cfg(feature = "env")]
+mod env_provider;
 mod error;
 #[cfg(feature = "extends")]
 mod extender;
 mod formats;
+mod interpolator;
 mod layer;
 mod loader;
 mod merger;
 mod parser;
 mod path;
+mod secret;
 mod settings;
 mod source;
 #[cfg(feature = "validate")]
@@ -16,6 +27,9 @@ mod validator;
 
 pub use cacher::*;
 pub use configs::*;
+pub use directives::*;
+#[cfg(feature = "env")]
+pub use env_provider::*;
 pub use error::*;
 #[cfg(feature = "extends")]
 pub use extender::*;
@@ -25,6 +39,7 @@ pub use loader::*;
 pub use merger::*;
 pub use parser::*;
 pub use path::*;
+pub use secret::*;
 pub use settings::*;
 pub use source::*;
 #[cfg(feature = "validate")]
@@ -26,6 +26,11 @@ impl Path {
         Self { segments }
     }
 
+    /// Return a list of all segments within the path.
+    pub fn segments(&self) -> &[PathSegment] {
+        &self.segments
+    }
+
     /// Create a new instance and append the provided [`PathSegment`]
     /// to the end of the current path.
     pub fn join(&self, segment: PathSegment) -> Self {
@@ -0,0 +1,34 @@
+use std::cell::Cell;
+
+/// The value that secret settings are replaced with when redacted.
+pub const REDACTED: &str = "[REDACTED]";
+
+thread_local! {
+    static REVEAL_SECRETS: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Return true if settings marked with `#[setting(secret)]` will be redacted
+/// when debugging, serializing, or validating configurations. Secrets are
+/// always redacted, unless revealed with [`reveal_secrets`].
+pub fn is_redacting_secrets() -> bool {
+    !REVEAL_SECRETS.get()
+}
+
+/// Reveal settings marked with `#[setting(secret)]` for the duration of the
+/// operation, on the current thread only. This should only be used when
+/// the output is not displayed to the user, for example, when writing
+/// a partial configuration back to a file.
+pub fn reveal_secrets<T>(op: impl FnOnce() -> T) -> T {
+    struct Restore(bool);
+
+    impl Drop for Restore {
+        fn drop(&mut self) {
+            REVEAL_SECRETS.set(self.0);
+        }
+    }
+
+    // Restore the previous state even if the operation panics
+    let _restore = Restore(REVEAL_SECRETS.replace(true));
+
+    op()
+}
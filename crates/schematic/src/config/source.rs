@@ -1,3 +1,4 @@
+use super::directives::MergeDirective;
 use super::error::ConfigError;
 use crate::helpers::*;
 use serde::{Deserialize, Serialize, de::DeserializeOwned};
@@ -32,7 +33,8 @@ impl Source {
             return Source::url(value);
         }
 
-        // Extending from a file is only allowed from file parent sources
+        // Extending from a file is only allowed from file parent sources,
+        // or code sources that represent a file on disk (an absolute path)
         if is_file_like(value) {
             let value = if let Some(stripped) = value.strip_prefix("file://") {
                 stripped
@@ -40,22 +42,21 @@ impl Source {
                 value
             };
 
-            return match parent_source {
-                None => Source::file(value, true),
-                Some(Source::File {
-                    path: parent_path, ..
-                }) => {
-                    let mut path = PathBuf::from(value);
-
-                    // Not absolute, so prefix with parent
-                    if !path.has_root() {
-                        path = parent_path.parent().unwrap().join(path);
-                    }
-
-                    Source::file(path, true)
-                }
-                Some(_) => Err(ConfigError::ExtendsFromParentFileOnly),
+            let parent_path = match parent_source {
+                None => return Source::file(value, true),
+                Some(Source::File { path, .. }) => path,
+                Some(Source::Code { path, .. }) if path.is_absolute() => path,
+                Some(_) => return Err(ConfigError::ExtendsFromParentFileOnly),
             };
+
+            let mut path = PathBuf::from(value);
+
+            // Not absolute, so prefix with parent
+            if !path.has_root() {
+                path = parent_path.parent().unwrap().join(path);
+            }
+
+            return Source::file(path, true);
         }
 
         Err(ConfigError::ExtendsFromNoCode)
@@ -134,4 +135,16 @@ pub trait SourceFormat<T: DeserializeOwned> {
         content: &str,
         cache_path: Option<&Path>,
     ) -> Result<T, ConfigError>;
+
+    /// Parse the source contents and return the deserialized value, and any
+    /// merge directives that were declared within the contents. Formats that
+    /// do not support directives will parse without them.
+    fn parse_with_directives(
+        &self,
+        source: &Source,
+        content: &str,
+        cache_path: Option<&Path>,
+    ) -> Result<(T, Vec<MergeDirective>), ConfigError> {
+        Ok((self.parse(source, content, cache_path)?, vec![]))
+    }
 }
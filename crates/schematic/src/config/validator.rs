@@ -1,25 +1,48 @@
 use super::path::{Path, PathSegment};
-use miette::Diagnostic;
+use miette::{Diagnostic, Severity};
 use starbase_styles::{Style, Stylize};
 use std::borrow::Borrow;
 use thiserror::Error;
 
 pub type ValidateResult = std::result::Result<(), ValidateError>;
 
+pub type ValidateConfigResult = std::result::Result<(), Vec<ValidateError>>;
+
 /// A validator function that receives a setting value to validate, the parent
 /// configuration the setting belongs to, the current context, and can return
 /// a [`ValidateError`] on failure.
 pub type Validator<Val, Data, Ctx> = Box<dyn FnOnce(&Val, &Data, &Ctx, bool) -> ValidateResult>;
 
+/// A validator function that receives the configuration to validate (as a partial),
+/// the current context, and can return multiple [`ValidateError`]s on failure.
+/// Used for rules that span multiple settings.
+pub type ConfigValidator<Data, Ctx> = Box<dyn FnOnce(&Data, &Ctx, bool) -> ValidateConfigResult>;
+
+/// Severity of a validation failure.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ValidateSeverity {
+    /// Fails validation and loading.
+    #[default]
+    Error,
+
+    /// Does not fail validation, and is collected into
+    /// [`ConfigLoadResult.warnings`](crate::ConfigLoadResult) instead.
+    Warning,
+}
+
 /// Error for a single validation failure.
-#[derive(Clone, Debug, Diagnostic, Error)]
+#[derive(Clone, Debug, Error)]
 #[error("{}{} {message}", .path.to_string().style(Style::Id), ":".style(Style::MutedLight))]
+#[non_exhaustive]
 pub struct ValidateError {
     /// Failure message.
     pub message: String,
 
     /// Relative path to the setting that failed validation.
     pub path: Path,
+
+    /// Severity of the failure.
+    pub severity: ValidateSeverity,
 }
 
 impl ValidateError {
@@ -28,6 +51,17 @@ impl ValidateError {
         ValidateError {
             message: message.as_ref().to_owned(),
             path: Path::default(),
+            severity: ValidateSeverity::Error,
+        }
+    }
+
+    /// Create a new validation warning with the provided message. Warnings
+    /// do not cause validation to fail.
+    pub fn warning<T: AsRef<str>>(message: T) -> Self {
+        ValidateError {
+            message: message.as_ref().to_owned(),
+            path: Path::default(),
+            severity: ValidateSeverity::Warning,
         }
     }
 
@@ -36,6 +70,7 @@ impl ValidateError {
         ValidateError {
             message: "this setting is required".into(),
             path: Path::default(),
+            severity: ValidateSeverity::Error,
         }
     }
 
@@ -44,6 +79,7 @@ impl ValidateError {
         ValidateError {
             message: message.as_ref().to_owned(),
             path,
+            severity: ValidateSeverity::Error,
         }
     }
 
@@ -60,18 +96,59 @@ impl ValidateError {
         ValidateError {
             message: message.as_ref().to_owned(),
             path: Path::new(segments.into_iter().collect()),
+            severity: ValidateSeverity::Error,
         }
     }
 
+    /// Change the severity of the failure.
+    pub fn with_severity(mut self, severity: ValidateSeverity) -> Self {
+        self.severity = severity;
+        self
+    }
+
+    /// Return true if the failure is a warning.
+    pub fn is_warning(&self) -> bool {
+        self.severity == ValidateSeverity::Warning
+    }
+
     #[doc(hidden)]
     pub fn prepend_path(self, path: Path) -> Self {
         Self {
             message: self.message,
             path: path.join_path(&self.path),
+            severity: self.severity,
         }
     }
 }
 
+impl Diagnostic for ValidateError {
+    fn severity(&self) -> Option<Severity> {
+        Some(match self.severity {
+            ValidateSeverity::Error => Severity::Error,
+            ValidateSeverity::Warning => Severity::Warning,
+        })
+    }
+}
+
+/// Split validation failures into errors and warnings. If `promote` is true,
+/// warnings are treated as errors instead.
+pub(crate) fn split_warnings(
+    failures: Vec<ValidateError>,
+    promote: bool,
+) -> (Vec<ValidateError>, Vec<ValidateError>) {
+    if promote {
+        return (
+            failures
+                .into_iter()
+                .map(|error| error.with_severity(ValidateSeverity::Error))
+                .collect(),
+            vec![],
+        );
+    }
+
+    failures.into_iter().partition(|error| !error.is_warning())
+}
+
 /// Error that contains multiple validation errors, for each setting that failed.
 #[derive(Debug, Diagnostic, Error)]
 #[error("{}", self.render_errors())]
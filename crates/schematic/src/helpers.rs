@@ -53,3 +53,28 @@ pub fn extract_file_ext(value: &str) -> Option<&str> {
 
     name.rfind('.').map(|index| &name[index + 1..])
 }
+
+/// Convert a setting name into the format used by environment variables,
+/// by separating words with an underscore. For example, `maxConnections`,
+/// `max-connections`, and `max_connections` all become `MAX_CONNECTIONS`.
+pub fn to_env_name(name: &str) -> String {
+    let mut result = String::with_capacity(name.len() + 4);
+    let mut prev_lower = false;
+
+    for ch in name.chars() {
+        if ch == '-' || ch == '_' {
+            result.push('_');
+            prev_lower = false;
+            continue;
+        }
+
+        if ch.is_uppercase() && prev_lower {
+            result.push('_');
+        }
+
+        prev_lower = ch.is_lowercase() || ch.is_ascii_digit();
+        result.extend(ch.to_uppercase());
+    }
+
+    result
+}
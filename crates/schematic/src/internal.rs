@@ -1,5 +1,14 @@
-use crate::config::{ConfigError, HandlerError, MergeError, MergeResult, PartialConfig};
+use crate::config::{
+    ConfigError, HandlerError, MergeError, MergeResult, PartialConfig, REDACTED,
+    is_redacting_secrets,
+};
 use schematic_types::Schema;
+use serde::de::{self, Visitor};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+use std::marker::PhantomData;
+use std::path::{Path, PathBuf};
 use std::str::FromStr;
 
 // Handles T and Option<T> values
@@ -17,16 +26,20 @@ pub fn track_env<T>(value: Option<T>, tracker: &mut std::collections::HashSet<bo
 }
 
 #[cfg(feature = "env")]
-pub fn default_env_value<T: FromStr>(key: &str) -> crate::config::ParseEnvResult<T> {
-    parse_env_value(key, |value| parse_value(value).map(|v| Some(v)))
+pub fn default_env_value<T: FromStr>(
+    env: &dyn crate::config::EnvProvider,
+    key: &str,
+) -> crate::config::ParseEnvResult<T> {
+    parse_env_value(env, key, |value| parse_value(value).map(|v| Some(v)))
 }
 
 #[cfg(feature = "env")]
 pub fn parse_env_value<T>(
+    env: &dyn crate::config::EnvProvider,
     key: &str,
     parser: impl Fn(String) -> crate::config::ParseEnvResult<T>,
 ) -> crate::config::ParseEnvResult<T> {
-    if let Ok(value) = std::env::var(key) {
+    if let Some(value) = env.get(key) {
         return parser(value)
             .map_err(|error| HandlerError(format!("Invalid environment variable {key}. {error}")));
     }
@@ -34,6 +47,24 @@ pub fn parse_env_value<T>(
     Ok(None)
 }
 
+#[cfg(feature = "env")]
+pub fn nested_env_values<T: serde::de::DeserializeOwned>(
+    env: &dyn crate::config::EnvProvider,
+    prefix: &str,
+    delimiter: &str,
+    settings: &crate::config::ConfigSettingMap,
+    explicit_vars: &[&str],
+) -> Result<Option<T>, ConfigError> {
+    crate::config::env_nested::parse_nested_env_vars(
+        env.vars()
+            .into_iter()
+            .filter(|(key, _)| !explicit_vars.contains(&key.as_str())),
+        prefix,
+        delimiter,
+        settings,
+    )
+}
+
 pub fn parse_value<T: FromStr, V: AsRef<str>>(value: V) -> Result<T, HandlerError> {
     let value = value.as_ref();
 
@@ -60,6 +91,60 @@ pub fn merge_setting<T, C>(
     }
 }
 
+/// Merge settings marked with `#[setting(clear_on_null)]`, where the outer `Option` is
+/// `None` when unset, and the inner `Option` is `None` when explicitly set to null.
+pub fn merge_clearable_setting<T, C>(
+    prev: Option<Option<T>>,
+    next: Option<Option<T>>,
+    context: &C,
+    merger: impl Fn(T, T, &C) -> MergeResult<T>,
+) -> MergeResult<Option<T>> {
+    match next {
+        // Not set, so keep the previous value
+        None => Ok(prev),
+        // Explicitly null, so clear the previous value
+        Some(None) => Ok(Some(None)),
+        // Otherwise merge with the previous value, if not cleared
+        Some(next) => Ok(merge_setting(prev.flatten(), next, context, merger)?.map(Some)),
+    }
+}
+
+/// Deserialize settings marked with `#[setting(clear_on_null)]`, so that an explicit
+/// null is deserialized as `Some(None)`, instead of `None`.
+pub fn deserialize_clearable<'de, T: Deserialize<'de>, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Option<Option<T>>, D::Error> {
+    struct ClearableVisitor<T>(PhantomData<T>);
+
+    impl<'de, T: Deserialize<'de>> Visitor<'de> for ClearableVisitor<T> {
+        type Value = Option<T>;
+
+        fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+            formatter.write_str("an optional value")
+        }
+
+        fn visit_none<E: de::Error>(self) -> Result<Self::Value, E> {
+            Ok(None)
+        }
+
+        fn visit_unit<E: de::Error>(self) -> Result<Self::Value, E> {
+            Ok(None)
+        }
+
+        fn visit_some<D: Deserializer<'de>>(
+            self,
+            deserializer: D,
+        ) -> Result<Self::Value, D::Error> {
+            // Values that were serialized from a partial are doubly wrapped
+            Option::<T>::deserialize(deserializer)
+        }
+    }
+
+    deserializer
+        .deserialize_option(ClearableVisitor(PhantomData))
+        .map(Some)
+}
+
 #[allow(clippy::unnecessary_unwrap)]
 pub fn merge_nested_setting<T: PartialConfig>(
     prev: Option<T>,
@@ -81,6 +166,184 @@ pub fn merge_nested_setting<T: PartialConfig>(
     }
 }
 
+pub fn get_merge_key<T: serde::de::DeserializeOwned>(
+    value: &serde_content::Value,
+    get_key: impl Fn(&T) -> Option<String>,
+) -> Option<String> {
+    let item = serde_content::Deserializer::new(value.clone())
+        .coerce_numbers()
+        .deserialize::<T>()
+        .ok()?;
+
+    get_key(&item)
+}
+
+pub trait RelativePath {
+    fn resolve_relative_path(&mut self, base_dir: &Path);
+}
+
+impl RelativePath for PathBuf {
+    fn resolve_relative_path(&mut self, base_dir: &Path) {
+        if self.is_relative() {
+            *self = base_dir.join(&self);
+        }
+    }
+}
+
+impl<T: RelativePath> RelativePath for Box<T> {
+    fn resolve_relative_path(&mut self, base_dir: &Path) {
+        self.as_mut().resolve_relative_path(base_dir);
+    }
+}
+
+impl<T: RelativePath> RelativePath for Option<T> {
+    fn resolve_relative_path(&mut self, base_dir: &Path) {
+        if let Some(value) = self {
+            value.resolve_relative_path(base_dir);
+        }
+    }
+}
+
+impl<T: RelativePath> RelativePath for Vec<T> {
+    fn resolve_relative_path(&mut self, base_dir: &Path) {
+        for value in self {
+            value.resolve_relative_path(base_dir);
+        }
+    }
+}
+
+impl<K, V: RelativePath> RelativePath for BTreeMap<K, V> {
+    fn resolve_relative_path(&mut self, base_dir: &Path) {
+        for value in self.values_mut() {
+            value.resolve_relative_path(base_dir);
+        }
+    }
+}
+
+impl<K, V: RelativePath, S> RelativePath for HashMap<K, V, S> {
+    fn resolve_relative_path(&mut self, base_dir: &Path) {
+        for value in self.values_mut() {
+            value.resolve_relative_path(base_dir);
+        }
+    }
+}
+
+pub fn serialize_secret<T: Serialize, S: Serializer>(
+    value: &Option<T>,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    match value {
+        Some(_) if is_redacting_secrets() => serializer.serialize_some(REDACTED),
+        _ => value.serialize(serializer),
+    }
+}
+
+pub fn serialize_secret_value<T: Serialize, S: Serializer>(
+    value: &T,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    if is_redacting_secrets() {
+        serializer.serialize_str(REDACTED)
+    } else {
+        value.serialize(serializer)
+    }
+}
+
+pub struct DebugSecret<'a, T>(pub &'a T);
+
+impl<T: fmt::Debug> fmt::Debug for DebugSecret<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if is_redacting_secrets() {
+            REDACTED.fmt(f)
+        } else {
+            self.0.fmt(f)
+        }
+    }
+}
+
+#[cfg(feature = "validate")]
+fn collect_secret_values(value: &serde_content::Value, values: &mut Vec<String>) {
+    use serde_content::{Data, Number, Value};
+
+    let mut collect_data = |data: &Data| match data {
+        Data::Unit => {}
+        Data::NewType { value } => collect_secret_values(value, values),
+        Data::Tuple { values: items } => {
+            for item in items {
+                collect_secret_values(item, values);
+            }
+        }
+        Data::Struct { fields } => {
+            for (_, item) in fields {
+                collect_secret_values(item, values);
+            }
+        }
+    };
+
+    match value {
+        Value::Number(number) => values.push(match number {
+            Number::I8(inner) => inner.to_string(),
+            Number::U8(inner) => inner.to_string(),
+            Number::I16(inner) => inner.to_string(),
+            Number::U16(inner) => inner.to_string(),
+            Number::I32(inner) => inner.to_string(),
+            Number::U32(inner) => inner.to_string(),
+            Number::F32(inner) => inner.to_string(),
+            Number::I64(inner) => inner.to_string(),
+            Number::U64(inner) => inner.to_string(),
+            Number::F64(inner) => inner.to_string(),
+            Number::I128(inner) => inner.to_string(),
+            Number::U128(inner) => inner.to_string(),
+            _ => return,
+        }),
+        Value::Char(inner) => values.push(inner.to_string()),
+        Value::String(inner) => values.push(inner.to_string()),
+        Value::Seq(items) | Value::Tuple(items) => {
+            for item in items {
+                collect_secret_values(item, values);
+            }
+        }
+        Value::Map(items) => {
+            for (key, item) in items {
+                collect_secret_values(key, values);
+                collect_secret_values(item, values);
+            }
+        }
+        Value::Option(Some(inner)) => collect_secret_values(inner, values),
+        Value::Struct(inner) => collect_data(&inner.data),
+        Value::Enum(inner) => collect_data(&inner.data),
+        _ => {}
+    };
+}
+
+#[cfg(feature = "validate")]
+pub fn redact_validate_error<T: Serialize>(
+    mut error: crate::config::ValidateError,
+    value: &T,
+) -> crate::config::ValidateError {
+    if !is_redacting_secrets() {
+        return error;
+    }
+
+    let mut values = vec![];
+
+    if let Ok(value) =
+        crate::config::reveal_secrets(|| serde_content::Serializer::new().serialize(value))
+    {
+        collect_secret_values(&value, &mut values);
+    }
+
+    // Replace the longest values first, so that partial matches are not left behind
+    values.retain(|value| !value.is_empty());
+    values.sort_by_key(|value| std::cmp::Reverse(value.len()));
+
+    for value in values {
+        error.message = error.message.replace(&value, REDACTED);
+    }
+
+    error
+}
+
 pub fn partialize_schema(schema: &mut Schema, force_partial: bool) {
     use schematic_types::*;
 
@@ -1,7 +1,7 @@
-use crate::config::MergeResult;
+use crate::config::{MergeError, MergeResult, PartialConfig};
 use std::{
     collections::{BTreeMap, BTreeSet, HashMap, HashSet},
-    hash::Hash,
+    hash::{BuildHasher, Hash},
 };
 
 /// Discard both previous and next values and return [`None`].
@@ -96,3 +96,114 @@ where
 
     Ok(Some(prev))
 }
+
+/// Deep merge the next list of partial configs into the previous list. Items at the same
+/// index are merged with [`PartialConfig::merge`], while additional items are appended.
+pub fn merge_nested_vec<T: PartialConfig>(
+    mut prev: Vec<T>,
+    next: Vec<T>,
+    context: &T::Context,
+) -> MergeResult<Vec<T>> {
+    for (index, item) in next.into_iter().enumerate() {
+        match prev.get_mut(index) {
+            Some(prev_item) => {
+                prev_item
+                    .merge(context, item)
+                    .map_err(|error| MergeError(error.to_string()))?;
+            }
+            None => {
+                prev.push(item);
+            }
+        };
+    }
+
+    Ok(Some(prev))
+}
+
+/// Deep merge the next list of partial configs into the previous list, by matching items
+/// on a key returned from the provided function. Items with the same key are merged with
+/// [`PartialConfig::merge`], while items with a new key (or without a key) are appended.
+pub fn merge_nested_vec_by_key<T: PartialConfig>(
+    mut prev: Vec<T>,
+    next: Vec<T>,
+    context: &T::Context,
+    get_key: impl Fn(&T) -> Option<String>,
+) -> MergeResult<Vec<T>> {
+    for item in next {
+        let Some(key) = get_key(&item) else {
+            prev.push(item);
+            continue;
+        };
+
+        match prev
+            .iter_mut()
+            .find(|prev_item| get_key(prev_item).is_some_and(|k| k == key))
+        {
+            Some(prev_item) => {
+                prev_item
+                    .merge(context, item)
+                    .map_err(|error| MergeError(error.to_string()))?;
+            }
+            None => {
+                prev.push(item);
+            }
+        };
+    }
+
+    Ok(Some(prev))
+}
+
+/// Deep merge the next [`BTreeMap`] of partial configs into the previous [`BTreeMap`]. Items
+/// of the same key are merged with [`PartialConfig::merge`], while new items are inserted.
+pub fn merge_nested_btreemap<K, V>(
+    mut prev: BTreeMap<K, V>,
+    next: BTreeMap<K, V>,
+    context: &V::Context,
+) -> MergeResult<BTreeMap<K, V>>
+where
+    K: Ord,
+    V: PartialConfig,
+{
+    for (key, value) in next {
+        match prev.get_mut(&key) {
+            Some(prev_value) => {
+                prev_value
+                    .merge(context, value)
+                    .map_err(|error| MergeError(error.to_string()))?;
+            }
+            None => {
+                prev.insert(key, value);
+            }
+        };
+    }
+
+    Ok(Some(prev))
+}
+
+/// Deep merge the next [`HashMap`] of partial configs into the previous [`HashMap`]. Items
+/// of the same key are merged with [`PartialConfig::merge`], while new items are inserted.
+pub fn merge_nested_hashmap<K, V, S>(
+    mut prev: HashMap<K, V, S>,
+    next: HashMap<K, V, S>,
+    context: &V::Context,
+) -> MergeResult<HashMap<K, V, S>>
+where
+    K: Eq + Hash,
+    V: PartialConfig,
+    S: BuildHasher,
+{
+    for (key, value) in next {
+        match prev.get_mut(&key) {
+            Some(prev_value) => {
+                prev_value
+                    .merge(context, value)
+                    .map_err(|error| MergeError(error.to_string()))?;
+            }
+            None => {
+                prev.insert(key, value);
+            }
+        };
+    }
+
+    Ok(Some(prev))
+}
@@ -0,0 +1,994 @@
+use convert_case::{Case, Casing};
+use indexmap::IndexMap;
+use miette::miette;
+use schematic_types::*;
+use serde_json::{Map, Number, Value};
+use std::collections::{BTreeMap, HashSet};
+use std::fmt;
+
+/// Keywords that contain definitions, for draft-07 and 2020-12 respectively.
+const DEFINITION_KEYWORDS: [&str; 2] = ["definitions", "$defs"];
+
+/// Keywords that only annotate a schema, and have no effect on the imported type.
+const ANNOTATION_KEYWORDS: [&str; 6] = [
+    "$comment",
+    "$id",
+    "$schema",
+    "examples",
+    "markdownDescription",
+    "title",
+];
+
+/// Keywords that describe a schema, and are applied after the type is imported.
+const METADATA_KEYWORDS: [&str; 5] = [
+    "default",
+    "deprecated",
+    "description",
+    "readOnly",
+    "writeOnly",
+];
+
+/// Keywords that are imported, either as a type or a constraint.
+const SUPPORTED_KEYWORDS: [&str; 31] = [
+    "$ref",
+    "additionalItems",
+    "additionalProperties",
+    "allOf",
+    "anyOf",
+    "const",
+    "contains",
+    "enum",
+    "exclusiveMaximum",
+    "exclusiveMinimum",
+    "format",
+    "items",
+    "maxContains",
+    "maximum",
+    "maxItems",
+    "maxLength",
+    "maxProperties",
+    "minContains",
+    "minimum",
+    "minItems",
+    "minLength",
+    "minProperties",
+    "multipleOf",
+    "oneOf",
+    "pattern",
+    "prefixItems",
+    "properties",
+    "propertyNames",
+    "required",
+    "type",
+    "uniqueItems",
+];
+
+/// A keyword that could not be imported, and was not represented in the
+/// resulting schemas.
+#[derive(Clone, Debug, PartialEq)]
+pub struct UnsupportedKeyword {
+    /// Name of the keyword.
+    pub keyword: String,
+
+    /// JSON pointer to the schema that contains the keyword.
+    pub pointer: String,
+}
+
+impl fmt::Display for UnsupportedKeyword {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} at {}", self.keyword, self.pointer)
+    }
+}
+
+/// Options to control the JSON schema import.
+#[derive(Default)]
+pub struct JsonSchemaImporterOptions {
+    /// Name of the root schema. Defaults to the document's `title`,
+    /// otherwise `Root`. When the root only references a definition,
+    /// the definition is renamed instead.
+    pub root_name: Option<String>,
+}
+
+/// The result of importing a JSON schema document.
+#[derive(Debug, Default)]
+pub struct JsonSchemaImport {
+    /// Named schemas, with definitions first and the root schema last.
+    pub schemas: IndexMap<String, Schema>,
+
+    /// Keywords that are not supported, and were not imported.
+    pub unsupported: Vec<UnsupportedKeyword>,
+}
+
+impl JsonSchemaImport {
+    /// Return the root schema, which is always the last schema.
+    pub fn root(&self) -> Option<&Schema> {
+        self.schemas.last().map(|(_, schema)| schema)
+    }
+}
+
+/// Imports a JSON schema document (draft-07 or 2020-12) into named [`Schema`]s,
+/// that can be added to a [`SchemaGenerator`](crate::schema::SchemaGenerator)
+/// and rendered with any renderer.
+#[derive(Default)]
+pub struct JsonSchemaImporter {
+    options: JsonSchemaImporterOptions,
+
+    /// Map of local references (`#/$defs/Name`) to schema names and values.
+    definitions: IndexMap<String, (String, Value)>,
+    names: HashSet<String>,
+    schemas: IndexMap<String, Schema>,
+    unsupported: Vec<UnsupportedKeyword>,
+}
+
+impl JsonSchemaImporter {
+    pub fn new(options: JsonSchemaImporterOptions) -> Self {
+        Self {
+            options,
+            ..Default::default()
+        }
+    }
+
+    /// Import the provided JSON schema document. Definitions within `definitions`
+    /// and `$defs` are imported as named schemas, and references to them are
+    /// imported as [`SchemaType::Reference`]s.
+    pub fn import(mut self, document: &Value) -> miette::Result<JsonSchemaImport> {
+        let mut root = match document {
+            Value::Object(map) => map.clone(),
+            Value::Bool(_) => Map::default(),
+            _ => {
+                return Err(miette!(
+                    "Invalid JSON schema document, expected an object or boolean."
+                ));
+            }
+        };
+
+        // Register all definitions first, so that references can be resolved
+        for keyword in DEFINITION_KEYWORDS {
+            match root.remove(keyword) {
+                Some(Value::Object(defs)) => {
+                    for (key, value) in defs {
+                        let name = self.create_name(&key);
+
+                        self.definitions.insert(
+                            format!("#/{keyword}/{}", escape_pointer(&key)),
+                            (name, value),
+                        );
+                    }
+                }
+                Some(_) => self.report(keyword, "#"),
+                None => {}
+            };
+        }
+
+        // A root that only references a definition is the definition itself
+        let root_reference = match root.get("$ref") {
+            Some(Value::String(reference))
+                if self.definitions.contains_key(reference)
+                    && root.keys().all(|key| {
+                        key == "$ref" || ANNOTATION_KEYWORDS.contains(&key.as_str())
+                    }) =>
+            {
+                Some(reference.to_owned())
+            }
+            _ => None,
+        };
+
+        let root_name = match &root_reference {
+            Some(reference) => {
+                let name = self.definitions[reference].0.clone();
+
+                // Rename the definition, so that references to it use the custom name
+                match self.options.root_name.clone() {
+                    Some(root_name) => {
+                        self.names.remove(&name);
+
+                        let root_name = self.create_name(&root_name);
+                        self.definitions[reference].0 = root_name.clone();
+
+                        root_name
+                    }
+                    None => name,
+                }
+            }
+            None => {
+                let name =
+                    self.options
+                        .root_name
+                        .clone()
+                        .unwrap_or_else(|| match root.get("title") {
+                            Some(Value::String(title)) => title.to_owned(),
+                            _ => "Root".into(),
+                        });
+
+                self.create_name(&name)
+            }
+        };
+
+        self.definitions
+            .insert("#".into(), (root_name.clone(), Value::Object(root.clone())));
+
+        let definitions = self
+            .definitions
+            .iter()
+            .filter(|(reference, _)| *reference != "#")
+            .map(|(reference, (name, value))| {
+                (reference.to_owned(), name.to_owned(), value.clone())
+            })
+            .collect::<Vec<_>>();
+
+        for (reference, name, value) in definitions {
+            let mut schema = self.import_schema(&value, &reference)?;
+            schema.name = Some(name.clone());
+
+            self.schemas.insert(name, schema);
+        }
+
+        match root_reference {
+            Some(_) => {
+                if let Some(schema) = self.schemas.shift_remove(&root_name) {
+                    self.schemas.insert(root_name, schema);
+                }
+            }
+            None => {
+                let mut schema = self.import_object(&root, "#")?;
+
+                // Documents that only contain definitions have no root
+                if !matches!(schema.ty, SchemaType::Unknown) || self.schemas.is_empty() {
+                    schema.name = Some(root_name.clone());
+
+                    self.schemas.insert(root_name, schema);
+                }
+            }
+        };
+
+        Ok(JsonSchemaImport {
+            schemas: self.schemas,
+            unsupported: self.unsupported,
+        })
+    }
+
+    fn create_name(&mut self, key: &str) -> String {
+        let mut base = key.to_case(Case::Pascal);
+        base.retain(|ch| ch.is_ascii_alphanumeric() || ch == '_');
+
+        if base.is_empty() || base.starts_with(|ch: char| ch.is_ascii_digit()) {
+            base.insert(0, '_');
+        }
+
+        let mut name = base.clone();
+        let mut count = 1;
+
+        while self.names.contains(&name) {
+            count += 1;
+            name = format!("{base}{count}");
+        }
+
+        self.names.insert(name.clone());
+        name
+    }
+
+    fn report(&mut self, keyword: &str, pointer: &str) {
+        let unsupported = UnsupportedKeyword {
+            keyword: keyword.to_owned(),
+            pointer: pointer.to_owned(),
+        };
+
+        if !self.unsupported.contains(&unsupported) {
+            self.unsupported.push(unsupported);
+        }
+    }
+
+    fn import_schema(&mut self, value: &Value, pointer: &str) -> miette::Result<Schema> {
+        match value {
+            Value::Bool(true) => Ok(Schema::unknown()),
+            Value::Bool(false) => {
+                self.report("false", pointer);
+
+                Ok(Schema::unknown())
+            }
+            Value::Object(map) => self.import_object(map, pointer),
+            _ => Err(miette!(
+                "Invalid schema at {pointer}, expected an object or boolean."
+            )),
+        }
+    }
+
+    fn import_object(&mut self, map: &Map<String, Value>, pointer: &str) -> miette::Result<Schema> {
+        let map = self.merge_all_of(map, pointer, &mut vec![])?;
+
+        for key in map.keys() {
+            if !SUPPORTED_KEYWORDS.contains(&key.as_str())
+                && !METADATA_KEYWORDS.contains(&key.as_str())
+                && !ANNOTATION_KEYWORDS.contains(&key.as_str())
+            {
+                self.report(key, pointer);
+            }
+        }
+
+        let mut schema = if let Some(reference) = map.get("$ref") {
+            // Keywords next to a reference that could not be merged are dropped
+            for key in map.keys() {
+                if key != "$ref" && SUPPORTED_KEYWORDS.contains(&key.as_str()) {
+                    self.report(key, pointer);
+                }
+            }
+
+            self.import_reference(reference, pointer)?
+        } else if map.contains_key("oneOf") || map.contains_key("anyOf") {
+            self.import_union(&map, pointer)?
+        } else if let Some(value) = map.get("const") {
+            self.import_const(value, pointer)
+        } else if let Some(value) = map.get("enum") {
+            self.import_enum(value, pointer)
+        } else {
+            match map.get("type") {
+                Some(Value::String(ty)) => self.import_type(ty, &map, pointer)?,
+                Some(Value::Array(types)) => self.import_types(types, &map, pointer)?,
+                Some(_) => {
+                    return Err(miette!(
+                        "Invalid type at {pointer}, expected a string or list of strings."
+                    ));
+                }
+                None => match infer_type(&map) {
+                    Some(ty) => self.import_type(ty, &map, pointer)?,
+                    None => Schema::unknown(),
+                },
+            }
+        };
+
+        if let Some(Value::String(description)) = map.get("description") {
+            schema.description = Some(description.to_owned());
+        }
+
+        match map.get("deprecated") {
+            Some(Value::Bool(true)) => {
+                schema.deprecated = Some(String::new());
+            }
+            Some(Value::String(message)) => {
+                schema.deprecated = Some(message.to_owned());
+            }
+            _ => {}
+        };
+
+        if let Some(default) = map.get("default")
+            && !default.is_null()
+            && !to_literal(default).is_some_and(|value| set_default(&mut schema.ty, value))
+        {
+            self.report("default", pointer);
+        }
+
+        if schema.ty.is_nullable() {
+            schema.nullable = true;
+        }
+
+        Ok(schema)
+    }
+
+    /// Merge all subschemas of `allOf` into the current schema, resolving
+    /// references to definitions so that they can be merged as well.
+    fn merge_all_of(
+        &mut self,
+        map: &Map<String, Value>,
+        pointer: &str,
+        stack: &mut Vec<String>,
+    ) -> miette::Result<Map<String, Value>> {
+        let mut merged = map.clone();
+
+        // Keywords next to a reference apply in addition to it (2019-09 and later),
+        // so the reference is merged with them as if it were in `allOf`
+        if let Some(Value::String(reference)) = merged.get("$ref")
+            && self.definitions.contains_key(reference)
+            && merged
+                .keys()
+                .any(|key| key != "$ref" && SUPPORTED_KEYWORDS.contains(&key.as_str()))
+        {
+            let reference = merged.remove("$ref").unwrap();
+
+            let mut items = match merged.remove("allOf") {
+                Some(Value::Array(items)) => items,
+                Some(_) => {
+                    self.report("allOf", pointer);
+
+                    vec![]
+                }
+                None => vec![],
+            };
+
+            items.insert(
+                0,
+                Value::Object(Map::from_iter([("$ref".into(), reference)])),
+            );
+            merged.insert("allOf".into(), Value::Array(items));
+        }
+
+        let Some(all_of) = merged.remove("allOf") else {
+            return Ok(merged);
+        };
+
+        let Value::Array(items) = all_of else {
+            self.report("allOf", pointer);
+
+            return Ok(merged);
+        };
+
+        // A single reference with metadata, as rendered by draft-07 generators,
+        // is kept as a reference instead of being inlined
+        if let [Value::Object(item)] = items.as_slice()
+            && item.len() == 1
+            && item.contains_key("$ref")
+            && merged.keys().all(|key| {
+                METADATA_KEYWORDS.contains(&key.as_str())
+                    || ANNOTATION_KEYWORDS.contains(&key.as_str())
+            })
+        {
+            merged.extend(item.clone());
+
+            return Ok(merged);
+        }
+
+        for item in items {
+            let item = match &item {
+                Value::Object(item) => match item.get("$ref") {
+                    Some(Value::String(reference))
+                        if item.keys().all(|key| {
+                            key == "$ref"
+                                || METADATA_KEYWORDS.contains(&key.as_str())
+                                || ANNOTATION_KEYWORDS.contains(&key.as_str())
+                        }) =>
+                    {
+                        // Avoid infinite recursion with cyclical references
+                        if stack.contains(reference) {
+                            self.report("allOf", pointer);
+                            continue;
+                        }
+
+                        let Some((_, value)) = self.definitions.get(reference).cloned() else {
+                            return Err(miette!("Unknown reference {reference} at {pointer}."));
+                        };
+
+                        let Value::Object(value) = value else {
+                            continue;
+                        };
+
+                        stack.push(reference.to_owned());
+                        let value = self.merge_all_of(&value, pointer, stack)?;
+                        stack.pop();
+
+                        value
+                    }
+                    _ => self.merge_all_of(item, pointer, stack)?,
+                },
+                Value::Bool(true) => continue,
+                _ => {
+                    self.report("allOf", pointer);
+                    continue;
+                }
+            };
+
+            for (key, value) in item {
+                match (merged.get_mut(&key), value) {
+                    (None, value) => {
+                        merged.insert(key, value);
+                    }
+                    (Some(Value::Object(current)), Value::Object(value)) if key == "properties" => {
+                        for (name, property) in value {
+                            current.entry(name).or_insert(property);
+                        }
+                    }
+                    (Some(Value::Array(current)), Value::Array(value)) if key == "required" => {
+                        for name in value {
+                            if !current.contains(&name) {
+                                current.push(name);
+                            }
+                        }
+                    }
+                    (Some(current), value) => {
+                        if *current != value
+                            && !METADATA_KEYWORDS.contains(&key.as_str())
+                            && !ANNOTATION_KEYWORDS.contains(&key.as_str())
+                        {
+                            self.report("allOf", pointer);
+                        }
+                    }
+                };
+            }
+        }
+
+        Ok(merged)
+    }
+
+    fn import_reference(&mut self, reference: &Value, pointer: &str) -> miette::Result<Schema> {
+        let Value::String(reference) = reference else {
+            return Err(miette!("Invalid $ref at {pointer}, expected a string."));
+        };
+
+        if let Some((name, _)) = self.definitions.get(reference) {
+            return Ok(Schema::new(SchemaType::Reference(name.to_owned())));
+        }
+
+        // Local references must point to a definition
+        if reference.starts_with("#/$defs/") || reference.starts_with("#/definitions/") {
+            return Err(miette!("Unknown reference {reference} at {pointer}."));
+        }
+
+        self.report("$ref", pointer);
+
+        Ok(Schema::unknown())
+    }
+
+    fn import_union(&mut self, map: &Map<String, Value>, pointer: &str) -> miette::Result<Schema> {
+        let (keyword, operator) = if map.contains_key("oneOf") {
+            if map.contains_key("anyOf") {
+                self.report("anyOf", pointer);
+            }
+
+            ("oneOf", UnionOperator::OneOf)
+        } else {
+            ("anyOf", UnionOperator::AnyOf)
+        };
+
+        let Some(Value::Array(variants)) = map.get(keyword) else {
+            return Err(miette!("Invalid {keyword} at {pointer}, expected a list."));
+        };
+
+        // Sibling keywords apply to every variant
+        let base = map
+            .iter()
+            .filter(|(key, _)| {
+                *key != "anyOf" && *key != "oneOf" && SUPPORTED_KEYWORDS.contains(&key.as_str())
+            })
+            .map(|(key, value)| (key.to_owned(), value.to_owned()))
+            .collect::<Map<_, _>>();
+
+        let mut variants_types = vec![];
+
+        for (index, variant) in variants.iter().enumerate() {
+            let variant_pointer = format!("{pointer}/{keyword}/{index}");
+
+            let schema = match variant {
+                Value::Object(variant) if !base.is_empty() => {
+                    let mut merged = base.clone();
+                    merged.extend(variant.clone());
+
+                    self.import_object(&merged, &variant_pointer)?
+                }
+                variant => self.import_schema(variant, &variant_pointer)?,
+            };
+
+            variants_types.push(Box::new(schema));
+        }
+
+        Ok(Schema::union(UnionType {
+            operator,
+            variants_types,
+            ..UnionType::default()
+        }))
+    }
+
+    fn import_const(&mut self, value: &Value, pointer: &str) -> Schema {
+        if value.is_null() {
+            return Schema::null();
+        }
+
+        match to_literal(value) {
+            Some(value) => Schema::literal_value(value),
+            None => {
+                self.report("const", pointer);
+
+                Schema::unknown()
+            }
+        }
+    }
+
+    fn import_enum(&mut self, value: &Value, pointer: &str) -> Schema {
+        let Value::Array(items) = value else {
+            self.report("enum", pointer);
+
+            return Schema::unknown();
+        };
+
+        let mut values = vec![];
+        let mut nullable = false;
+
+        for item in items {
+            if item.is_null() {
+                nullable = true;
+            } else if let Some(value) = to_literal(item) {
+                values.push(value);
+            } else {
+                self.report("enum", pointer);
+
+                return Schema::unknown();
+            }
+        }
+
+        let schema = Schema::enumerable(EnumType::new(values));
+
+        if nullable {
+            return Schema::union(UnionType::new_any([schema, Schema::null()]));
+        }
+
+        schema
+    }
+
+    fn import_types(
+        &mut self,
+        types: &[Value],
+        map: &Map<String, Value>,
+        pointer: &str,
+    ) -> miette::Result<Schema> {
+        let mut variants_types = vec![];
+
+        for ty in types {
+            let Value::String(ty) = ty else {
+                return Err(miette!(
+                    "Invalid type at {pointer}, expected a string or list of strings."
+                ));
+            };
+
+            variants_types.push(self.import_type(ty, map, pointer)?);
+        }
+
+        if variants_types.len() == 1 {
+            return Ok(variants_types.remove(0));
+        }
+
+        Ok(Schema::union(UnionType::new_any(variants_types)))
+    }
+
+    fn import_type(
+        &mut self,
+        ty: &str,
+        map: &Map<String, Value>,
+        pointer: &str,
+    ) -> miette::Result<Schema> {
+        let schema = match ty {
+            "array" => self.import_array(map, pointer)?,
+            "boolean" => Schema::boolean(BooleanType::default()),
+            "integer" => {
+                let min = get_number(map, "minimum");
+
+                Schema::integer(IntegerType {
+                    format: get_string(map, "format"),
+                    kind: if min.is_some_and(|min| min >= 0.0) {
+                        IntegerKind::U64
+                    } else {
+                        IntegerKind::I64
+                    },
+                    max: get_number(map, "maximum").map(|value| value as isize),
+                    max_exclusive: get_exclusive(map, "exclusiveMaximum", "maximum")
+                        .map(|value| value as isize),
+                    min: min.map(|value| value as isize),
+                    min_exclusive: get_exclusive(map, "exclusiveMinimum", "minimum")
+                        .map(|value| value as isize),
+                    multiple_of: get_number(map, "multipleOf").map(|value| value as isize),
+                    ..IntegerType::default()
+                })
+            }
+            "null" => Schema::null(),
+            "number" => Schema::float(FloatType {
+                format: get_string(map, "format"),
+                kind: FloatKind::F64,
+                max: get_number(map, "maximum"),
+                max_exclusive: get_exclusive(map, "exclusiveMaximum", "maximum"),
+                min: get_number(map, "minimum"),
+                min_exclusive: get_exclusive(map, "exclusiveMinimum", "minimum"),
+                multiple_of: get_number(map, "multipleOf"),
+                ..FloatType::default()
+            }),
+            "object" => self.import_object_type(map, pointer)?,
+            "string" => Schema::string(StringType {
+                format: get_string(map, "format"),
+                max_length: get_size(map, "maxLength"),
+                min_length: get_size(map, "minLength"),
+                pattern: get_string(map, "pattern"),
+                ..StringType::default()
+            }),
+            other => {
+                return Err(miette!("Unknown type {other} at {pointer}."));
+            }
+        };
+
+        Ok(schema)
+    }
+
+    fn import_array(&mut self, map: &Map<String, Value>, pointer: &str) -> miette::Result<Schema> {
+        let prefix_items = match map.get("prefixItems") {
+            Some(Value::Array(items)) => Some(("prefixItems", items, "items")),
+            _ => match map.get("items") {
+                Some(Value::Array(items)) => Some(("items", items, "additionalItems")),
+                _ => None,
+            },
+        };
+
+        // Tuples
+        if let Some((keyword, items, rest_keyword)) = prefix_items {
+            if map
+                .get(rest_keyword)
+                .is_some_and(|rest| *rest != Value::Bool(false))
+            {
+                self.report(rest_keyword, pointer);
+            }
+
+            // Tuples always have a fixed length and may contain duplicates
+            for keyword in ["maxItems", "minItems"] {
+                if map
+                    .get(keyword)
+                    .is_some_and(|value| value.as_u64() != Some(items.len() as u64))
+                {
+                    self.report(keyword, pointer);
+                }
+            }
+
+            if map.get("uniqueItems") == Some(&Value::Bool(true)) {
+                self.report("uniqueItems", pointer);
+            }
+
+            let mut items_types = vec![];
+
+            for (index, item) in items.iter().enumerate() {
+                items_types
+                    .push(self.import_schema(item, &format!("{pointer}/{keyword}/{index}"))?);
+            }
+
+            return Ok(Schema::tuple(TupleType::new(items_types)));
+        }
+
+        let mut array = ArrayType {
+            max_length: get_size(map, "maxItems"),
+            min_length: get_size(map, "minItems"),
+            unique: match map.get("uniqueItems") {
+                Some(Value::Bool(true)) => Some(true),
+                _ => None,
+            },
+            ..ArrayType::default()
+        };
+
+        match (map.get("items"), map.get("contains")) {
+            (Some(items), contains) => {
+                array.items_type =
+                    Box::new(self.import_schema(items, &format!("{pointer}/items"))?);
+
+                if contains.is_some() {
+                    self.report("contains", pointer);
+                }
+            }
+            (None, Some(contains)) => {
+                array.contains = Some(true);
+                array.items_type =
+                    Box::new(self.import_schema(contains, &format!("{pointer}/contains"))?);
+                array.max_contains = get_size(map, "maxContains");
+                array.min_contains = get_size(map, "minContains");
+            }
+            (None, None) => {
+                array.items_type = Box::new(Schema::unknown());
+            }
+        };
+
+        Ok(Schema::array(array))
+    }
+
+    fn import_object_type(
+        &mut self,
+        map: &Map<String, Value>,
+        pointer: &str,
+    ) -> miette::Result<Schema> {
+        let required = match map.get("required") {
+            Some(Value::Array(names)) => names
+                .iter()
+                .filter_map(|name| name.as_str().map(|name| name.to_owned()))
+                .collect::<Vec<_>>(),
+            _ => vec![],
+        };
+
+        // Structs
+        if let Some(properties) = map.get("properties") {
+            let Value::Object(properties) = properties else {
+                return Err(miette!(
+                    "Invalid properties at {pointer}, expected an object."
+                ));
+            };
+
+            // Structs are closed and have no size constraints, so these can't be represented
+            if map
+                .get("additionalProperties")
+                .is_some_and(|value| *value != Value::Bool(false))
+            {
+                self.report("additionalProperties", pointer);
+            }
+
+            for keyword in ["maxProperties", "minProperties", "propertyNames"] {
+                if map.contains_key(keyword) {
+                    self.report(keyword, pointer);
+                }
+            }
+
+            let mut fields = BTreeMap::new();
+
+            for (name, property) in properties {
+                let schema = self.import_schema(
+                    property,
+                    &format!("{pointer}/properties/{}", escape_pointer(name)),
+                )?;
+
+                let mut field = SchemaField::from(schema);
+                field.deprecated = field.schema.deprecated.take();
+                field.nullable = field.schema.nullable;
+                field.optional = !required.contains(name);
+                field.read_only = property.get("readOnly") == Some(&Value::Bool(true));
+                field.write_only = property.get("writeOnly") == Some(&Value::Bool(true));
+
+                fields.insert(name.to_owned(), Box::new(field));
+            }
+
+            return Ok(Schema::structure(StructType {
+                fields,
+                ..StructType::default()
+            }));
+        }
+
+        // Maps
+        let value_type = match map.get("additionalProperties") {
+            // No properties are allowed, so an empty struct
+            Some(Value::Bool(false)) => {
+                return Ok(Schema::structure(StructType::default()));
+            }
+            Some(value) => self.import_schema(value, &format!("{pointer}/additionalProperties"))?,
+            None => Schema::unknown(),
+        };
+
+        let key_type = match map.get("propertyNames") {
+            Some(value) => self.import_schema(value, &format!("{pointer}/propertyNames"))?,
+            None => Schema::string(StringType::default()),
+        };
+
+        Ok(Schema::object(ObjectType {
+            key_type: Box::new(key_type),
+            max_length: get_size(map, "maxProperties"),
+            min_length: get_size(map, "minProperties"),
+            required: if required.is_empty() {
+                None
+            } else {
+                Some(required)
+            },
+            value_type: Box::new(value_type),
+        }))
+    }
+}
+
+fn escape_pointer(key: &str) -> String {
+    key.replace('~', "~0").replace('/', "~1")
+}
+
+/// Infer the type of a schema without a `type` keyword, from its constraint keywords.
+fn infer_type(map: &Map<String, Value>) -> Option<&'static str> {
+    let has = |keywords: &[&str]| keywords.iter().any(|keyword| map.contains_key(*keyword));
+
+    if has(&[
+        "additionalProperties",
+        "maxProperties",
+        "minProperties",
+        "properties",
+        "propertyNames",
+        "required",
+    ]) {
+        Some("object")
+    } else if has(&[
+        "contains",
+        "items",
+        "maxItems",
+        "minItems",
+        "prefixItems",
+        "uniqueItems",
+    ]) {
+        Some("array")
+    } else if has(&["maxLength", "minLength", "pattern"]) {
+        Some("string")
+    } else if has(&[
+        "exclusiveMaximum",
+        "exclusiveMinimum",
+        "maximum",
+        "minimum",
+        "multipleOf",
+    ]) {
+        Some("number")
+    } else {
+        None
+    }
+}
+
+fn get_number(map: &Map<String, Value>, key: &str) -> Option<f64> {
+    map.get(key).and_then(|value| value.as_f64())
+}
+
+fn get_size(map: &Map<String, Value>, key: &str) -> Option<usize> {
+    map.get(key)
+        .and_then(|value| value.as_u64())
+        .map(|value| value as usize)
+}
+
+fn get_string(map: &Map<String, Value>, key: &str) -> Option<String> {
+    map.get(key)
+        .and_then(|value| value.as_str())
+        .map(|value| value.to_owned())
+}
+
+/// Return an exclusive bound, either as a number (draft-06 and later),
+/// or as a boolean that modifies the inclusive bound (draft-04).
+fn get_exclusive(map: &Map<String, Value>, key: &str, inclusive_key: &str) -> Option<f64> {
+    match map.get(key) {
+        Some(Value::Bool(true)) => get_number(map, inclusive_key),
+        Some(value) => value.as_f64(),
+        None => None,
+    }
+}
+
+fn to_literal(value: &Value) -> Option<LiteralValue> {
+    match value {
+        Value::Bool(inner) => Some(LiteralValue::Bool(*inner)),
+        Value::Number(inner) => Some(number_to_literal(inner)),
+        Value::String(inner) => Some(LiteralValue::String(inner.to_owned())),
+        _ => None,
+    }
+}
+
+fn number_to_literal(number: &Number) -> LiteralValue {
+    if let Some(value) = number.as_u64() {
+        LiteralValue::UInt(value as usize)
+    } else if let Some(value) = number.as_i64() {
+        LiteralValue::Int(value as isize)
+    } else {
+        LiteralValue::F64(number.as_f64().unwrap_or_default())
+    }
+}
+
+/// Set the default value on the type, or the first union variant that accepts it.
+/// Returns false if the value is not applicable to the type.
+fn set_default(ty: &mut SchemaType, value: LiteralValue) -> bool {
+    match (ty, value) {
+        (SchemaType::Boolean(inner), value @ LiteralValue::Bool(_)) => {
+            inner.default = Some(value);
+        }
+        (SchemaType::Float(inner), LiteralValue::Int(value)) => {
+            inner.default = Some(LiteralValue::F64(value as f64));
+        }
+        (SchemaType::Float(inner), LiteralValue::UInt(value)) => {
+            inner.default = Some(LiteralValue::F64(value as f64));
+        }
+        (SchemaType::Float(inner), value @ LiteralValue::F64(_)) => {
+            inner.default = Some(value);
+        }
+        (SchemaType::Integer(inner), value @ (LiteralValue::Int(_) | LiteralValue::UInt(_))) => {
+            inner.default = Some(value);
+        }
+        (SchemaType::String(inner), value @ LiteralValue::String(_)) => {
+            inner.default = Some(value);
+        }
+        (SchemaType::Enum(inner), value) => {
+            let Some(index) = inner.values.iter().position(|item| *item == value) else {
+                return false;
+            };
+
+            inner.default_index = Some(index);
+        }
+        (SchemaType::Literal(inner), value) => {
+            return inner.value == value;
+        }
+        (SchemaType::Union(inner), value) => {
+            for (index, variant) in inner.variants_types.iter_mut().enumerate() {
+                if set_default(&mut variant.ty, value.clone()) {
+                    inner.default_index = Some(index);
+
+                    return true;
+                }
+            }
+
+            return false;
+        }
+        _ => {
+            return false;
+        }
+    };
+
+    true
+}
@@ -0,0 +1,2 @@
+#[cfg(feature = "importer_json_schema")]
+pub mod json_schema;
@@ -1,11 +1,28 @@
 mod generator;
+mod importers;
 mod renderer;
 mod renderers;
+#[cfg(feature = "validate_schema")]
+mod validator;
 
 pub use generator::*;
 pub use indexmap;
 pub use renderer::*;
 pub use schematic_types::*;
+#[cfg(feature = "validate_schema")]
+pub use validator::*;
+
+/// Imports JSON schemas.
+#[cfg(feature = "importer_json_schema")]
+pub use importers::json_schema::*;
+
+/// Renders environment variable documentation.
+#[cfg(feature = "renderer_env_vars")]
+pub use renderers::env_vars::{self, *};
+
+/// Renders Go types.
+#[cfg(feature = "renderer_go")]
+pub use renderers::go::{self, *};
 
 /// Renders JSON schemas.
 #[cfg(feature = "renderer_json_schema")]
@@ -19,10 +36,26 @@ pub use renderers::json_template::*;
 #[cfg(all(feature = "renderer_template", feature = "json"))]
 pub use renderers::jsonc_template::*;
 
+/// Renders Markdown documentation.
+#[cfg(feature = "renderer_markdown")]
+pub use renderers::markdown::{self, *};
+
+/// Renders Pkl classes and modules.
+#[cfg(feature = "renderer_pkl")]
+pub use renderers::pkl::{self, *};
+
 /// Renders Pkl config templates.
 #[cfg(all(feature = "renderer_template", feature = "pkl"))]
 pub use renderers::pkl_template::*;
 
+/// Renders Python types.
+#[cfg(feature = "renderer_python")]
+pub use renderers::python::{self, *};
+
+/// Renders Rust source code.
+#[cfg(feature = "renderer_rust")]
+pub use renderers::rust::{self, *};
+
 /// Helpers for config templates.
 #[cfg(feature = "renderer_template")]
 pub use renderers::template::TemplateOptions;
@@ -38,3 +71,7 @@ pub use renderers::typescript::{self, *};
 /// Renders YAML config templates.
 #[cfg(all(feature = "renderer_template", feature = "yaml"))]
 pub use renderers::yaml_template::*;
+
+/// Renders Zod schemas.
+#[cfg(feature = "renderer_zod")]
+pub use renderers::zod::{self, *};
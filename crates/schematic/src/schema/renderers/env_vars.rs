@@ -0,0 +1,484 @@
+use crate::helpers::to_env_name;
+use crate::schema::{RenderResult, SchemaRenderer};
+use indexmap::IndexMap;
+use miette::miette;
+use schematic_types::*;
+
+/// Format of the rendered environment variables.
+#[derive(Default)]
+pub enum EnvVarsFormat {
+    /// Sample `.env` file, with each variable set to its default value.
+    Dotenv,
+    /// Markdown table, with a row for each variable.
+    #[default]
+    Markdown,
+}
+
+/// Options to control the rendered environment variables.
+#[derive(Default)]
+pub struct EnvVarsOptions {
+    /// Format to render the variables as.
+    pub format: EnvVarsFormat,
+
+    /// Content to append to the bottom of the output.
+    pub footer: String,
+
+    /// Content to prepend to the top of the output.
+    pub header: String,
+
+    /// Include fields marked as hidden.
+    pub include_hidden: bool,
+}
+
+/// A setting that can be configured with an environment variable.
+#[derive(Debug, Default)]
+pub struct EnvVar {
+    pub default: Option<String>,
+    pub deprecated: Option<String>,
+    pub description: Option<String>,
+    pub name: String,
+    pub path: String,
+    pub type_of: String,
+}
+
+/// Variable names for settings nested within a `#[config(env_nested_delimiter)]`.
+struct NestedScope {
+    delimiter: String,
+    prefix: String,
+}
+
+impl NestedScope {
+    fn child(&self, name: &str) -> Self {
+        Self {
+            delimiter: self.delimiter.clone(),
+            prefix: format!("{name}{}", self.delimiter),
+        }
+    }
+}
+
+/// Renders documentation for all settings that are configurable through environment
+/// variables (`#[setting(env)]` or `#[config(env_prefix)]`), either as a Markdown table,
+/// or as a sample `.env` file.
+#[derive(Default)]
+pub struct EnvVarsRenderer {
+    options: EnvVarsOptions,
+    schemas: IndexMap<String, Schema>,
+    stack: Vec<String>,
+    vars: Vec<EnvVar>,
+}
+
+impl EnvVarsRenderer {
+    pub fn new(options: EnvVarsOptions) -> Self {
+        Self {
+            options,
+            ..Default::default()
+        }
+    }
+
+    fn resolve_struct(&self, schema: &Schema) -> Option<StructType> {
+        match &schema.ty {
+            SchemaType::Struct(structure) => Some(structure.as_ref().to_owned()),
+            SchemaType::Reference(name) => self
+                .schemas
+                .get(name)
+                .and_then(|schema| self.resolve_struct(schema)),
+            // Optional nested structs
+            SchemaType::Union(uni) if uni.has_null() && uni.variants_types.len() == 2 => uni
+                .variants_types
+                .iter()
+                .find(|variant| !variant.is_null())
+                .and_then(|variant| self.resolve_struct(variant)),
+            _ => None,
+        }
+    }
+
+    fn resolve_collection(&self, schema: &Schema) -> Option<(&'static str, Schema)> {
+        match &schema.ty {
+            SchemaType::Array(array) => Some(("{index}", array.items_type.as_ref().to_owned())),
+            SchemaType::Object(object) => Some(("{key}", object.value_type.as_ref().to_owned())),
+            SchemaType::Reference(name) => self
+                .schemas
+                .get(name)
+                .and_then(|schema| self.resolve_collection(schema)),
+            // Optional collections
+            SchemaType::Union(uni) if uni.has_null() && uni.variants_types.len() == 2 => uni
+                .variants_types
+                .iter()
+                .find(|variant| !variant.is_null())
+                .and_then(|variant| self.resolve_collection(variant)),
+            _ => None,
+        }
+    }
+
+    fn collect_vars(
+        &mut self,
+        structure: &StructType,
+        scope: Option<NestedScope>,
+    ) -> RenderResult<()> {
+        // The outermost delimiter takes precedence when nested
+        let scope = match (
+            scope,
+            &structure.env_prefix,
+            &structure.env_nested_delimiter,
+        ) {
+            (None, Some(prefix), Some(delimiter)) => Some(NestedScope {
+                delimiter: delimiter.to_owned(),
+                prefix: prefix.to_owned(),
+            }),
+            (scope, _, _) => scope,
+        };
+
+        for (name, field) in &structure.fields {
+            if field.hidden && !self.options.include_hidden {
+                continue;
+            }
+
+            self.stack.push(name.to_owned());
+
+            let nested_name = scope
+                .as_ref()
+                .map(|scope| format!("{}{}", scope.prefix, to_env_name(name)));
+
+            if let Some(nested) = self.resolve_struct(&field.schema) {
+                let child = scope
+                    .as_ref()
+                    .zip(nested_name.as_ref())
+                    .map(|(scope, var)| scope.child(var));
+
+                self.collect_vars(&nested, child)?;
+            } else {
+                // Items of collections can be targeted individually
+                let collection =
+                    scope
+                        .as_ref()
+                        .zip(nested_name.as_ref())
+                        .and_then(|(scope, var)| {
+                            self.resolve_collection(&field.schema)
+                                .map(|(placeholder, item)| (scope, var, placeholder, item))
+                        });
+
+                // Maps and nested configs can't be represented by a single value
+                let single = match &collection {
+                    Some((_, _, placeholder, item)) => {
+                        *placeholder != "{key}" && self.resolve_struct(item).is_none()
+                    }
+                    None => true,
+                };
+
+                if single
+                    && let Some(env_var) = field
+                        .env_var
+                        .as_ref()
+                        .filter(|env_var| !env_var.is_empty())
+                        .or(nested_name.as_ref())
+                {
+                    let var = self.create_var(field, &field.schema, env_var)?;
+
+                    self.vars.push(var);
+                }
+
+                if let Some((scope, var, placeholder, item)) = collection {
+                    let env_var = format!("{var}{}{placeholder}", scope.delimiter);
+
+                    self.stack.push(placeholder.to_owned());
+
+                    if let Some(nested) = self.resolve_struct(&item) {
+                        self.collect_vars(&nested, Some(scope.child(&env_var)))?;
+                    } else {
+                        let var = self.create_var(field, &item, &env_var)?;
+
+                        self.vars.push(var);
+                    }
+
+                    self.stack.pop();
+                }
+            }
+
+            self.stack.pop();
+        }
+
+        Ok(())
+    }
+
+    fn create_var(
+        &mut self,
+        field: &SchemaField,
+        schema: &Schema,
+        env_var: &str,
+    ) -> RenderResult<EnvVar> {
+        Ok(EnvVar {
+            default: if field.secret {
+                None
+            } else {
+                get_default(schema)
+            },
+            deprecated: field.deprecated.clone(),
+            description: field
+                .comment
+                .as_ref()
+                .or(field.schema.description.as_ref())
+                .map(|comment| comment.trim().to_owned()),
+            name: env_var.to_owned(),
+            path: self.stack.join("."),
+            type_of: self.render_schema(schema)?,
+        })
+    }
+
+    fn render_dotenv(&self) -> String {
+        let mut out = vec![];
+
+        for var in &self.vars {
+            let mut lines = vec![];
+
+            if let Some(description) = &var.description {
+                for line in description.lines() {
+                    lines.push(format!("# {}", line.trim()).trim_end().to_owned());
+                }
+            }
+
+            lines.push(format!("# @setting {}", var.path));
+            lines.push(format!("# @type {}", var.type_of));
+
+            if let Some(deprecated) = &var.deprecated {
+                lines.push(if deprecated.is_empty() {
+                    "# @deprecated".into()
+                } else {
+                    format!("# @deprecated {deprecated}")
+                });
+            }
+
+            // Placeholders for list indexes and map keys must be replaced
+            let comment = if var.name.contains('{') { "# " } else { "" };
+
+            lines.push(format!(
+                "{comment}{}={}",
+                var.name,
+                var.default.as_deref().unwrap_or_default()
+            ));
+
+            out.push(lines.join("\n"));
+        }
+
+        out.join("\n\n")
+    }
+
+    fn render_markdown(&self) -> String {
+        let mut out = vec![
+            "| Variable | Setting | Type | Default | Description | Deprecated |".to_owned(),
+            "| --- | --- | --- | --- | --- | --- |".to_owned(),
+        ];
+
+        for var in &self.vars {
+            out.push(format!(
+                "| {} | {} | {} | {} | {} | {} |",
+                code(&var.name),
+                code(&var.path),
+                escape_cell(&code(&var.type_of)),
+                var.default
+                    .as_deref()
+                    .map(|value| escape_cell(&code(value)))
+                    .unwrap_or_default(),
+                var.description
+                    .as_deref()
+                    .map(escape_cell)
+                    .unwrap_or_default(),
+                match &var.deprecated {
+                    Some(deprecated) if deprecated.is_empty() => "Yes".into(),
+                    Some(deprecated) => escape_cell(deprecated),
+                    None => String::new(),
+                },
+            ));
+        }
+
+        out.join("\n")
+    }
+}
+
+/// Wrap the value in a code span, with a fence that's longer
+/// than any sequence of backticks within the value.
+fn code(value: &str) -> String {
+    let mut longest = 0;
+    let mut current = 0;
+
+    for ch in value.chars() {
+        if ch == '`' {
+            current += 1;
+            longest = longest.max(current);
+        } else {
+            current = 0;
+        }
+    }
+
+    let fence = "`".repeat(longest + 1);
+
+    if value.starts_with('`') || value.ends_with('`') {
+        format!("{fence} {value} {fence}")
+    } else {
+        format!("{fence}{value}{fence}")
+    }
+}
+
+fn escape_cell(value: &str) -> String {
+    value
+        .lines()
+        .map(|line| line.trim())
+        .collect::<Vec<_>>()
+        .join(" ")
+        .replace('|', "\\|")
+}
+
+fn lit_to_string(lit: &LiteralValue) -> String {
+    match lit {
+        LiteralValue::Bool(inner) => inner.to_string(),
+        LiteralValue::F32(inner) => inner.to_string(),
+        LiteralValue::F64(inner) => inner.to_string(),
+        LiteralValue::Int(inner) => inner.to_string(),
+        LiteralValue::UInt(inner) => inner.to_string(),
+        LiteralValue::String(inner) => inner.to_owned(),
+    }
+}
+
+fn get_default(schema: &Schema) -> Option<String> {
+    match &schema.ty {
+        SchemaType::Boolean(inner) => inner.default.as_ref().map(lit_to_string),
+        SchemaType::Enum(inner) => inner
+            .default_index
+            .and_then(|index| inner.values.get(index))
+            .map(lit_to_string),
+        SchemaType::Float(inner) => inner.default.as_ref().map(lit_to_string),
+        SchemaType::Integer(inner) => inner.default.as_ref().map(lit_to_string),
+        SchemaType::String(inner) => inner.default.as_ref().map(lit_to_string),
+        SchemaType::Union(inner) => match inner.default_index {
+            Some(index) => inner.variants_types.get(index).and_then(|v| get_default(v)),
+            None => inner.variants_types.iter().find_map(|v| get_default(v)),
+        },
+        _ => None,
+    }
+}
+
+impl SchemaRenderer<String> for EnvVarsRenderer {
+    fn is_reference(&self, name: &str) -> bool {
+        self.schemas.contains_key(name)
+    }
+
+    fn render_array(&mut self, array: &ArrayType, _schema: &Schema) -> RenderResult {
+        Ok(format!("{}[]", self.render_schema(&array.items_type)?))
+    }
+
+    fn render_boolean(&mut self, _boolean: &BooleanType, _schema: &Schema) -> RenderResult {
+        Ok("boolean".into())
+    }
+
+    fn render_enum(&mut self, enu: &EnumType, _schema: &Schema) -> RenderResult {
+        Ok(enu
+            .values
+            .iter()
+            .map(|value| match value {
+                LiteralValue::String(inner) => format!("\"{inner}\""),
+                other => lit_to_string(other),
+            })
+            .collect::<Vec<_>>()
+            .join(" | "))
+    }
+
+    fn render_float(&mut self, _float: &FloatType, _schema: &Schema) -> RenderResult {
+        Ok("float".into())
+    }
+
+    fn render_integer(&mut self, _integer: &IntegerType, _schema: &Schema) -> RenderResult {
+        Ok("integer".into())
+    }
+
+    fn render_literal(&mut self, literal: &LiteralType, _schema: &Schema) -> RenderResult {
+        Ok(match &literal.value {
+            LiteralValue::String(inner) => format!("\"{inner}\""),
+            other => lit_to_string(other),
+        })
+    }
+
+    fn render_null(&mut self, _schema: &Schema) -> RenderResult {
+        Ok("null".into())
+    }
+
+    fn render_object(&mut self, object: &ObjectType, _schema: &Schema) -> RenderResult {
+        Ok(format!(
+            "map<{}, {}>",
+            self.render_schema(&object.key_type)?,
+            self.render_schema(&object.value_type)?
+        ))
+    }
+
+    fn render_reference(&mut self, reference: &str, _schema: &Schema) -> RenderResult {
+        if let Some(schema) = self.schemas.get(reference) {
+            return self.render_schema_without_reference(&schema.to_owned());
+        }
+
+        Ok(reference.into())
+    }
+
+    fn render_string(&mut self, _string: &StringType, _schema: &Schema) -> RenderResult {
+        Ok("string".into())
+    }
+
+    fn render_struct(&mut self, _structure: &StructType, schema: &Schema) -> RenderResult {
+        Ok(schema.name.clone().unwrap_or_else(|| "object".into()))
+    }
+
+    fn render_tuple(&mut self, tuple: &TupleType, _schema: &Schema) -> RenderResult {
+        let mut items = vec![];
+
+        for item in &tuple.items_types {
+            items.push(self.render_schema(item)?);
+        }
+
+        Ok(format!("[{}]", items.join(", ")))
+    }
+
+    fn render_union(&mut self, uni: &UnionType, _schema: &Schema) -> RenderResult {
+        let mut items = vec![];
+
+        // Variables are optional by nature, so omit null
+        for item in uni.variants_types.iter().filter(|item| !item.is_null()) {
+            items.push(self.render_schema(item)?);
+        }
+
+        Ok(items.join(" | "))
+    }
+
+    fn render_unknown(&mut self, _schema: &Schema) -> RenderResult {
+        Ok("unknown".into())
+    }
+
+    fn render(&mut self, schemas: IndexMap<String, Schema>) -> RenderResult {
+        self.schemas = schemas;
+
+        let Some(root) = self.schemas.values().last() else {
+            return Err(miette!(
+                "At least 1 schema is required to generate environment variables."
+            ));
+        };
+
+        let Some(root) = self.resolve_struct(root) else {
+            return Err(miette!("The last registered schema must be a struct type."));
+        };
+
+        self.collect_vars(&root, None)?;
+
+        let mut output = String::new();
+
+        if !self.options.header.is_empty() {
+            output.push_str(&self.options.header);
+        }
+
+        output.push_str(&match self.options.format {
+            EnvVarsFormat::Dotenv => self.render_dotenv(),
+            EnvVarsFormat::Markdown => self.render_markdown(),
+        });
+
+        if !self.options.footer.is_empty() {
+            output.push_str(&self.options.footer);
+        }
+
+        Ok(output)
+    }
+}
@@ -0,0 +1,687 @@
+use crate::schema::{RenderResult, SchemaRenderer};
+use convert_case::{Boundary, Case, Casing};
+use indexmap::IndexMap;
+use schematic_types::*;
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+const DIGIT_BOUNDARIES: [Boundary; 4] = [
+    Boundary::UpperDigit,
+    Boundary::LowerDigit,
+    Boundary::DigitUpper,
+    Boundary::DigitLower,
+];
+
+// Words that golint expects to be fully capitalized
+const INITIALISMS: [&str; 30] = [
+    "ACL", "API", "ASCII", "CPU", "CSS", "DNS", "EOF", "GUID", "HTML", "HTTP", "HTTPS", "ID", "IP",
+    "JSON", "RAM", "SQL", "SSH", "TCP", "TLS", "TOML", "TTL", "UDP", "UI", "UID", "URI", "URL",
+    "UUID", "VM", "XML", "YAML",
+];
+
+/// Options to control the rendered Go output.
+pub struct GoOptions {
+    /// List of references to exclude from rendering as a type.
+    pub exclude_references: Vec<String>,
+
+    /// Map of package import paths to a list of types to import.
+    /// Will be rendered as an `import "path"` declaration, and types
+    /// will be qualified with the last segment of the path.
+    pub external_types: HashMap<String, Vec<String>>,
+
+    /// Character(s) to use for indentation.
+    pub indent_char: String,
+
+    /// Name of the package declared at the top of the file.
+    pub package_name: String,
+
+    /// List of struct tags to render for each field, using the
+    /// serialized field name.
+    pub tags: Vec<String>,
+}
+
+impl Default for GoOptions {
+    fn default() -> Self {
+        Self {
+            exclude_references: vec![],
+            external_types: HashMap::new(),
+            indent_char: "\t".into(),
+            package_name: "config".into(),
+            tags: vec!["json".into(), "yaml".into(), "toml".into()],
+        }
+    }
+}
+
+enum Line {
+    Comment(String),
+    Cells(Vec<String>),
+}
+
+/// Renders Go structs and types from a schema.
+#[derive(Default)]
+pub struct GoRenderer {
+    context: Vec<String>,
+    hoisted: Vec<String>,
+    names: HashSet<String>,
+    options: GoOptions,
+    references: HashSet<String>,
+}
+
+fn sanitize(value: &str) -> String {
+    let value = value
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect::<String>();
+
+    value.trim_matches('_').to_owned()
+}
+
+/// Convert a value to an exported Go identifier, capitalizing initialisms.
+fn to_go_name(value: &str) -> String {
+    sanitize(value)
+        .remove_boundaries(&DIGIT_BOUNDARIES)
+        .to_case(Case::Snake)
+        .split('_')
+        .map(|word| {
+            let upper = word.to_uppercase();
+
+            if INITIALISMS.contains(&upper.as_str()) {
+                return upper;
+            }
+
+            let mut chars = word.chars();
+
+            match chars.next() {
+                Some(first) => first.to_uppercase().chain(chars).collect(),
+                None => String::new(),
+            }
+        })
+        .collect()
+}
+
+fn is_string_union(uni: &UnionType) -> bool {
+    let mut count = 0;
+
+    for item in &uni.variants_types {
+        match &item.ty {
+            SchemaType::Null => {}
+            SchemaType::Literal(lit) if matches!(lit.value, LiteralValue::String(_)) => {
+                count += 1;
+            }
+            _ => return false,
+        };
+    }
+
+    count > 1
+}
+
+/// Return true if the type can already be nil, and doesn't require a pointer.
+fn is_nilable(ty: &str) -> bool {
+    ty == "any" || ty.starts_with('*') || ty.starts_with("[]") || ty.starts_with("map[")
+}
+
+fn lit_to_string(lit: &LiteralValue) -> String {
+    match lit {
+        LiteralValue::Bool(inner) => inner.to_string(),
+        LiteralValue::F32(inner) => format!("{inner:?}"),
+        LiteralValue::F64(inner) => format!("{inner:?}"),
+        LiteralValue::Int(inner) => inner.to_string(),
+        LiteralValue::UInt(inner) => inner.to_string(),
+        LiteralValue::String(inner) => format!("{inner:?}"),
+    }
+}
+
+fn lit_to_type(lit: &LiteralValue) -> &'static str {
+    match lit {
+        LiteralValue::Bool(_) => "bool",
+        LiteralValue::F32(_) => "float32",
+        LiteralValue::F64(_) => "float64",
+        LiteralValue::Int(_) => "int64",
+        LiteralValue::UInt(_) => "uint64",
+        LiteralValue::String(_) => "string",
+    }
+}
+
+impl GoRenderer {
+    pub fn new(options: GoOptions) -> Self {
+        Self {
+            options,
+            ..Default::default()
+        }
+    }
+
+    fn indent(&self) -> String {
+        if self.options.indent_char.is_empty() {
+            "\t".into()
+        } else {
+            self.options.indent_char.clone()
+        }
+    }
+
+    fn is_excluded(&self, name: &str) -> bool {
+        self.options.exclude_references.iter().any(|r| r == name)
+    }
+
+    fn get_external_package(&self, name: &str) -> Option<&str> {
+        self.options
+            .external_types
+            .iter()
+            .find(|(_, externals)| externals.iter().any(|e| e == name))
+            .map(|(path, _)| path.rsplit('/').next().unwrap_or(path))
+    }
+
+    fn create_type_name(&mut self) -> String {
+        let base = match self.context.last() {
+            Some(name) => name.clone(),
+            None => "Type".into(),
+        };
+        let mut name = base.clone();
+        let mut index = 1;
+
+        while self.names.contains(&name) {
+            index += 1;
+            name = format!("{base}{index}");
+        }
+
+        self.names.insert(name.clone());
+
+        name
+    }
+
+    fn create_const_name(&mut self, type_name: &str, value: &LiteralValue) -> String {
+        let suffix = match value {
+            LiteralValue::String(inner) => to_go_name(inner),
+            other => to_go_name(&other.to_string()),
+        };
+        let base = format!(
+            "{type_name}{}",
+            if suffix.is_empty() { "Empty" } else { &suffix }
+        );
+        let mut name = base.clone();
+        let mut index = 1;
+
+        while self.names.contains(&name) {
+            index += 1;
+            name = format!("{base}{index}");
+        }
+
+        self.names.insert(name.clone());
+
+        name
+    }
+
+    fn render_comment(
+        &self,
+        indent: &str,
+        comment: Option<&String>,
+        deprecated: Option<&String>,
+    ) -> Vec<String> {
+        let mut lines = vec![];
+
+        if let Some(comment) = comment {
+            for line in comment.trim().split('\n') {
+                let line = line.trim();
+
+                lines.push(if line.is_empty() {
+                    format!("{indent}//")
+                } else {
+                    format!("{indent}// {line}")
+                });
+            }
+        }
+
+        if let Some(deprecated) = deprecated {
+            if !lines.is_empty() {
+                lines.push(format!("{indent}//"));
+            }
+
+            // Tooling requires the "Deprecated: " prefix to be followed by text
+            lines.push(format!(
+                "{indent}// Deprecated: {}",
+                if deprecated.is_empty() {
+                    "Do not use."
+                } else {
+                    deprecated
+                }
+            ));
+        }
+
+        lines
+    }
+
+    /// Align consecutive rows of cells into columns, like `gofmt` does.
+    /// Comments end the current block of aligned rows.
+    fn render_aligned(&self, lines: Vec<Line>) -> Vec<String> {
+        let indent = self.indent();
+        let mut out = vec![];
+        let mut block: Vec<Vec<String>> = vec![];
+
+        let flush = |block: &mut Vec<Vec<String>>, out: &mut Vec<String>| {
+            let mut widths = vec![];
+
+            for cells in block.iter() {
+                for (index, cell) in cells.iter().enumerate().take(cells.len() - 1) {
+                    if widths.len() <= index {
+                        widths.push(0);
+                    }
+
+                    widths[index] = widths[index].max(cell.len());
+                }
+            }
+
+            for cells in block.drain(..) {
+                let mut row = indent.clone();
+
+                for (index, cell) in cells.iter().enumerate() {
+                    if index == cells.len() - 1 {
+                        row.push_str(cell);
+                    } else {
+                        row.push_str(&format!("{cell:<width$} ", width = widths[index]));
+                    }
+                }
+
+                out.push(row);
+            }
+        };
+
+        for line in lines {
+            match line {
+                Line::Comment(comment) => {
+                    flush(&mut block, &mut out);
+                    out.push(comment);
+                }
+                Line::Cells(cells) => {
+                    block.push(cells);
+                }
+            };
+        }
+
+        flush(&mut block, &mut out);
+
+        out
+    }
+
+    fn render_with_context(&mut self, suffix: &str, schema: &Schema) -> RenderResult {
+        let name = format!(
+            "{}{}",
+            self.context
+                .last()
+                .map(|name| name.as_str())
+                .unwrap_or_default(),
+            suffix
+        );
+
+        self.context.push(name);
+        let out = self.render_schema(schema);
+        self.context.pop();
+
+        out
+    }
+
+    fn render_tags(&self, field_name: &str, field: &SchemaField) -> String {
+        let tags = self
+            .options
+            .tags
+            .iter()
+            .map(|tag| {
+                let value = if field.flatten {
+                    // Only YAML supports inlining a map or struct
+                    if tag == "yaml" { ",inline" } else { "-" }.to_owned()
+                } else if field.optional || field.nullable || field.schema.is_nullable() {
+                    format!("{field_name},omitempty")
+                } else {
+                    field_name.to_owned()
+                };
+
+                format!("{tag}:{value:?}")
+            })
+            .collect::<Vec<_>>();
+
+        format!("`{}`", tags.join(" "))
+    }
+
+    fn export_type(&mut self, name: &str, schema: &Schema) -> RenderResult {
+        self.context.push(name.to_owned());
+        let value = self.render_schema_without_reference(schema);
+        self.context.pop();
+
+        let mut out =
+            self.render_comment("", schema.description.as_ref(), schema.deprecated.as_ref());
+
+        out.push(format!("type {name} {}", value?));
+
+        Ok(out.join("\n"))
+    }
+
+    fn export_enum(
+        &mut self,
+        name: &str,
+        values: &[(LiteralValue, Option<&SchemaField>)],
+        schema: &Schema,
+    ) -> RenderResult {
+        let Some((first, _)) = values.first() else {
+            return self.export_type(name, schema);
+        };
+        let base = lit_to_type(first);
+
+        // Constants must share the same underlying type
+        if values.iter().any(|(value, _)| lit_to_type(value) != base) {
+            let mut out =
+                self.render_comment("", schema.description.as_ref(), schema.deprecated.as_ref());
+
+            out.push(format!("type {name} any"));
+
+            return Ok(out.join("\n"));
+        }
+
+        let indent = self.indent();
+        let mut lines = vec![];
+
+        for (value, field) in values {
+            if let Some(field) = field {
+                for comment in
+                    self.render_comment(&indent, field.comment.as_ref(), field.deprecated.as_ref())
+                {
+                    lines.push(Line::Comment(comment));
+                }
+            }
+
+            lines.push(Line::Cells(vec![
+                self.create_const_name(name, value),
+                name.to_owned(),
+                format!("= {}", lit_to_string(value)),
+            ]));
+        }
+
+        let mut out =
+            self.render_comment("", schema.description.as_ref(), schema.deprecated.as_ref());
+
+        out.push(format!("type {name} {base}"));
+        out.push(String::new());
+        out.push("const (".into());
+        out.extend(self.render_aligned(lines));
+        out.push(")".into());
+
+        Ok(out.join("\n"))
+    }
+
+    fn export_struct(
+        &mut self,
+        name: &str,
+        structure: &StructType,
+        schema: &Schema,
+    ) -> RenderResult {
+        self.context.push(name.to_owned());
+
+        let indent = self.indent();
+        let mut field_names = HashSet::new();
+        let mut lines = vec![];
+
+        for (field_name, field) in &structure.fields {
+            if field.hidden {
+                continue;
+            }
+
+            let mut go_name = to_go_name(field_name);
+
+            if go_name.is_empty() || go_name.starts_with(|c: char| c.is_ascii_digit()) {
+                go_name = format!("Field{go_name}");
+            }
+
+            if field_names.contains(&go_name) {
+                let base = go_name.clone();
+                let mut index = 1;
+
+                while field_names.contains(&go_name) {
+                    index += 1;
+                    go_name = format!("{base}{index}");
+                }
+            }
+
+            field_names.insert(go_name.clone());
+
+            let mut value = self.render_with_context(&to_go_name(field_name), &field.schema)?;
+
+            if (field.optional || field.nullable) && !is_nilable(&value) {
+                value = format!("*{value}");
+            }
+
+            for comment in
+                self.render_comment(&indent, field.comment.as_ref(), field.deprecated.as_ref())
+            {
+                lines.push(Line::Comment(comment));
+            }
+
+            let mut cells = vec![go_name, value];
+
+            if !self.options.tags.is_empty() {
+                cells.push(self.render_tags(field_name, field));
+            }
+
+            lines.push(Line::Cells(cells));
+        }
+
+        self.context.pop();
+
+        let mut out =
+            self.render_comment("", schema.description.as_ref(), schema.deprecated.as_ref());
+
+        if lines.is_empty() {
+            out.push(format!("type {name} struct{{}}"));
+        } else {
+            out.push(format!("type {name} struct {{"));
+            out.extend(self.render_aligned(lines));
+            out.push("}".into());
+        }
+
+        Ok(out.join("\n"))
+    }
+
+    fn export_schema(&mut self, name: &str, schema: &Schema) -> RenderResult {
+        match &schema.ty {
+            SchemaType::Enum(enu) => {
+                let values = match &enu.variants {
+                    Some(variants) => variants
+                        .values()
+                        .filter_map(|field| match &field.schema.ty {
+                            SchemaType::Literal(lit) => {
+                                Some((lit.value.clone(), Some(field.as_ref())))
+                            }
+                            // Fallback variants accept any value
+                            _ => None,
+                        })
+                        .collect::<Vec<_>>(),
+                    None => enu
+                        .values
+                        .iter()
+                        .map(|value| (value.clone(), None))
+                        .collect(),
+                };
+
+                self.export_enum(name, &values, schema)
+            }
+            SchemaType::Struct(inner) => self.export_struct(name, inner, schema),
+            SchemaType::Union(uni) if is_string_union(uni) => {
+                let values = uni
+                    .variants_types
+                    .iter()
+                    .filter_map(|item| match &item.ty {
+                        SchemaType::Literal(lit) => Some((lit.value.clone(), None)),
+                        _ => None,
+                    })
+                    .collect::<Vec<_>>();
+
+                self.export_enum(name, &values, schema)
+            }
+            _ => self.export_type(name, schema),
+        }
+    }
+
+    fn hoist(&mut self, schema: &Schema) -> RenderResult {
+        let name = self.create_type_name();
+        let output = self.export_schema(&name, schema)?;
+
+        self.hoisted.push(output);
+
+        Ok(name)
+    }
+}
+
+impl SchemaRenderer<String> for GoRenderer {
+    fn is_reference(&self, name: &str) -> bool {
+        self.references.contains(name) || self.get_external_package(name).is_some()
+    }
+
+    fn render_array(&mut self, array: &ArrayType, _schema: &Schema) -> RenderResult {
+        Ok(format!(
+            "[]{}",
+            self.render_with_context("Item", &array.items_type)?
+        ))
+    }
+
+    fn render_boolean(&mut self, _boolean: &BooleanType, _schema: &Schema) -> RenderResult {
+        Ok("bool".into())
+    }
+
+    fn render_enum(&mut self, _enu: &EnumType, schema: &Schema) -> RenderResult {
+        self.hoist(schema)
+    }
+
+    fn render_float(&mut self, float: &FloatType, _schema: &Schema) -> RenderResult {
+        Ok(match float.kind {
+            FloatKind::F32 => "float32",
+            FloatKind::F64 => "float64",
+        }
+        .into())
+    }
+
+    fn render_integer(&mut self, integer: &IntegerType, _schema: &Schema) -> RenderResult {
+        Ok(match integer.kind {
+            IntegerKind::Isize => "int",
+            IntegerKind::I8 => "int8",
+            IntegerKind::I16 => "int16",
+            IntegerKind::I32 => "int32",
+            IntegerKind::I64 | IntegerKind::I128 => "int64",
+            IntegerKind::Usize => "uint",
+            IntegerKind::U8 => "uint8",
+            IntegerKind::U16 => "uint16",
+            IntegerKind::U32 => "uint32",
+            IntegerKind::U64 | IntegerKind::U128 => "uint64",
+        }
+        .into())
+    }
+
+    fn render_literal(&mut self, literal: &LiteralType, _schema: &Schema) -> RenderResult {
+        Ok(lit_to_type(&literal.value).into())
+    }
+
+    fn render_null(&mut self, _schema: &Schema) -> RenderResult {
+        Ok("any".into())
+    }
+
+    fn render_object(&mut self, object: &ObjectType, _schema: &Schema) -> RenderResult {
+        Ok(format!(
+            "map[{}]{}",
+            self.render_with_context("Key", &object.key_type)?,
+            self.render_with_context("Value", &object.value_type)?
+        ))
+    }
+
+    fn render_reference(&mut self, reference: &str, _schema: &Schema) -> RenderResult {
+        Ok(match self.get_external_package(reference) {
+            Some(package) => format!("{package}.{reference}"),
+            None => reference.into(),
+        })
+    }
+
+    fn render_string(&mut self, _string: &StringType, _schema: &Schema) -> RenderResult {
+        Ok("string".into())
+    }
+
+    fn render_struct(&mut self, _structure: &StructType, schema: &Schema) -> RenderResult {
+        self.hoist(schema)
+    }
+
+    fn render_tuple(&mut self, _tuple: &TupleType, _schema: &Schema) -> RenderResult {
+        Ok("[]any".into())
+    }
+
+    fn render_union(&mut self, uni: &UnionType, schema: &Schema) -> RenderResult {
+        let variants_types = uni
+            .variants_types
+            .iter()
+            .filter(|item| !item.is_null())
+            .collect::<Vec<_>>();
+
+        let value = match variants_types.len() {
+            0 => return Ok("any".into()),
+            1 => self.render_schema(variants_types[0])?,
+            // Unions of strings can be represented as typed constants
+            _ if is_string_union(uni) => self.hoist(&Schema {
+                ty: SchemaType::Union(Box::new(UnionType {
+                    variants_types: variants_types.into_iter().cloned().collect(),
+                    ..uni.clone()
+                })),
+                ..schema.clone()
+            })?,
+            _ => return Ok("any".into()),
+        };
+
+        Ok(if uni.has_null() && !is_nilable(&value) {
+            format!("*{value}")
+        } else {
+            value
+        })
+    }
+
+    fn render_unknown(&mut self, _schema: &Schema) -> RenderResult {
+        Ok("any".into())
+    }
+
+    fn render(&mut self, schemas: IndexMap<String, Schema>) -> RenderResult {
+        self.references = HashSet::from_iter(schemas.keys().cloned());
+        self.names = self.references.clone();
+
+        let mut outputs = vec![];
+
+        for (name, schema) in &schemas {
+            if self.is_excluded(name) {
+                continue;
+            }
+
+            let output = self.export_schema(name, schema)?;
+
+            outputs.append(&mut self.hoisted);
+            outputs.push(output);
+        }
+
+        let mut header = vec![
+            "// Code generated by schematic. DO NOT EDIT.".to_owned(),
+            format!("package {}", self.options.package_name),
+        ];
+
+        let imports = BTreeMap::from_iter(&self.options.external_types)
+            .into_keys()
+            .collect::<Vec<_>>();
+
+        match imports.len() {
+            0 => {}
+            1 => header.push(format!("import {:?}", imports[0])),
+            _ => {
+                let indent = self.indent();
+
+                header.push(format!(
+                    "import (\n{}\n)",
+                    imports
+                        .iter()
+                        .map(|import| format!("{indent}{import:?}"))
+                        .collect::<Vec<_>>()
+                        .join("\n")
+                ));
+            }
+        };
+
+        header.extend(outputs);
+
+        Ok(header.join("\n\n"))
+    }
+}
@@ -183,7 +183,7 @@ impl JsonSchemaRenderer {
                     .map(|desc| clean_comment(desc, self.options.allow_newlines_in_description)),
                 deprecated: field.deprecated.is_some(),
                 read_only: field.read_only,
-                write_only: field.write_only,
+                write_only: field.write_only || field.secret,
                 ..Default::default()
             };
 
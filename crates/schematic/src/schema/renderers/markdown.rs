@@ -0,0 +1,628 @@
+use crate::schema::{RenderResult, SchemaRenderer};
+use convert_case::{Case, Casing};
+use indexmap::IndexMap;
+use miette::miette;
+use schematic_types::*;
+
+/// Markers that wrap the name of a type with its own section, within a
+/// rendered type, so that it can be linked to.
+const LINK_START: char = '\u{2}';
+const LINK_END: char = '\u{3}';
+
+/// Options to control the rendered Markdown documentation.
+#[derive(Default)]
+pub struct MarkdownOptions {
+    /// Render the settings of referenced structs directly, instead of within
+    /// collapsible `<details>` sections.
+    pub expand_nested: bool,
+
+    /// Content to append to the bottom of the output.
+    pub footer: String,
+
+    /// Content to prepend to the top of the output.
+    pub header: String,
+
+    /// List of field names to not render. Supports dot notation.
+    pub hide_fields: Vec<String>,
+
+    /// List of field names to only render. Supports dot notation.
+    pub only_fields: Vec<String>,
+}
+
+#[derive(Clone)]
+struct Section {
+    /// Path of the setting that first referenced this type.
+    prefix: String,
+    schema: Schema,
+}
+
+/// Renders a Markdown reference for all settings within the root struct,
+/// with a section per referenced struct and enum that types link to.
+#[derive(Default)]
+pub struct MarkdownRenderer {
+    /// Name for inline types without a name, derived from the parent type and setting.
+    context: String,
+    options: MarkdownOptions,
+    /// Path of the setting currently being rendered.
+    prefix: String,
+    schemas: IndexMap<String, Schema>,
+    sections: IndexMap<String, Section>,
+}
+
+fn code(value: &str) -> String {
+    if value.contains('`') {
+        format!("`` {value} ``")
+    } else {
+        format!("`{value}`")
+    }
+}
+
+/// Render a type as code, with the names of types that have a section linked.
+fn format_type(value: &str) -> String {
+    let mut out = String::new();
+
+    for (index, part) in value.split(LINK_START).enumerate() {
+        let (name, rest) = match part.split_once(LINK_END) {
+            Some((name, rest)) if index > 0 => (Some(name), rest),
+            _ => (None, part),
+        };
+
+        if let Some(name) = name {
+            out.push_str(&format!("[{}](#{})", code(name), slug(name)));
+        }
+
+        if !rest.is_empty() {
+            out.push_str(&code(rest));
+        }
+    }
+
+    out
+}
+
+fn slug(value: &str) -> String {
+    let mut slug = String::new();
+
+    for ch in value.chars() {
+        if ch.is_ascii_alphanumeric() {
+            slug.push(ch.to_ascii_lowercase());
+        } else if !slug.is_empty() && !slug.ends_with('-') {
+            slug.push('-');
+        }
+    }
+
+    slug.trim_end_matches('-').to_owned()
+}
+
+fn lit_to_string(lit: &LiteralValue) -> String {
+    match lit {
+        LiteralValue::Bool(inner) => inner.to_string(),
+        LiteralValue::F32(inner) => inner.to_string(),
+        LiteralValue::F64(inner) => inner.to_string(),
+        LiteralValue::Int(inner) => inner.to_string(),
+        LiteralValue::UInt(inner) => inner.to_string(),
+        LiteralValue::String(inner) => format!("\"{inner}\""),
+    }
+}
+
+fn render_bounds(
+    label: &str,
+    min: Option<String>,
+    min_exclusive: Option<String>,
+    max: Option<String>,
+    max_exclusive: Option<String>,
+) -> Vec<String> {
+    let prefix = if label.is_empty() {
+        String::new()
+    } else {
+        format!("{label} ")
+    };
+    let mut bounds = vec![];
+
+    if let Some(min) = min {
+        bounds.push(format!("{prefix}>= {min}"));
+    }
+
+    if let Some(min) = min_exclusive {
+        bounds.push(format!("{prefix}> {min}"));
+    }
+
+    if let Some(max) = max {
+        bounds.push(format!("{prefix}<= {max}"));
+    }
+
+    if let Some(max) = max_exclusive {
+        bounds.push(format!("{prefix}< {max}"));
+    }
+
+    bounds
+}
+
+fn render_description(description: &str) -> String {
+    description
+        .trim()
+        .lines()
+        .map(|line| line.trim())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn render_enum_values(enu: &EnumType) -> Option<String> {
+    let values = match &enu.variants {
+        Some(variants) => variants
+            .values()
+            .filter(|variant| !variant.hidden)
+            .map(|variant| match &variant.schema.ty {
+                SchemaType::Literal(lit) => code(&lit_to_string(&lit.value)),
+                // Fallback variant
+                _ => "any string".into(),
+            })
+            .collect::<Vec<_>>(),
+        None => enu
+            .values
+            .iter()
+            .map(|value| code(&lit_to_string(value)))
+            .collect(),
+    };
+
+    (!values.is_empty()).then(|| values.join(", "))
+}
+
+impl MarkdownRenderer {
+    pub fn new(options: MarkdownOptions) -> Self {
+        Self {
+            options,
+            ..Default::default()
+        }
+    }
+
+    fn is_hidden(&self, key: &str, field: &SchemaField) -> bool {
+        if field.hidden || self.options.hide_fields.iter().any(|hide| hide == key) {
+            return true;
+        }
+
+        // Parents and children of an only field must also be rendered
+        !self.options.only_fields.is_empty()
+            && !self.options.only_fields.iter().any(|only| {
+                only == key
+                    || only.starts_with(&format!("{key}."))
+                    || key.starts_with(&format!("{only}."))
+            })
+    }
+
+    fn resolve(&self, schema: &Schema) -> Schema {
+        let schema = schema.get_nonnull_schema().unwrap_or(schema);
+
+        if let SchemaType::Reference(name) = &schema.ty
+            && let Some(reference) = self.schemas.get(name)
+        {
+            return reference.to_owned();
+        }
+
+        schema.to_owned()
+    }
+
+    fn add_section(&mut self, name: &str, schema: &Schema) -> String {
+        if !self.sections.contains_key(name) {
+            self.sections.insert(
+                name.to_owned(),
+                Section {
+                    prefix: self.prefix.clone(),
+                    schema: schema.to_owned(),
+                },
+            );
+        }
+
+        format!("{LINK_START}{name}{LINK_END}")
+    }
+
+    fn create_inline_name(&self, schema: &Schema) -> String {
+        let base = &self.context;
+        let mut name = base.clone();
+        let mut count = 1;
+
+        while self.schemas.contains_key(&name)
+            || self
+                .sections
+                .get(&name)
+                .is_some_and(|section| section.schema != *schema)
+        {
+            count += 1;
+            name = format!("{base}{count}");
+        }
+
+        name
+    }
+
+    fn render_constraints(&self, schema: &Schema) -> Vec<String> {
+        let schema = self.resolve(schema);
+        let mut constraints = vec![];
+
+        match &schema.ty {
+            SchemaType::Array(inner) => {
+                constraints.extend(render_bounds(
+                    "length",
+                    inner.min_length.map(|v| v.to_string()),
+                    None,
+                    inner.max_length.map(|v| v.to_string()),
+                    None,
+                ));
+
+                if inner.unique == Some(true) {
+                    constraints.push("unique items".into());
+                }
+            }
+            SchemaType::Float(inner) => {
+                constraints.extend(render_bounds(
+                    "",
+                    inner.min.map(|v| v.to_string()),
+                    inner.min_exclusive.map(|v| v.to_string()),
+                    inner.max.map(|v| v.to_string()),
+                    inner.max_exclusive.map(|v| v.to_string()),
+                ));
+
+                if let Some(multiple) = inner.multiple_of {
+                    constraints.push(format!("multiple of {multiple}"));
+                }
+            }
+            SchemaType::Integer(inner) => {
+                constraints.extend(render_bounds(
+                    "",
+                    inner.min.map(|v| v.to_string()),
+                    inner.min_exclusive.map(|v| v.to_string()),
+                    inner.max.map(|v| v.to_string()),
+                    inner.max_exclusive.map(|v| v.to_string()),
+                ));
+
+                if let Some(multiple) = inner.multiple_of {
+                    constraints.push(format!("multiple of {multiple}"));
+                }
+            }
+            SchemaType::Object(inner) => {
+                constraints.extend(render_bounds(
+                    "length",
+                    inner.min_length.map(|v| v.to_string()),
+                    None,
+                    inner.max_length.map(|v| v.to_string()),
+                    None,
+                ));
+            }
+            SchemaType::String(inner) => {
+                constraints.extend(render_bounds(
+                    "length",
+                    inner.min_length.map(|v| v.to_string()),
+                    None,
+                    inner.max_length.map(|v| v.to_string()),
+                    None,
+                ));
+
+                if let Some(pattern) = &inner.pattern {
+                    constraints.push(format!("matches {}", code(pattern)));
+                }
+
+                if let Some(format) = &inner.format {
+                    constraints.push(format!("format {}", code(format)));
+                }
+            }
+            _ => {}
+        };
+
+        constraints
+    }
+
+    /// Render the allowed values of an inline enum. Named enums have their
+    /// own section instead.
+    fn render_enum_values(&self, schema: &Schema) -> Option<String> {
+        let schema = schema.get_nonnull_schema().unwrap_or(schema);
+
+        match &schema.ty {
+            SchemaType::Enum(enu) if schema.name.is_none() => render_enum_values(enu),
+            _ => None,
+        }
+    }
+
+    fn render_field(
+        &mut self,
+        section: &str,
+        key: &str,
+        name: &str,
+        field: &SchemaField,
+        depth: usize,
+    ) -> RenderResult<Vec<String>> {
+        let mut out = vec![
+            format!("<a id=\"{}\"></a>", slug(&format!("{section}-{name}"))),
+            format!("{} {}", "#".repeat(depth), code(name)),
+        ];
+
+        if let Some(comment) = field.comment.as_ref().or(field.schema.description.as_ref()) {
+            out.push(String::new());
+            out.push(render_description(comment));
+        }
+
+        self.prefix = key.to_owned();
+        self.context = format!("{section}{}", name.to_case(Case::Pascal));
+
+        let mut list = vec![format!(
+            "- **Type:** {}",
+            format_type(&self.render_schema(&field.schema)?)
+        )];
+
+        if !field.secret
+            && let Some(default) = field.schema.get_default()
+        {
+            list.push(format!("- **Default:** {}", code(&lit_to_string(default))));
+        }
+
+        if let Some(values) = self.render_enum_values(&field.schema) {
+            list.push(format!("- **Allowed values:** {values}"));
+        }
+
+        if !field.aliases.is_empty() {
+            list.push(format!(
+                "- **Aliases:** {}",
+                field
+                    .aliases
+                    .iter()
+                    .map(|alias| code(alias))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ));
+        }
+
+        if let Some(env_var) = &field.env_var
+            && !env_var.is_empty()
+        {
+            list.push(format!("- **Environment variable:** {}", code(env_var)));
+        }
+
+        let constraints = self.render_constraints(&field.schema);
+
+        if !constraints.is_empty() {
+            list.push(format!("- **Constraints:** {}", constraints.join(", ")));
+        }
+
+        if field.secret {
+            list.push("- **Secret:** Yes".into());
+        }
+
+        if let Some(deprecated) = &field.deprecated {
+            list.push(if deprecated.is_empty() {
+                "- **Deprecated:** Yes".into()
+            } else {
+                format!("- **Deprecated:** {deprecated}")
+            });
+        }
+
+        out.push(String::new());
+        out.push(list.join("\n"));
+
+        Ok(out)
+    }
+
+    fn render_fields(
+        &mut self,
+        section: &str,
+        prefix: &str,
+        structure: &StructType,
+        depth: usize,
+    ) -> RenderResult<Vec<String>> {
+        let mut out = vec![];
+
+        for (name, field) in &structure.fields {
+            let key = if prefix.is_empty() {
+                name.to_owned()
+            } else {
+                format!("{prefix}.{name}")
+            };
+
+            if self.is_hidden(&key, field) {
+                continue;
+            }
+
+            out.push(
+                self.render_field(section, &key, name, field, depth)?
+                    .join("\n"),
+            );
+        }
+
+        Ok(out)
+    }
+
+    fn render_section(&mut self, name: &str, section: &Section) -> RenderResult<String> {
+        let mut out = vec![format!("<a id=\"{}\"></a>\n## {name}", slug(name))];
+
+        if let Some(description) = &section.schema.description {
+            out.push(render_description(description));
+        }
+
+        match &section.schema.ty {
+            SchemaType::Enum(enu) => {
+                if let Some(values) = render_enum_values(enu) {
+                    out.push(format!("- **Allowed values:** {values}"));
+                }
+            }
+            SchemaType::Struct(structure) => {
+                let fields = self.render_fields(name, &section.prefix, structure, 3)?;
+
+                if !fields.is_empty() {
+                    out.push(if self.options.expand_nested {
+                        fields.join("\n\n")
+                    } else {
+                        format!(
+                            "<details>\n<summary>Settings of <code>{name}</code></summary>\n\n{}\n\n</details>",
+                            fields.join("\n\n")
+                        )
+                    });
+                }
+            }
+            _ => {}
+        };
+
+        Ok(out.join("\n\n"))
+    }
+}
+
+impl SchemaRenderer<String> for MarkdownRenderer {
+    fn is_reference(&self, name: &str) -> bool {
+        self.schemas.contains_key(name)
+    }
+
+    fn render_array(&mut self, array: &ArrayType, _schema: &Schema) -> RenderResult {
+        let prefix = self.prefix.clone();
+
+        self.prefix = format!("{prefix}[]");
+        let out = self.render_schema(&array.items_type)?;
+        self.prefix = prefix;
+
+        Ok(if out.contains('|') {
+            format!("({out})[]")
+        } else {
+            format!("{out}[]")
+        })
+    }
+
+    fn render_boolean(&mut self, _boolean: &BooleanType, _schema: &Schema) -> RenderResult {
+        Ok("boolean".into())
+    }
+
+    fn render_enum(&mut self, enu: &EnumType, schema: &Schema) -> RenderResult {
+        if let Some(name) = &schema.name {
+            return Ok(self.add_section(name, schema));
+        }
+
+        Ok(enu
+            .values
+            .iter()
+            .map(lit_to_string)
+            .collect::<Vec<_>>()
+            .join(" | "))
+    }
+
+    fn render_float(&mut self, _float: &FloatType, _schema: &Schema) -> RenderResult {
+        Ok("float".into())
+    }
+
+    fn render_integer(&mut self, _integer: &IntegerType, _schema: &Schema) -> RenderResult {
+        Ok("integer".into())
+    }
+
+    fn render_literal(&mut self, literal: &LiteralType, _schema: &Schema) -> RenderResult {
+        Ok(lit_to_string(&literal.value))
+    }
+
+    fn render_null(&mut self, _schema: &Schema) -> RenderResult {
+        Ok("null".into())
+    }
+
+    fn render_object(&mut self, object: &ObjectType, _schema: &Schema) -> RenderResult {
+        let prefix = self.prefix.clone();
+        let key = self.render_schema(&object.key_type)?;
+
+        self.prefix = format!("{prefix}.*");
+        let value = self.render_schema(&object.value_type)?;
+        self.prefix = prefix;
+
+        Ok(format!("map<{key}, {value}>"))
+    }
+
+    fn render_reference(&mut self, reference: &str, _schema: &Schema) -> RenderResult {
+        if let Some(schema) = self.schemas.get(reference)
+            && matches!(schema.ty, SchemaType::Enum(_) | SchemaType::Struct(_))
+        {
+            return Ok(self.add_section(reference, &schema.to_owned()));
+        }
+
+        Ok(reference.into())
+    }
+
+    fn render_string(&mut self, _string: &StringType, _schema: &Schema) -> RenderResult {
+        Ok("string".into())
+    }
+
+    fn render_struct(&mut self, _structure: &StructType, schema: &Schema) -> RenderResult {
+        let name = match &schema.name {
+            Some(name) => name.to_owned(),
+            None => self.create_inline_name(schema),
+        };
+
+        Ok(self.add_section(&name, schema))
+    }
+
+    fn render_tuple(&mut self, tuple: &TupleType, _schema: &Schema) -> RenderResult {
+        let mut items = vec![];
+
+        for item in &tuple.items_types {
+            items.push(self.render_schema(item)?);
+        }
+
+        Ok(format!("[{}]", items.join(", ")))
+    }
+
+    fn render_union(&mut self, uni: &UnionType, _schema: &Schema) -> RenderResult {
+        let mut items = vec![];
+
+        for item in &uni.variants_types {
+            items.push(self.render_schema(item)?);
+        }
+
+        Ok(items.join(" | "))
+    }
+
+    fn render_unknown(&mut self, _schema: &Schema) -> RenderResult {
+        Ok("unknown".into())
+    }
+
+    fn render(&mut self, schemas: IndexMap<String, Schema>) -> RenderResult {
+        self.schemas = schemas;
+
+        let Some((name, root)) = self
+            .schemas
+            .iter()
+            .last()
+            .map(|(name, schema)| (name.to_owned(), schema.to_owned()))
+        else {
+            return Err(miette!(
+                "At least 1 schema is required to generate documentation."
+            ));
+        };
+
+        let SchemaType::Struct(structure) = &root.ty else {
+            return Err(miette!("The last registered schema must be a struct type."));
+        };
+
+        let mut out = vec![format!("# {name}")];
+
+        if let Some(description) = &root.description {
+            out.push(render_description(description));
+        }
+
+        self.add_section(&name, &root);
+
+        out.extend(self.render_fields(&name, "", structure, 2)?);
+
+        // Sections are added while rendering the settings of previous sections
+        let mut index = 1;
+
+        while let Some((name, section)) = self
+            .sections
+            .get_index(index)
+            .map(|(name, section)| (name.to_owned(), section.clone()))
+        {
+            out.push(self.render_section(&name, &section)?);
+            index += 1;
+        }
+
+        let mut output = String::new();
+
+        if !self.options.header.is_empty() {
+            output.push_str(&self.options.header);
+        }
+
+        output.push_str(&out.join("\n\n"));
+
+        if !self.options.footer.is_empty() {
+            output.push_str(&self.options.footer);
+        }
+
+        Ok(output)
+    }
+}
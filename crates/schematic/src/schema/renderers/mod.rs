@@ -1,3 +1,9 @@
+#[cfg(feature = "renderer_env_vars")]
+pub mod env_vars;
+
+#[cfg(feature = "renderer_go")]
+pub mod go;
+
 #[cfg(feature = "renderer_json_schema")]
 pub mod json_schema;
 
@@ -7,9 +13,21 @@ pub mod json_template;
 #[cfg(all(feature = "renderer_template", feature = "json"))]
 pub mod jsonc_template;
 
+#[cfg(feature = "renderer_markdown")]
+pub mod markdown;
+
+#[cfg(feature = "renderer_pkl")]
+pub mod pkl;
+
 #[cfg(all(feature = "renderer_template", feature = "pkl"))]
 pub mod pkl_template;
 
+#[cfg(feature = "renderer_python")]
+pub mod python;
+
+#[cfg(feature = "renderer_rust")]
+pub mod rust;
+
 #[cfg(feature = "renderer_template")]
 pub mod template;
 
@@ -21,3 +39,6 @@ pub mod typescript;
 
 #[cfg(all(feature = "renderer_template", feature = "yaml"))]
 pub mod yaml_template;
+
+#[cfg(feature = "renderer_zod")]
+pub mod zod;
@@ -0,0 +1,793 @@
+use crate::schema::{RenderResult, SchemaRenderer};
+use convert_case::{Boundary, Case, Casing};
+use indexmap::IndexMap;
+use schematic_types::*;
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+const DIGIT_BOUNDARIES: [Boundary; 4] = [
+    Boundary::UpperDigit,
+    Boundary::LowerDigit,
+    Boundary::DigitUpper,
+    Boundary::DigitLower,
+];
+
+const KEYWORDS: [&str; 41] = [
+    "abstract",
+    "amends",
+    "as",
+    "case",
+    "class",
+    "const",
+    "delete",
+    "else",
+    "extends",
+    "external",
+    "false",
+    "fixed",
+    "for",
+    "function",
+    "hidden",
+    "if",
+    "import",
+    "in",
+    "is",
+    "let",
+    "local",
+    "module",
+    "new",
+    "nothing",
+    "null",
+    "open",
+    "out",
+    "outer",
+    "override",
+    "protected",
+    "read",
+    "record",
+    "super",
+    "switch",
+    "this",
+    "throw",
+    "trace",
+    "true",
+    "typealias",
+    "unknown",
+    "vararg",
+];
+
+/// Options to control the rendered Pkl output.
+#[derive(Default)]
+pub struct PklOptions {
+    /// List of references to exclude from rendering as a class or type alias.
+    pub exclude_references: Vec<String>,
+
+    /// Map of module URIs to a list of types to import.
+    /// Will be rendered as an `import "uri"` declaration, and types
+    /// will be qualified with the file name of the module.
+    pub external_types: HashMap<String, Vec<String>>,
+
+    /// Character(s) to use for indentation.
+    pub indent_char: String,
+
+    /// Name of the module declared at the top of the file. Defaults to
+    /// the name of the root schema.
+    pub module_name: Option<String>,
+}
+
+/// Renders a Pkl module, with classes and type aliases, from a schema.
+/// The fields of the root schema are rendered as properties of the module,
+/// so that config files can `amends` it.
+#[derive(Default)]
+pub struct PklRenderer {
+    context: Vec<String>,
+    hoisted: Vec<String>,
+    names: HashSet<String>,
+    options: PklOptions,
+    references: HashSet<String>,
+    used: HashSet<String>,
+}
+
+fn sanitize(value: &str) -> String {
+    let value = value
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect::<String>();
+
+    value.trim_matches('_').to_owned()
+}
+
+fn to_case(value: &str, to: Case) -> String {
+    sanitize(value)
+        .remove_boundaries(&DIGIT_BOUNDARIES)
+        .to_case(to)
+}
+
+fn to_identifier(value: &str) -> String {
+    let is_valid = value
+        .chars()
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_' || c == '$')
+        && value
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '$');
+
+    if is_valid && !KEYWORDS.contains(&value) {
+        value.to_owned()
+    } else {
+        format!("`{value}`")
+    }
+}
+
+fn lit_to_string(lit: &LiteralValue) -> String {
+    match lit {
+        LiteralValue::Bool(inner) => inner.to_string(),
+        LiteralValue::F32(inner) => format!("{inner:?}"),
+        LiteralValue::F64(inner) => format!("{inner:?}"),
+        LiteralValue::Int(inner) => inner.to_string(),
+        LiteralValue::UInt(inner) => inner.to_string(),
+        LiteralValue::String(inner) => format!("{inner:?}"),
+    }
+}
+
+fn lit_to_type(lit: &LiteralValue) -> String {
+    match lit {
+        // Only strings are supported as literal types
+        LiteralValue::String(_) => lit_to_string(lit),
+        LiteralValue::Bool(_) => format!("Boolean(this == {})", lit_to_string(lit)),
+        LiteralValue::F32(_) | LiteralValue::F64(_) => {
+            format!("Float(this == {})", lit_to_string(lit))
+        }
+        LiteralValue::Int(_) | LiteralValue::UInt(_) => {
+            format!("Int(this == {})", lit_to_string(lit))
+        }
+    }
+}
+
+fn with_constraints(ty: &str, constraints: Vec<String>) -> String {
+    if constraints.is_empty() {
+        ty.to_owned()
+    } else {
+        format!("{ty}({})", constraints.join(", "))
+    }
+}
+
+fn render_bounds<T: ToString>(
+    subject: &str,
+    min: Option<T>,
+    max: Option<T>,
+    constraints: &mut Vec<String>,
+) {
+    match (min, max) {
+        (Some(min), Some(max)) => constraints.push(format!(
+            "{subject}isBetween({}, {})",
+            min.to_string(),
+            max.to_string()
+        )),
+        (Some(min), None) => constraints.push(format!(
+            "{} >= {}",
+            if subject.is_empty() {
+                "this"
+            } else {
+                subject.trim_end_matches('.')
+            },
+            min.to_string()
+        )),
+        (None, Some(max)) => constraints.push(format!(
+            "{} <= {}",
+            if subject.is_empty() {
+                "this"
+            } else {
+                subject.trim_end_matches('.')
+            },
+            max.to_string()
+        )),
+        (None, None) => {}
+    };
+}
+
+fn make_nullable(ty: String) -> String {
+    if ty.ends_with('?') {
+        ty
+    } else if ty.contains('|') {
+        format!("({ty})?")
+    } else {
+        format!("{ty}?")
+    }
+}
+
+impl PklRenderer {
+    pub fn new(options: PklOptions) -> Self {
+        Self {
+            options,
+            ..Default::default()
+        }
+    }
+
+    fn indent(&self) -> String {
+        if self.options.indent_char.is_empty() {
+            "  ".into()
+        } else {
+            self.options.indent_char.clone()
+        }
+    }
+
+    fn is_excluded(&self, name: &str) -> bool {
+        self.options.exclude_references.iter().any(|r| r == name)
+    }
+
+    fn get_external_module(&self, name: &str) -> Option<&str> {
+        self.options
+            .external_types
+            .iter()
+            .find(|(_, externals)| externals.iter().any(|e| e == name))
+            .map(|(uri, _)| {
+                let file = uri.rsplit('/').next().unwrap_or(uri);
+
+                file.strip_suffix(".pkl").unwrap_or(file)
+            })
+    }
+
+    fn create_type_name(&mut self) -> String {
+        let base = match self.context.last() {
+            Some(name) => name.clone(),
+            None => "Type".into(),
+        };
+        let mut name = base.clone();
+        let mut index = 1;
+
+        while self.names.contains(&name) {
+            index += 1;
+            name = format!("{base}{index}");
+        }
+
+        self.names.insert(name.clone());
+
+        name
+    }
+
+    fn render_with_context(&mut self, suffix: &str, schema: &Schema) -> RenderResult {
+        let name = format!(
+            "{}{}",
+            self.context
+                .last()
+                .map(|name| name.as_str())
+                .unwrap_or_default(),
+            suffix
+        );
+
+        self.context.push(name);
+        let out = self.render_schema(schema);
+        self.context.pop();
+
+        out
+    }
+
+    fn render_comment(
+        &self,
+        indent: &str,
+        comment: Option<&String>,
+        deprecated: Option<&String>,
+    ) -> Vec<String> {
+        let mut lines = vec![];
+
+        if let Some(comment) = comment {
+            for line in comment.trim().split('\n') {
+                let line = line.trim();
+
+                lines.push(if line.is_empty() {
+                    format!("{indent}///")
+                } else {
+                    format!("{indent}/// {line}")
+                });
+            }
+        }
+
+        if let Some(deprecated) = deprecated {
+            lines.push(if deprecated.is_empty() {
+                format!("{indent}@Deprecated")
+            } else {
+                format!("{indent}@Deprecated {{ message = {deprecated:?} }}")
+            });
+        }
+
+        lines
+    }
+
+    fn render_property(&mut self, name: &str, field: &SchemaField, indent: &str) -> RenderResult {
+        let mut value = self.render_with_context(&to_case(name, Case::Pascal), &field.schema)?;
+        let default = field.schema.get_default();
+
+        if field.optional && default.is_none() {
+            value = make_nullable(value);
+        }
+
+        let mut out =
+            self.render_comment(indent, field.comment.as_ref(), field.deprecated.as_ref());
+
+        out.push(match default {
+            Some(default) => format!(
+                "{indent}{}: {value} = {}",
+                to_identifier(name),
+                lit_to_string(default)
+            ),
+            None => format!("{indent}{}: {value}", to_identifier(name)),
+        });
+
+        Ok(out.join("\n"))
+    }
+
+    fn render_properties(&mut self, structure: &StructType, indent: &str) -> RenderResult<String> {
+        let mut properties = vec![];
+
+        for (name, field) in &structure.fields {
+            if field.hidden {
+                continue;
+            }
+
+            // Pkl has no concept of flattening, so merge the fields instead
+            if field.flatten {
+                if let Some(SchemaType::Struct(inner)) =
+                    field.schema.get_nonnull_schema().map(|schema| &schema.ty)
+                {
+                    properties.push(self.render_properties(inner, indent)?);
+                }
+
+                continue;
+            }
+
+            properties.push(self.render_property(name, field, indent)?);
+        }
+
+        // Separate documented properties with a blank line
+        let mut out = String::new();
+
+        for (index, property) in properties.iter().enumerate() {
+            if index > 0 {
+                out.push('\n');
+
+                if property.contains('\n') || properties[index - 1].contains('\n') {
+                    out.push('\n');
+                }
+            }
+
+            out.push_str(property);
+        }
+
+        Ok(out)
+    }
+
+    fn export_type_alias(&mut self, name: &str, schema: &Schema) -> RenderResult {
+        self.context.push(name.to_owned());
+        let value = self.render_schema_without_reference(schema);
+        self.context.pop();
+
+        let mut out =
+            self.render_comment("", schema.description.as_ref(), schema.deprecated.as_ref());
+
+        out.push(format!("typealias {name} = {}", value?));
+
+        Ok(out.join("\n"))
+    }
+
+    fn export_class(
+        &mut self,
+        name: &str,
+        structure: &StructType,
+        schema: &Schema,
+    ) -> RenderResult {
+        self.context.push(name.to_owned());
+        let properties = self.render_properties(structure, &self.indent());
+        self.context.pop();
+
+        let properties = properties?;
+        let mut out =
+            self.render_comment("", schema.description.as_ref(), schema.deprecated.as_ref());
+
+        out.push(if properties.is_empty() {
+            format!("class {name}")
+        } else {
+            format!("class {name} {{\n{properties}\n}}")
+        });
+
+        Ok(out.join("\n"))
+    }
+
+    fn export_schema(&mut self, name: &str, schema: &Schema) -> RenderResult {
+        match &schema.ty {
+            SchemaType::Struct(inner) => self.export_class(name, inner, schema),
+            _ => self.export_type_alias(name, schema),
+        }
+    }
+
+    fn hoist(&mut self, schema: &Schema) -> RenderResult {
+        let name = self.create_type_name();
+        let output = self.export_schema(&name, schema)?;
+
+        self.hoisted.push(output);
+
+        Ok(name)
+    }
+}
+
+impl SchemaRenderer<String> for PklRenderer {
+    fn is_reference(&self, name: &str) -> bool {
+        self.references.contains(name) || self.get_external_module(name).is_some()
+    }
+
+    fn render_array(&mut self, array: &ArrayType, _schema: &Schema) -> RenderResult {
+        let mut constraints = vec![];
+
+        render_bounds(
+            "length.",
+            array.min_length,
+            array.max_length,
+            &mut constraints,
+        );
+
+        if array.unique.is_some_and(|unique| unique) {
+            constraints.push("isDistinct".into());
+        }
+
+        Ok(with_constraints(
+            &format!(
+                "Listing<{}>",
+                self.render_with_context("Item", &array.items_type)?
+            ),
+            constraints,
+        ))
+    }
+
+    fn render_boolean(&mut self, _boolean: &BooleanType, _schema: &Schema) -> RenderResult {
+        Ok("Boolean".into())
+    }
+
+    fn render_enum(&mut self, enu: &EnumType, _schema: &Schema) -> RenderResult {
+        let mut items = vec![];
+        let mut strings = true;
+
+        let mut push_item = |value: &LiteralValue| {
+            strings = strings && matches!(value, LiteralValue::String(_));
+            items.push(value.clone());
+        };
+
+        let mut fallback = false;
+
+        match &enu.variants {
+            Some(variants) => {
+                for variant in variants.values() {
+                    if variant.hidden {
+                        continue;
+                    }
+
+                    match &variant.schema.ty {
+                        SchemaType::Literal(lit) => push_item(&lit.value),
+                        _ => fallback = true,
+                    };
+                }
+            }
+            None => {
+                for value in &enu.values {
+                    push_item(value);
+                }
+            }
+        };
+
+        if !strings {
+            let base = match items.first() {
+                Some(LiteralValue::Bool(_)) => "Boolean",
+                Some(LiteralValue::F32(_) | LiteralValue::F64(_)) => "Float",
+                _ => "Int",
+            };
+
+            return Ok(format!(
+                "{base}(List({}).contains(this))",
+                items
+                    .iter()
+                    .map(lit_to_string)
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ));
+        }
+
+        let mut values = items
+            .iter()
+            .enumerate()
+            .map(|(index, value)| {
+                // Mark the default value of the type
+                if enu.default_index == Some(index) {
+                    format!("*{}", lit_to_string(value))
+                } else {
+                    lit_to_string(value)
+                }
+            })
+            .collect::<Vec<_>>();
+
+        if fallback {
+            values.push("String".into());
+        }
+
+        Ok(values.join("|"))
+    }
+
+    fn render_float(&mut self, float: &FloatType, _schema: &Schema) -> RenderResult {
+        if let Some(values) = &float.enum_values {
+            return Ok(format!(
+                "Float(List({}).contains(this))",
+                values
+                    .iter()
+                    .map(|value| format!("{value:?}"))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ));
+        }
+
+        let mut constraints = vec![];
+
+        render_bounds(
+            "",
+            float.min.map(|value| format!("{value:?}")),
+            float.max.map(|value| format!("{value:?}")),
+            &mut constraints,
+        );
+
+        if let Some(min) = float.min_exclusive {
+            constraints.push(format!("this > {min:?}"));
+        }
+
+        if let Some(max) = float.max_exclusive {
+            constraints.push(format!("this < {max:?}"));
+        }
+
+        if let Some(multiple) = float.multiple_of {
+            constraints.push(format!("this % {multiple:?} == 0"));
+        }
+
+        Ok(with_constraints("Float", constraints))
+    }
+
+    fn render_integer(&mut self, integer: &IntegerType, _schema: &Schema) -> RenderResult {
+        if let Some(values) = &integer.enum_values {
+            return Ok(format!(
+                "Int(List({}).contains(this))",
+                values
+                    .iter()
+                    .map(|value| value.to_string())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ));
+        }
+
+        let ty = match integer.kind {
+            IntegerKind::I8 => "Int8",
+            IntegerKind::I16 => "Int16",
+            IntegerKind::I32 => "Int32",
+            IntegerKind::Isize | IntegerKind::I64 | IntegerKind::I128 => "Int",
+            IntegerKind::U8 => "UInt8",
+            IntegerKind::U16 => "UInt16",
+            IntegerKind::U32 => "UInt32",
+            IntegerKind::Usize | IntegerKind::U64 | IntegerKind::U128 => "UInt",
+        };
+
+        let mut constraints = vec![];
+
+        render_bounds("", integer.min, integer.max, &mut constraints);
+
+        if let Some(min) = integer.min_exclusive {
+            constraints.push(format!("this > {min}"));
+        }
+
+        if let Some(max) = integer.max_exclusive {
+            constraints.push(format!("this < {max}"));
+        }
+
+        if let Some(multiple) = integer.multiple_of {
+            constraints.push(format!("this % {multiple} == 0"));
+        }
+
+        Ok(with_constraints(ty, constraints))
+    }
+
+    fn render_literal(&mut self, literal: &LiteralType, _schema: &Schema) -> RenderResult {
+        Ok(lit_to_type(&literal.value))
+    }
+
+    fn render_null(&mut self, _schema: &Schema) -> RenderResult {
+        Ok("Null".into())
+    }
+
+    fn render_object(&mut self, object: &ObjectType, _schema: &Schema) -> RenderResult {
+        let mut constraints = vec![];
+
+        render_bounds(
+            "length.",
+            object.min_length,
+            object.max_length,
+            &mut constraints,
+        );
+
+        Ok(with_constraints(
+            &format!(
+                "Mapping<{}, {}>",
+                self.render_with_context("Key", &object.key_type)?,
+                self.render_with_context("Value", &object.value_type)?
+            ),
+            constraints,
+        ))
+    }
+
+    fn render_reference(&mut self, reference: &str, _schema: &Schema) -> RenderResult {
+        self.used.insert(reference.to_owned());
+
+        Ok(match self.get_external_module(reference) {
+            Some(module) => format!("{module}.{reference}"),
+            None => reference.into(),
+        })
+    }
+
+    fn render_string(&mut self, string: &StringType, _schema: &Schema) -> RenderResult {
+        if let Some(values) = &string.enum_values {
+            return Ok(values
+                .iter()
+                .map(|value| format!("{value:?}"))
+                .collect::<Vec<_>>()
+                .join("|"));
+        }
+
+        let mut constraints = vec![];
+
+        render_bounds(
+            "length.",
+            string.min_length,
+            string.max_length,
+            &mut constraints,
+        );
+
+        if let Some(pattern) = &string.pattern {
+            // Use a custom delimiter so the pattern doesn't need escaping
+            let mut delimiter = "#".to_owned();
+
+            while pattern.contains(&format!("\"{delimiter}")) {
+                delimiter.push('#');
+            }
+
+            constraints.push(format!(
+                "matches(Regex({delimiter}\"{pattern}\"{delimiter}))"
+            ));
+        }
+
+        Ok(with_constraints("String", constraints))
+    }
+
+    fn render_struct(&mut self, _structure: &StructType, schema: &Schema) -> RenderResult {
+        self.hoist(schema)
+    }
+
+    fn render_tuple(&mut self, tuple: &TupleType, _schema: &Schema) -> RenderResult {
+        let mut items = vec![];
+
+        for (index, item) in tuple.items_types.iter().enumerate() {
+            let item = self.render_with_context(&format!("Item{index}"), item)?;
+
+            if !items.contains(&item) {
+                items.push(item);
+            }
+        }
+
+        Ok(format!(
+            "Listing<{}>(length == {})",
+            items.join("|"),
+            tuple.items_types.len()
+        ))
+    }
+
+    fn render_union(&mut self, uni: &UnionType, _schema: &Schema) -> RenderResult {
+        let mut items = vec![];
+
+        for (index, item) in uni.variants_types.iter().enumerate() {
+            if item.is_null() {
+                continue;
+            }
+
+            let mut value = self.render_schema(item)?;
+
+            if value.contains('|') && !value.starts_with('(') {
+                value = format!("({value})");
+            }
+
+            // Mark the default variant, unless it defaults to null
+            if uni.default_index == Some(index) && !uni.has_null() && !value.starts_with('*') {
+                value = format!("*{value}");
+            }
+
+            items.push(value);
+        }
+
+        let value = match items.len() {
+            0 => return Ok("Null".into()),
+            _ => items.join("|"),
+        };
+
+        Ok(if uni.has_null() {
+            make_nullable(value)
+        } else {
+            value
+        })
+    }
+
+    fn render_unknown(&mut self, _schema: &Schema) -> RenderResult {
+        Ok("Any".into())
+    }
+
+    fn render(&mut self, schemas: IndexMap<String, Schema>) -> RenderResult {
+        self.references = HashSet::from_iter(schemas.keys().cloned());
+        self.names = self.references.clone();
+
+        let mut outputs = vec![];
+        let mut root = None;
+
+        for (index, (name, schema)) in schemas.iter().enumerate() {
+            if self.is_excluded(name) {
+                continue;
+            }
+
+            // The last struct is rendered as the module itself
+            if index == schemas.len() - 1
+                && let SchemaType::Struct(inner) = &schema.ty
+            {
+                self.context.push(name.to_owned());
+                let properties = self.render_properties(inner, "");
+                self.context.pop();
+
+                outputs.append(&mut self.hoisted);
+                root = Some((name, schema, properties?));
+
+                continue;
+            }
+
+            let output = self.export_schema(name, schema)?;
+
+            outputs.append(&mut self.hoisted);
+            outputs.push(output);
+        }
+
+        let mut header = vec!["// Automatically generated by schematic. DO NOT MODIFY!".to_owned()];
+
+        if let Some((name, schema, _)) = &root {
+            let mut module =
+                self.render_comment("", schema.description.as_ref(), schema.deprecated.as_ref());
+
+            module.push(format!(
+                "module {}",
+                self.options.module_name.as_deref().unwrap_or(name)
+            ));
+
+            header.push(module.join("\n"));
+        }
+
+        let imports = BTreeMap::from_iter(&self.options.external_types)
+            .into_keys()
+            .map(|uri| format!("import {uri:?}"))
+            .collect::<Vec<_>>();
+
+        if !imports.is_empty() {
+            header.push(imports.join("\n"));
+        }
+
+        if let Some((name, schema, properties)) = root {
+            if !properties.is_empty() {
+                header.push(properties);
+            }
+
+            // The module can't be referenced by name, so it must also be a class
+            if self.used.contains(name)
+                && let SchemaType::Struct(inner) = &schema.ty
+            {
+                outputs.push(self.export_class(name, inner, schema)?);
+            }
+        }
+
+        header.extend(outputs);
+
+        Ok(header.join("\n\n"))
+    }
+}
@@ -0,0 +1,911 @@
+use crate::schema::{RenderResult, SchemaRenderer};
+use convert_case::{Boundary, Case, Casing};
+use indexmap::{IndexMap, IndexSet};
+use schematic_types::*;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+
+const KEYWORDS: [&str; 35] = [
+    "False", "None", "True", "and", "as", "assert", "async", "await", "break", "class", "continue",
+    "def", "del", "elif", "else", "except", "finally", "for", "from", "global", "if", "import",
+    "in", "is", "lambda", "nonlocal", "not", "or", "pass", "raise", "return", "try", "while",
+    "with", "yield",
+];
+
+// Attributes of `BaseModel` that can't be shadowed by a field
+const MODEL_ATTRIBUTES: [&str; 12] = [
+    "construct",
+    "copy",
+    "dict",
+    "from_orm",
+    "json",
+    "parse_file",
+    "parse_obj",
+    "parse_raw",
+    "schema",
+    "schema_json",
+    "update_forward_refs",
+    "validate",
+];
+
+/// Format of the rendered Python classes.
+#[derive(Default)]
+pub enum PythonFormat {
+    /// Pydantic (v2) models: `class Name(BaseModel): ...`
+    #[default]
+    Pydantic,
+    /// Typed dictionaries: `class Name(TypedDict): ...`
+    TypedDict,
+}
+
+/// Format of the rendered Python enums.
+#[derive(Default)]
+pub enum PythonEnumFormat {
+    /// Enum class: `class Name(str, Enum): ...`
+    Enum,
+    /// Literal type alias: `Name = Literal["foo", "bar"]`
+    #[default]
+    Literal,
+}
+
+/// Options to control the rendered Python output.
+#[derive(Default)]
+pub struct PythonOptions {
+    /// Format to render enums, either a `Literal` or an `Enum` class.
+    pub enum_format: PythonEnumFormat,
+
+    /// List of references to exclude from rendering as a type.
+    pub exclude_references: Vec<String>,
+
+    /// Map of module paths to a list of types to import.
+    /// Will be rendered as a `from module import ...` declaration.
+    pub external_types: HashMap<String, Vec<String>>,
+
+    /// Format to render structs, either a pydantic model or a `TypedDict`.
+    pub format: PythonFormat,
+
+    /// Character(s) to use for indentation.
+    pub indent_char: String,
+}
+
+/// Renders Python type definitions (pydantic models or typed dictionaries)
+/// from a schema.
+#[derive(Default)]
+pub struct PythonRenderer {
+    classes: Vec<String>,
+    context: Vec<String>,
+    defined: HashSet<String>,
+    forward_refs: IndexSet<String>,
+    hoisted: Vec<String>,
+    imports: HashMap<&'static str, BTreeSet<&'static str>>,
+    names: HashSet<String>,
+    options: PythonOptions,
+    references: HashSet<String>,
+}
+
+fn is_identifier(value: &str) -> bool {
+    value
+        .chars()
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && value.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+        && !KEYWORDS.contains(&value)
+}
+
+fn to_case(value: &str, to: Case) -> String {
+    value
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect::<String>()
+        .remove_boundaries(&[
+            Boundary::UpperDigit,
+            Boundary::LowerDigit,
+            Boundary::DigitUpper,
+            Boundary::DigitLower,
+        ])
+        .to_case(to)
+}
+
+fn lit_to_string(lit: &LiteralValue) -> String {
+    match lit {
+        LiteralValue::Bool(inner) => {
+            if *inner {
+                "True".into()
+            } else {
+                "False".into()
+            }
+        }
+        LiteralValue::F32(inner) => format!("{inner:?}"),
+        LiteralValue::F64(inner) => format!("{inner:?}"),
+        LiteralValue::Int(inner) => inner.to_string(),
+        LiteralValue::UInt(inner) => inner.to_string(),
+        LiteralValue::String(inner) => format!("{inner:?}"),
+    }
+}
+
+fn collect_references(schema: &Schema, references: &HashSet<String>, deps: &mut Vec<String>) {
+    if let Some(name) = &schema.name
+        && references.contains(name)
+    {
+        deps.push(name.to_owned());
+        return;
+    }
+
+    match &schema.ty {
+        SchemaType::Array(inner) => collect_references(&inner.items_type, references, deps),
+        SchemaType::Object(inner) => {
+            collect_references(&inner.key_type, references, deps);
+            collect_references(&inner.value_type, references, deps);
+        }
+        SchemaType::Reference(name) => deps.push(name.to_owned()),
+        SchemaType::Struct(inner) => {
+            for field in inner.fields.values() {
+                collect_references(&field.schema, references, deps);
+            }
+        }
+        SchemaType::Tuple(inner) => {
+            for item in &inner.items_types {
+                collect_references(item, references, deps);
+            }
+        }
+        SchemaType::Union(inner) => {
+            for item in &inner.variants_types {
+                collect_references(item, references, deps);
+            }
+        }
+        _ => {}
+    };
+}
+
+impl PythonRenderer {
+    pub fn new(options: PythonOptions) -> Self {
+        Self {
+            options,
+            ..Default::default()
+        }
+    }
+
+    fn indent(&self) -> String {
+        if self.options.indent_char.is_empty() {
+            "    ".into()
+        } else {
+            self.options.indent_char.clone()
+        }
+    }
+
+    fn import(&mut self, module: &'static str, name: &'static str) {
+        self.imports.entry(module).or_default().insert(name);
+    }
+
+    fn is_excluded(&self, name: &str) -> bool {
+        self.options.exclude_references.iter().any(|r| r == name)
+    }
+
+    fn is_external(&self, name: &str) -> bool {
+        self.options
+            .external_types
+            .values()
+            .any(|externals| externals.iter().any(|e| e == name))
+    }
+
+    fn is_pydantic(&self) -> bool {
+        matches!(self.options.format, PythonFormat::Pydantic)
+    }
+
+    fn create_type_name(&mut self) -> String {
+        let base = self
+            .context
+            .last()
+            .cloned()
+            .unwrap_or_else(|| "Type".into());
+        let mut name = base.clone();
+        let mut index = 1;
+
+        while self.names.contains(&name) {
+            index += 1;
+            name = format!("{base}{index}");
+        }
+
+        self.names.insert(name.clone());
+
+        name
+    }
+
+    fn create_field_name(&self, name: &str) -> String {
+        let field = to_case(name, Case::Snake);
+
+        if field.is_empty() || field.starts_with(|c: char| c.is_ascii_digit()) {
+            return format!("field_{field}");
+        }
+
+        if KEYWORDS.contains(&field.as_str())
+            || MODEL_ATTRIBUTES.contains(&field.as_str())
+            || field.starts_with("model_")
+        {
+            return format!("{field}_");
+        }
+
+        field
+    }
+
+    fn create_member_name(&self, value: &LiteralValue) -> String {
+        let member = match value {
+            LiteralValue::String(inner) => to_case(inner, Case::UpperSnake),
+            other => format!("VALUE_{}", to_case(&other.to_string(), Case::UpperSnake)),
+        };
+
+        if member.is_empty() || member.starts_with(|c: char| c.is_ascii_digit()) {
+            format!("VALUE_{member}")
+        } else {
+            member
+        }
+    }
+
+    fn render_with_context(&mut self, suffix: &str, schema: &Schema) -> RenderResult {
+        let name = format!(
+            "{}{}",
+            self.context
+                .last()
+                .map(|name| name.as_str())
+                .unwrap_or_default(),
+            suffix
+        );
+
+        self.context.push(name);
+        let out = self.render_schema(schema);
+        self.context.pop();
+
+        out
+    }
+
+    fn render_docstring(&self, indent: &str, description: Option<&String>) -> Option<String> {
+        let description = description?.trim();
+
+        if description.is_empty() {
+            return None;
+        }
+
+        let description = description.replace("\"\"\"", "\\\"\\\"\\\"");
+        let lines = description
+            .lines()
+            .map(|line| line.trim())
+            .collect::<Vec<_>>();
+
+        Some(if lines.len() == 1 {
+            format!("{indent}\"\"\"{}\"\"\"", lines[0])
+        } else {
+            let mut out = vec![format!("{indent}\"\"\"{}", lines[0])];
+
+            for line in &lines[1..] {
+                out.push(if line.is_empty() {
+                    String::new()
+                } else {
+                    format!("{indent}{line}")
+                });
+            }
+
+            out.push(format!("{indent}\"\"\""));
+            out.join("\n")
+        })
+    }
+
+    fn render_optional(&mut self, value: String) -> String {
+        self.import("typing", "Optional");
+
+        format!("Optional[{value}]")
+    }
+
+    fn export_type_alias(&mut self, name: &str, schema: &Schema) -> RenderResult {
+        self.context.push(name.to_owned());
+        let value = self.render_schema_without_reference(schema);
+        self.context.pop();
+
+        let mut out = vec![format!("{name} = {}", value?)];
+
+        if let Some(docstring) = self.render_docstring("", schema.description.as_ref()) {
+            out.push(docstring);
+        }
+
+        Ok(out.join("\n"))
+    }
+
+    fn render_default(&self, schema: &Schema, default: &LiteralValue) -> String {
+        // Enum classes should default to a member instead of the raw value
+        if matches!(self.options.enum_format, PythonEnumFormat::Enum)
+            && let Some(inner) = schema.get_nonnull_schema()
+            && let Some(name) = &inner.name
+            && let SchemaType::Enum(enu) = &inner.ty
+            && enu
+                .variants
+                .as_ref()
+                .is_none_or(|variants| variants.len() == enu.values.len())
+            && enu.values.contains(default)
+            && self.references.contains(name)
+        {
+            return format!("{name}.{}", self.create_member_name(default));
+        }
+
+        lit_to_string(default)
+    }
+
+    fn export_enum(&mut self, name: &str, enu: &EnumType, schema: &Schema) -> RenderResult {
+        // A fallback variant can't be represented as a member
+        let has_fallback = enu
+            .variants
+            .as_ref()
+            .is_some_and(|variants| variants.len() != enu.values.len());
+
+        if has_fallback || matches!(self.options.enum_format, PythonEnumFormat::Literal) {
+            return self.export_type_alias(name, schema);
+        }
+
+        self.import("enum", "Enum");
+
+        let indent = self.indent();
+        let base = if enu
+            .values
+            .iter()
+            .all(|value| matches!(value, LiteralValue::String(_)))
+        {
+            "str, Enum"
+        } else if enu
+            .values
+            .iter()
+            .all(|value| matches!(value, LiteralValue::Int(_) | LiteralValue::UInt(_)))
+        {
+            "int, Enum"
+        } else {
+            "Enum"
+        };
+
+        let mut out = vec![format!("class {name}({base}):")];
+
+        if let Some(docstring) = self.render_docstring(&indent, schema.description.as_ref()) {
+            out.push(docstring);
+            out.push(String::new());
+        }
+
+        for value in &enu.values {
+            out.push(format!(
+                "{indent}{} = {}",
+                self.create_member_name(value),
+                lit_to_string(value)
+            ));
+        }
+
+        Ok(out.join("\n"))
+    }
+
+    fn export_model(
+        &mut self,
+        name: &str,
+        structure: &StructType,
+        schema: &Schema,
+    ) -> RenderResult {
+        self.import("pydantic", "BaseModel");
+
+        let indent = self.indent();
+        let mut out = vec![format!("class {name}(BaseModel):")];
+        let mut fields = vec![];
+
+        if let Some(docstring) = self.render_docstring(&indent, schema.description.as_ref()) {
+            out.push(docstring);
+        }
+
+        self.classes.push(name.to_owned());
+        self.context.push(name.to_owned());
+
+        for (field_name, field) in &structure.fields {
+            if field.hidden {
+                continue;
+            }
+
+            let attr_name = self.create_field_name(field_name);
+            let mut value =
+                self.render_with_context(&to_case(field_name, Case::Pascal), &field.schema)?;
+            let mut args = vec![];
+
+            if let Some(default) = field.schema.get_default() {
+                args.push(format!(
+                    "default={}",
+                    self.render_default(&field.schema, default)
+                ));
+            } else if field.optional || field.nullable || field.schema.is_nullable() {
+                match field.schema.get_nonnull_schema().map(|schema| &schema.ty) {
+                    Some(SchemaType::Array(_)) if !field.schema.is_nullable() => {
+                        args.push("default_factory=list".into());
+                    }
+                    Some(SchemaType::Object(_)) if !field.schema.is_nullable() => {
+                        args.push("default_factory=dict".into());
+                    }
+                    _ => {
+                        if !field.schema.is_nullable() {
+                            value = self.render_optional(value);
+                        }
+
+                        args.push("default=None".into());
+                    }
+                };
+            }
+
+            if attr_name != *field_name {
+                args.push(format!("alias={field_name:?}"));
+            }
+
+            if !field.aliases.is_empty() {
+                self.import("pydantic", "AliasChoices");
+
+                let mut choices = vec![format!("{field_name:?}")];
+                choices.extend(field.aliases.iter().map(|alias| format!("{alias:?}")));
+
+                args.push(format!(
+                    "validation_alias=AliasChoices({})",
+                    choices.join(", ")
+                ));
+            }
+
+            if let Some(comment) = &field.comment {
+                let comment = comment
+                    .trim()
+                    .lines()
+                    .map(|line| line.trim())
+                    .collect::<Vec<_>>()
+                    .join("\n");
+
+                args.push(format!("description={comment:?}"));
+            }
+
+            if let Some(deprecated) = &field.deprecated {
+                args.push(if deprecated.is_empty() {
+                    "deprecated=True".into()
+                } else {
+                    format!("deprecated={deprecated:?}")
+                });
+            }
+
+            fields.push(if args.is_empty() {
+                format!("{indent}{attr_name}: {value}")
+            } else {
+                self.import("pydantic", "Field");
+
+                format!("{indent}{attr_name}: {value} = Field({})", args.join(", "))
+            });
+        }
+
+        self.classes.pop();
+        self.context.pop();
+
+        if fields.is_empty() {
+            if out.len() == 1 {
+                out.push(format!("{indent}pass"));
+            }
+        } else {
+            if out.len() > 1 {
+                out.push(String::new());
+            }
+
+            out.extend(fields);
+        }
+
+        Ok(out.join("\n"))
+    }
+
+    fn export_typed_dict(
+        &mut self,
+        name: &str,
+        structure: &StructType,
+        schema: &Schema,
+    ) -> RenderResult {
+        self.import("typing", "TypedDict");
+
+        let indent = self.indent();
+        let mut fields = vec![];
+
+        self.classes.push(name.to_owned());
+        self.context.push(name.to_owned());
+
+        for (field_name, field) in &structure.fields {
+            if field.hidden {
+                continue;
+            }
+
+            let mut value =
+                self.render_with_context(&to_case(field_name, Case::Pascal), &field.schema)?;
+
+            if field.optional || field.nullable || field.schema.is_nullable() {
+                self.import("typing", "NotRequired");
+
+                value = format!("NotRequired[{value}]");
+            }
+
+            let mut lines = vec![];
+
+            if let Some(comment) = &field.comment {
+                lines.push(comment.trim().to_owned());
+            }
+
+            if let Some(default) = field.schema.get_default() {
+                lines.push(format!("Defaults to `{}`.", lit_to_string(default)));
+            }
+
+            if let Some(deprecated) = &field.deprecated {
+                lines.push(if deprecated.is_empty() {
+                    "Deprecated.".into()
+                } else {
+                    format!("Deprecated: {deprecated}")
+                });
+            }
+
+            let comment = (!lines.is_empty()).then(|| lines.join("\n\n"));
+
+            fields.push((field_name.to_owned(), value.clone(), comment));
+
+            for alias in &field.aliases {
+                let value = if value.starts_with("NotRequired[") {
+                    value.clone()
+                } else {
+                    self.import("typing", "NotRequired");
+
+                    format!("NotRequired[{value}]")
+                };
+
+                fields.push((
+                    alias.to_owned(),
+                    value,
+                    Some(format!("Alias of `{field_name}`.")),
+                ));
+            }
+        }
+
+        self.classes.pop();
+        self.context.pop();
+
+        // Keys that aren't identifiers require the functional syntax
+        if fields.iter().any(|(key, _, _)| !is_identifier(key)) {
+            let mut out = vec![format!("{name} = TypedDict(")];
+
+            out.push(format!("{indent}{name:?},"));
+            out.push(format!("{indent}{{"));
+
+            for (key, value, _) in fields {
+                out.push(format!("{indent}{indent}{key:?}: {value},"));
+            }
+
+            out.push(format!("{indent}}},"));
+            out.push(")".into());
+
+            if let Some(docstring) = self.render_docstring("", schema.description.as_ref()) {
+                out.push(docstring);
+            }
+
+            return Ok(out.join("\n"));
+        }
+
+        let mut out = vec![format!("class {name}(TypedDict):")];
+
+        if let Some(docstring) = self.render_docstring(&indent, schema.description.as_ref()) {
+            out.push(docstring);
+
+            if !fields.is_empty() {
+                out.push(String::new());
+            }
+        }
+
+        if fields.is_empty() && out.len() == 1 {
+            out.push(format!("{indent}pass"));
+        }
+
+        for (key, value, comment) in fields {
+            out.push(format!("{indent}{key}: {value}"));
+
+            if let Some(docstring) = self.render_docstring(&indent, comment.as_ref()) {
+                out.push(docstring);
+            }
+        }
+
+        Ok(out.join("\n"))
+    }
+
+    fn export_schema(&mut self, name: &str, schema: &Schema) -> RenderResult {
+        match &schema.ty {
+            SchemaType::Enum(inner) => self.export_enum(name, inner, schema),
+            SchemaType::Struct(inner) => {
+                if self.is_pydantic() {
+                    self.export_model(name, inner, schema)
+                } else {
+                    self.export_typed_dict(name, inner, schema)
+                }
+            }
+            _ => self.export_type_alias(name, schema),
+        }
+    }
+
+    fn hoist(&mut self, schema: &Schema) -> RenderResult {
+        let name = self.create_type_name();
+        let output = self.export_schema(&name, schema)?;
+
+        self.hoisted.push(output);
+        self.defined.insert(name.clone());
+
+        Ok(name)
+    }
+
+    /// Sort the schemas so that references are defined before they're used.
+    fn sort_schemas(&self, schemas: &IndexMap<String, Schema>) -> Vec<String> {
+        fn visit(
+            name: &str,
+            schemas: &IndexMap<String, Schema>,
+            references: &HashSet<String>,
+            visited: &mut HashSet<String>,
+            sorted: &mut Vec<String>,
+        ) {
+            if !visited.insert(name.to_owned()) {
+                return;
+            }
+
+            if let Some(schema) = schemas.get(name) {
+                let mut deps = vec![];
+
+                collect_references(
+                    &Schema {
+                        name: None,
+                        ..schema.to_owned()
+                    },
+                    references,
+                    &mut deps,
+                );
+
+                for dep in deps {
+                    visit(&dep, schemas, references, visited, sorted);
+                }
+
+                sorted.push(name.to_owned());
+            }
+        }
+
+        let mut visited = HashSet::new();
+        let mut sorted = vec![];
+
+        for name in schemas.keys() {
+            visit(name, schemas, &self.references, &mut visited, &mut sorted);
+        }
+
+        sorted
+    }
+}
+
+impl SchemaRenderer<String> for PythonRenderer {
+    fn is_reference(&self, name: &str) -> bool {
+        self.references.contains(name) || self.is_external(name)
+    }
+
+    fn render_array(&mut self, array: &ArrayType, _schema: &Schema) -> RenderResult {
+        Ok(format!(
+            "list[{}]",
+            self.render_with_context("Item", &array.items_type)?
+        ))
+    }
+
+    fn render_boolean(&mut self, _boolean: &BooleanType, _schema: &Schema) -> RenderResult {
+        Ok("bool".into())
+    }
+
+    fn render_enum(&mut self, enu: &EnumType, schema: &Schema) -> RenderResult {
+        let has_fallback = enu
+            .variants
+            .as_ref()
+            .is_some_and(|variants| variants.len() != enu.values.len());
+
+        if !has_fallback && matches!(self.options.enum_format, PythonEnumFormat::Enum) {
+            return self.hoist(schema);
+        }
+
+        self.import("typing", "Literal");
+
+        let literal = format!(
+            "Literal[{}]",
+            enu.values
+                .iter()
+                .map(lit_to_string)
+                .collect::<Vec<_>>()
+                .join(", ")
+        );
+
+        Ok(if has_fallback {
+            self.import("typing", "Union");
+
+            format!("Union[{literal}, str]")
+        } else {
+            literal
+        })
+    }
+
+    fn render_float(&mut self, _float: &FloatType, _schema: &Schema) -> RenderResult {
+        Ok("float".into())
+    }
+
+    fn render_integer(&mut self, _integer: &IntegerType, _schema: &Schema) -> RenderResult {
+        Ok("int".into())
+    }
+
+    fn render_literal(&mut self, literal: &LiteralType, _schema: &Schema) -> RenderResult {
+        self.import("typing", "Literal");
+
+        Ok(format!("Literal[{}]", lit_to_string(&literal.value)))
+    }
+
+    fn render_null(&mut self, _schema: &Schema) -> RenderResult {
+        Ok("None".into())
+    }
+
+    fn render_object(&mut self, object: &ObjectType, _schema: &Schema) -> RenderResult {
+        Ok(format!(
+            "dict[{}, {}]",
+            self.render_with_context("Key", &object.key_type)?,
+            self.render_with_context("Value", &object.value_type)?
+        ))
+    }
+
+    fn render_reference(&mut self, reference: &str, _schema: &Schema) -> RenderResult {
+        // Not defined yet, so use a forward reference
+        if self.references.contains(reference) && !self.defined.contains(reference) {
+            if let Some(name) = self.classes.last() {
+                self.forward_refs.insert(name.to_owned());
+            }
+
+            return Ok(format!("{reference:?}"));
+        }
+
+        Ok(reference.into())
+    }
+
+    fn render_string(&mut self, _string: &StringType, _schema: &Schema) -> RenderResult {
+        Ok("str".into())
+    }
+
+    fn render_struct(&mut self, _structure: &StructType, schema: &Schema) -> RenderResult {
+        self.hoist(schema)
+    }
+
+    fn render_tuple(&mut self, tuple: &TupleType, _schema: &Schema) -> RenderResult {
+        let mut items = vec![];
+
+        for (index, item) in tuple.items_types.iter().enumerate() {
+            items.push(self.render_with_context(&format!("Item{index}"), item)?);
+        }
+
+        Ok(format!("tuple[{}]", items.join(", ")))
+    }
+
+    fn render_union(&mut self, uni: &UnionType, _schema: &Schema) -> RenderResult {
+        let mut items = vec![];
+
+        for (index, item) in uni
+            .variants_types
+            .iter()
+            .filter(|item| !item.is_null())
+            .enumerate()
+        {
+            let value = self.render_with_context(&format!("Variant{index}"), item)?;
+
+            if !items.contains(&value) {
+                items.push(value);
+            }
+        }
+
+        let value = match items.len() {
+            0 => "None".into(),
+            1 => items.remove(0),
+            _ => {
+                self.import("typing", "Union");
+
+                format!("Union[{}]", items.join(", "))
+            }
+        };
+
+        Ok(if uni.has_null() && value != "None" {
+            self.render_optional(value)
+        } else {
+            value
+        })
+    }
+
+    fn render_unknown(&mut self, _schema: &Schema) -> RenderResult {
+        self.import("typing", "Any");
+
+        Ok("Any".into())
+    }
+
+    fn render(&mut self, schemas: IndexMap<String, Schema>) -> RenderResult {
+        self.references = HashSet::from_iter(schemas.keys().cloned());
+        self.names = self.references.clone();
+        self.defined = HashSet::from_iter(
+            self.options
+                .external_types
+                .values()
+                .flatten()
+                .cloned()
+                .chain(self.options.exclude_references.iter().cloned()),
+        );
+
+        let mut outputs = vec![];
+
+        for name in self.sort_schemas(&schemas) {
+            if self.is_excluded(&name) {
+                continue;
+            }
+
+            let schema = &schemas[&name];
+
+            self.context.clear();
+
+            let output = self.export_schema(&name, schema)?;
+
+            outputs.append(&mut self.hoisted);
+            outputs.push(output);
+
+            self.defined.insert(name);
+        }
+
+        // Models with forward references must be rebuilt once all types are defined
+        if self.is_pydantic() && !self.forward_refs.is_empty() {
+            outputs.push(
+                self.forward_refs
+                    .iter()
+                    .map(|name| format!("{name}.model_rebuild()"))
+                    .collect::<Vec<_>>()
+                    .join("\n"),
+            );
+        }
+
+        let mut imports = vec![];
+
+        // Standard library first, then third-party, then external types
+        for modules in [vec!["enum", "typing"], vec!["pydantic"]] {
+            let group = modules
+                .into_iter()
+                .filter_map(|module| {
+                    self.imports.get(module).map(|names| {
+                        format!(
+                            "from {module} import {}",
+                            names.iter().copied().collect::<Vec<_>>().join(", ")
+                        )
+                    })
+                })
+                .collect::<Vec<_>>();
+
+            if !group.is_empty() {
+                imports.push(group.join("\n"));
+            }
+        }
+
+        if !self.options.external_types.is_empty() {
+            let mut group = vec![];
+
+            for (module, types) in BTreeMap::from_iter(&self.options.external_types) {
+                let mut imported_types = types.to_vec();
+                imported_types.sort();
+
+                group.push(format!(
+                    "from {module} import {}",
+                    imported_types.join(", ")
+                ));
+            }
+
+            imports.push(group.join("\n"));
+        }
+
+        let mut output = vec!["# Automatically generated by schematic. DO NOT MODIFY!".to_owned()];
+
+        if !imports.is_empty() {
+            output.push(imports.join("\n\n"));
+        }
+
+        let output = output.join("\n\n");
+
+        if outputs.is_empty() {
+            return Ok(output);
+        }
+
+        Ok(format!("{output}\n\n\n{}", outputs.join("\n\n\n")))
+    }
+}
@@ -0,0 +1,819 @@
+use crate::schema::{RenderResult, SchemaRenderer};
+use convert_case::{Boundary, Case, Casing};
+use indexmap::IndexMap;
+use schematic_types::*;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+
+const DIGIT_BOUNDARIES: [Boundary; 4] = [
+    Boundary::UpperDigit,
+    Boundary::LowerDigit,
+    Boundary::DigitUpper,
+    Boundary::DigitLower,
+];
+
+const KEYWORDS: [&str; 48] = [
+    "abstract", "as", "async", "await", "become", "box", "break", "const", "continue", "do", "dyn",
+    "else", "enum", "extern", "false", "final", "fn", "for", "gen", "if", "impl", "in", "let",
+    "loop", "macro", "match", "mod", "move", "mut", "override", "priv", "pub", "ref", "return",
+    "static", "struct", "trait", "true", "try", "type", "typeof", "unsafe", "unsized", "use",
+    "virtual", "where", "while", "yield",
+];
+
+const RESERVED_KEYWORDS: [&str; 4] = ["crate", "self", "super", "Self"];
+
+/// Options to control the rendered Rust output.
+#[derive(Default)]
+pub struct RustOptions {
+    /// List of references to exclude from rendering as a type.
+    pub exclude_references: Vec<String>,
+
+    /// Map of module paths to a list of types to import.
+    /// Will be rendered as a `use path::{};` declaration.
+    pub external_types: HashMap<String, Vec<String>>,
+
+    /// Character(s) to use for indentation.
+    pub indent_char: String,
+}
+
+/// Renders Rust source code, using schematic's `Config` and `ConfigEnum`
+/// derives, from a schema.
+#[derive(Default)]
+pub struct RustRenderer {
+    configs: HashSet<String>,
+    context: Vec<String>,
+    hoisted: Vec<String>,
+    imports: BTreeSet<&'static str>,
+    names: HashSet<String>,
+    options: RustOptions,
+    references: HashSet<String>,
+}
+
+fn sanitize(value: &str) -> String {
+    let value = value
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect::<String>();
+
+    value.trim_matches('_').to_owned()
+}
+
+fn is_string_union(uni: &UnionType) -> bool {
+    let mut count = 0;
+
+    for item in &uni.variants_types {
+        match &item.ty {
+            SchemaType::Null => {}
+            SchemaType::Literal(lit) if matches!(lit.value, LiteralValue::String(_)) => {
+                count += 1;
+            }
+            _ => return false,
+        };
+    }
+
+    count > 1
+}
+
+fn to_case(value: &str, to: Case) -> String {
+    sanitize(value)
+        .remove_boundaries(&DIGIT_BOUNDARIES)
+        .to_case(to)
+}
+
+/// Mirrors the casing applied by the `Config` and `ConfigEnum` derives.
+fn format_case(value: &str, from: Case, to: Case) -> String {
+    value
+        .from_case(from)
+        .remove_boundaries(&DIGIT_BOUNDARIES)
+        .to_case(to)
+}
+
+impl RustRenderer {
+    pub fn new(options: RustOptions) -> Self {
+        Self {
+            options,
+            ..Default::default()
+        }
+    }
+
+    fn indent(&self) -> String {
+        if self.options.indent_char.is_empty() {
+            "    ".into()
+        } else {
+            self.options.indent_char.clone()
+        }
+    }
+
+    fn is_excluded(&self, name: &str) -> bool {
+        self.options.exclude_references.iter().any(|r| r == name)
+    }
+
+    fn is_external(&self, name: &str) -> bool {
+        self.options
+            .external_types
+            .values()
+            .any(|externals| externals.iter().any(|e| e == name))
+    }
+
+    /// Return true if the rendered type is a struct or config enum, either
+    /// directly or wrapped in an `Option`, `Vec`, or `HashMap`.
+    fn is_nested(&self, ty: &str) -> bool {
+        let mut ty = ty;
+
+        loop {
+            if let Some(inner) = ty
+                .strip_prefix("Option<")
+                .or_else(|| ty.strip_prefix("Vec<"))
+            {
+                ty = inner.strip_suffix('>').unwrap_or(inner);
+            } else if let Some(inner) = ty.strip_prefix("HashMap<")
+                && let Some((_, value)) = inner.strip_suffix('>').unwrap_or(inner).split_once(", ")
+            {
+                ty = value;
+            } else {
+                break;
+            }
+        }
+
+        self.configs.contains(ty)
+    }
+
+    fn create_type_name(&mut self) -> String {
+        let base = match self.context.last() {
+            Some(name) => name.clone(),
+            None => "Type".into(),
+        };
+        let mut name = base.clone();
+        let mut index = 1;
+
+        while self.names.contains(&name) {
+            index += 1;
+            name = format!("{base}{index}");
+        }
+
+        self.names.insert(name.clone());
+
+        name
+    }
+
+    fn create_field_name(&self, name: &str) -> String {
+        let field = to_case(name, Case::Snake);
+
+        if field.is_empty() || field.starts_with(|c: char| c.is_ascii_digit()) {
+            return format!("field_{field}");
+        }
+
+        if RESERVED_KEYWORDS.contains(&field.as_str()) {
+            return format!("{field}_");
+        }
+
+        if KEYWORDS.contains(&field.as_str()) {
+            return format!("r#{field}");
+        }
+
+        field
+    }
+
+    fn create_variant_name(&self, name: &str) -> String {
+        let variant = to_case(name, Case::Pascal);
+
+        if variant.is_empty() || variant.starts_with(|c: char| c.is_ascii_digit()) {
+            return format!("Variant{variant}");
+        }
+
+        if variant == "Self" {
+            return "Self_".into();
+        }
+
+        variant
+    }
+
+    fn lit_to_string(&self, lit: &LiteralValue) -> String {
+        match lit {
+            LiteralValue::Bool(inner) => inner.to_string(),
+            LiteralValue::F32(inner) => format!("{inner:?}"),
+            LiteralValue::F64(inner) => format!("{inner:?}"),
+            LiteralValue::Int(inner) => inner.to_string(),
+            LiteralValue::UInt(inner) => inner.to_string(),
+            LiteralValue::String(inner) => format!("{inner:?}"),
+        }
+    }
+
+    fn lit_to_type(&self, lit: &LiteralValue) -> String {
+        match lit {
+            LiteralValue::Bool(_) => "bool",
+            LiteralValue::F32(_) => "f32",
+            LiteralValue::F64(_) => "f64",
+            LiteralValue::Int(_) => "i64",
+            LiteralValue::UInt(_) => "u64",
+            LiteralValue::String(_) => "String",
+        }
+        .into()
+    }
+
+    fn render_with_context(&mut self, suffix: &str, schema: &Schema) -> RenderResult {
+        let name = format!(
+            "{}{}",
+            self.context
+                .last()
+                .map(|name| name.as_str())
+                .unwrap_or_default(),
+            suffix
+        );
+
+        self.context.push(name);
+        let out = self.render_schema(schema);
+        self.context.pop();
+
+        out
+    }
+
+    fn wrap_in_comment(
+        &self,
+        indent: &str,
+        comment: Option<&String>,
+        deprecated: Option<&String>,
+        attrs: Vec<String>,
+        value: String,
+    ) -> String {
+        let mut lines = vec![];
+
+        if let Some(comment) = comment {
+            for line in comment.trim().split('\n') {
+                let line = line.trim();
+
+                lines.push(if line.is_empty() {
+                    format!("{indent}///")
+                } else {
+                    format!("{indent}/// {line}")
+                });
+            }
+        }
+
+        if let Some(deprecated) = deprecated {
+            lines.push(if deprecated.is_empty() {
+                format!("{indent}#[deprecated]")
+            } else {
+                format!("{indent}#[deprecated = {deprecated:?}]")
+            });
+        }
+
+        for attr in attrs {
+            lines.push(format!("{indent}{attr}"));
+        }
+
+        lines.push(value);
+        lines.join("\n")
+    }
+
+    fn export_type_alias(&mut self, name: &str, schema: &Schema) -> RenderResult {
+        self.context.push(name.to_owned());
+        let value = self.render_schema_without_reference(schema);
+        self.context.pop();
+
+        Ok(self.wrap_in_comment(
+            "",
+            schema.description.as_ref(),
+            schema.deprecated.as_ref(),
+            vec![],
+            format!("pub type {name} = {};", value?),
+        ))
+    }
+
+    fn export_config_enum(&mut self, name: &str, uni: &UnionType, schema: &Schema) -> RenderResult {
+        self.context.push(name.to_owned());
+        self.imports.insert("Config");
+
+        let indent = self.indent();
+        let default_index = uni.default_index.unwrap_or_default();
+        let mut variant_names = HashSet::new();
+        let mut variants = vec![];
+
+        for (index, item) in uni.variants_types.iter().enumerate() {
+            if item.is_null() {
+                continue;
+            }
+
+            let mut variant = match &item.name {
+                Some(name) if self.is_reference(name) => self.create_variant_name(name),
+                _ => match &item.ty {
+                    SchemaType::Array(_) => "List".into(),
+                    SchemaType::Boolean(_) => "Boolean".into(),
+                    SchemaType::Enum(_) => "Enum".into(),
+                    SchemaType::Float(_) => "Float".into(),
+                    SchemaType::Integer(_) => "Integer".into(),
+                    SchemaType::Literal(_) => "Literal".into(),
+                    SchemaType::Object(_) => "Map".into(),
+                    SchemaType::Reference(name) => self.create_variant_name(name),
+                    SchemaType::String(_) => "String".into(),
+                    SchemaType::Struct(_) => "Object".into(),
+                    SchemaType::Tuple(_) => "Tuple".into(),
+                    SchemaType::Union(_) => "Union".into(),
+                    _ => "Unknown".into(),
+                },
+            };
+
+            if variant_names.contains(&variant) {
+                variant = format!("{variant}{}", index + 1);
+            }
+
+            variant_names.insert(variant.clone());
+
+            let mut value = self.render_with_context(&variant, item)?;
+
+            // Variants can't nest a config type within a collection
+            if self.is_nested(&value) && !self.configs.contains(&value) {
+                value = "serde_json::Value".into();
+            }
+            let mut args = vec![];
+
+            if index == default_index {
+                args.push("default");
+            }
+
+            // Nested variants must be a config type itself
+            if self.configs.contains(&value) {
+                args.push("nested");
+            }
+
+            let mut attrs = vec![];
+
+            if !args.is_empty() {
+                attrs.push(format!("#[setting({})]", args.join(", ")));
+            }
+
+            variants.push(self.wrap_in_comment(
+                &indent,
+                item.description.as_ref(),
+                item.deprecated.as_ref(),
+                attrs,
+                format!("{indent}{variant}({value}),"),
+            ));
+        }
+
+        self.context.pop();
+
+        Ok(self.wrap_in_comment(
+            "",
+            schema.description.as_ref(),
+            schema.deprecated.as_ref(),
+            vec![
+                "#[derive(Clone, Config, Debug, PartialEq)]".into(),
+                "#[config(serde(untagged))]".into(),
+            ],
+            format!("pub enum {name} {{\n{}\n}}", variants.join("\n")),
+        ))
+    }
+
+    fn export_enum(&mut self, name: &str, enu: &EnumType, schema: &Schema) -> RenderResult {
+        // Only string values are supported by `ConfigEnum`
+        if !enu
+            .values
+            .iter()
+            .all(|value| matches!(value, LiteralValue::String(_)))
+        {
+            return self.export_type_alias(name, schema);
+        }
+
+        self.imports.insert("ConfigEnum");
+        self.imports.insert("derive_enum");
+
+        let indent = self.indent();
+        let default_index = enu.default_index.unwrap_or_default();
+        let mut variants = vec![];
+
+        let mut create_variant = |variant: String, value: Option<&str>, field: &SchemaField| {
+            let mut attrs = vec![];
+
+            if variants.len() == default_index {
+                attrs.push("#[default]".into());
+            }
+
+            let row = match value {
+                Some(value) => {
+                    if format_case(&variant, Case::Pascal, Case::Kebab) != value {
+                        attrs.push(format!("#[variant(value = {value:?})]"));
+                    }
+
+                    format!("{indent}{indent}{variant},")
+                }
+                None => {
+                    attrs.push("#[variant(fallback)]".into());
+
+                    format!("{indent}{indent}{variant}(String),")
+                }
+            };
+
+            variants.push(self.wrap_in_comment(
+                &indent.repeat(2),
+                field.comment.as_ref(),
+                field.deprecated.as_ref(),
+                attrs,
+                row,
+            ));
+        };
+
+        match &enu.variants {
+            Some(items) => {
+                for (variant, field) in items {
+                    let value = match &field.schema.ty {
+                        SchemaType::Literal(lit) => match &lit.value {
+                            LiteralValue::String(value) => Some(value.as_str()),
+                            _ => continue,
+                        },
+                        _ => None,
+                    };
+
+                    create_variant(self.create_variant_name(variant), value, field);
+                }
+            }
+            None => {
+                for value in &enu.values {
+                    if let LiteralValue::String(value) = value {
+                        create_variant(
+                            self.create_variant_name(value),
+                            Some(value),
+                            &SchemaField::default(),
+                        );
+                    }
+                }
+            }
+        };
+
+        let output = self.wrap_in_comment(
+            &indent,
+            schema.description.as_ref(),
+            schema.deprecated.as_ref(),
+            vec!["#[derive(ConfigEnum, Default)]".into()],
+            format!(
+                "{indent}pub enum {name} {{\n{}\n{indent}}}",
+                variants.join("\n")
+            ),
+        );
+
+        Ok(format!("derive_enum!(\n{output}\n);"))
+    }
+
+    fn export_struct(
+        &mut self,
+        name: &str,
+        structure: &StructType,
+        schema: &Schema,
+    ) -> RenderResult {
+        self.context.push(name.to_owned());
+        self.imports.insert("Config");
+
+        let indent = self.indent();
+        let mut fields = vec![];
+
+        for (field_name, field) in &structure.fields {
+            let rust_name = self.create_field_name(field_name);
+            let value =
+                self.render_with_context(&to_case(field_name, Case::Pascal), &field.schema)?;
+            let mut setting_args = vec![];
+
+            // Only a single alias is supported by the derive
+            if let Some(alias) = field.aliases.first() {
+                setting_args.push(format!("alias = {alias:?}"));
+            }
+
+            if let Some(env_var) = &field.env_var {
+                setting_args.push(format!("env = {env_var:?}"));
+            }
+
+            if field.flatten {
+                setting_args.push("flatten".into());
+            }
+
+            // Referenced types define their own default
+            if self.is_nested(&value) {
+                setting_args.push("nested".into());
+            } else if !field
+                .schema
+                .get_nonnull_schema()
+                .and_then(|schema| schema.name.as_ref())
+                .is_some_and(|name| self.is_reference(name))
+                && let Some(default) = field.schema.get_default()
+            {
+                setting_args.push(format!("default = {}", self.lit_to_string(default)));
+            }
+
+            // Passed through to serde
+            if rust_name.starts_with("r#")
+                || format_case(&rust_name, Case::Snake, Case::Camel) != *field_name
+            {
+                setting_args.push(format!("rename = {field_name:?}"));
+            }
+
+            if field.secret {
+                setting_args.push("secret".into());
+            }
+
+            if field.hidden {
+                setting_args.push("skip".into());
+            }
+
+            let mut attrs = vec![];
+
+            if !setting_args.is_empty() {
+                attrs.push(format!("#[setting({})]", setting_args.join(", ")));
+            }
+
+            fields.push(self.wrap_in_comment(
+                &indent,
+                field.comment.as_ref(),
+                field.deprecated.as_ref(),
+                attrs,
+                format!("{indent}pub {rust_name}: {value},"),
+            ));
+        }
+
+        self.context.pop();
+
+        // `Config` implements a redacting `Debug` when secrets are present
+        let derive = if structure.fields.values().any(|field| field.secret) {
+            "#[derive(Clone, Config, PartialEq)]"
+        } else {
+            "#[derive(Clone, Config, Debug, PartialEq)]"
+        };
+
+        let body = if fields.is_empty() {
+            "{}".to_owned()
+        } else {
+            format!("{{\n{}\n}}", fields.join("\n"))
+        };
+
+        Ok(self.wrap_in_comment(
+            "",
+            schema.description.as_ref(),
+            schema.deprecated.as_ref(),
+            vec![derive.into()],
+            format!("pub struct {name} {body}"),
+        ))
+    }
+
+    fn export_union(&mut self, name: &str, uni: &UnionType, schema: &Schema) -> RenderResult {
+        // Unions of strings can be represented as a unit-only enum
+        if is_string_union(uni) {
+            let mut default_index = None;
+            let mut values = vec![];
+
+            for (index, item) in uni.variants_types.iter().enumerate() {
+                if let SchemaType::Literal(lit) = &item.ty {
+                    if uni.default_index == Some(index) {
+                        default_index = Some(values.len());
+                    }
+
+                    values.push(lit.value.clone());
+                }
+            }
+
+            return self.export_enum(
+                name,
+                &EnumType {
+                    default_index,
+                    values,
+                    variants: None,
+                },
+                schema,
+            );
+        }
+
+        if uni.has_null() || uni.variants_types.len() <= 1 {
+            return self.export_type_alias(name, schema);
+        }
+
+        self.export_config_enum(name, uni, schema)
+    }
+
+    fn hoist(&mut self, schema: &Schema) -> RenderResult {
+        let name = self.create_type_name();
+        let output = self.export_schema(&name, schema)?;
+
+        self.hoisted.push(output);
+
+        Ok(name)
+    }
+
+    fn export_schema(&mut self, name: &str, schema: &Schema) -> RenderResult {
+        match &schema.ty {
+            SchemaType::Enum(inner) => self.export_enum(name, inner, schema),
+            SchemaType::Struct(inner) => self.export_struct(name, inner, schema),
+            SchemaType::Union(inner) => self.export_union(name, inner, schema),
+            _ => self.export_type_alias(name, schema),
+        }
+    }
+}
+
+impl SchemaRenderer<String> for RustRenderer {
+    fn is_reference(&self, name: &str) -> bool {
+        self.references.contains(name) || self.is_external(name)
+    }
+
+    fn render_array(&mut self, array: &ArrayType, _schema: &Schema) -> RenderResult {
+        Ok(format!(
+            "Vec<{}>",
+            self.render_with_context("Item", &array.items_type)?
+        ))
+    }
+
+    fn render_boolean(&mut self, _boolean: &BooleanType, _schema: &Schema) -> RenderResult {
+        Ok("bool".into())
+    }
+
+    fn render_enum(&mut self, enu: &EnumType, schema: &Schema) -> RenderResult {
+        if let Some(value) = enu.values.first()
+            && !matches!(value, LiteralValue::String(_))
+        {
+            return Ok(self.lit_to_type(value));
+        }
+
+        self.hoist(schema)
+    }
+
+    fn render_float(&mut self, float: &FloatType, _schema: &Schema) -> RenderResult {
+        Ok(match float.kind {
+            FloatKind::F32 => "f32",
+            FloatKind::F64 => "f64",
+        }
+        .into())
+    }
+
+    fn render_integer(&mut self, integer: &IntegerType, _schema: &Schema) -> RenderResult {
+        Ok(match integer.kind {
+            IntegerKind::Isize => "isize",
+            IntegerKind::I8 => "i8",
+            IntegerKind::I16 => "i16",
+            IntegerKind::I32 => "i32",
+            IntegerKind::I64 => "i64",
+            IntegerKind::I128 => "i128",
+            IntegerKind::Usize => "usize",
+            IntegerKind::U8 => "u8",
+            IntegerKind::U16 => "u16",
+            IntegerKind::U32 => "u32",
+            IntegerKind::U64 => "u64",
+            IntegerKind::U128 => "u128",
+        }
+        .into())
+    }
+
+    fn render_literal(&mut self, literal: &LiteralType, _schema: &Schema) -> RenderResult {
+        Ok(self.lit_to_type(&literal.value))
+    }
+
+    fn render_null(&mut self, _schema: &Schema) -> RenderResult {
+        Ok("()".into())
+    }
+
+    fn render_object(&mut self, object: &ObjectType, _schema: &Schema) -> RenderResult {
+        Ok(format!(
+            "HashMap<{}, {}>",
+            self.render_with_context("Key", &object.key_type)?,
+            self.render_with_context("Value", &object.value_type)?
+        ))
+    }
+
+    fn render_reference(&mut self, reference: &str, _schema: &Schema) -> RenderResult {
+        Ok(reference.into())
+    }
+
+    fn render_string(&mut self, _string: &StringType, _schema: &Schema) -> RenderResult {
+        Ok("String".into())
+    }
+
+    fn render_struct(&mut self, _structure: &StructType, schema: &Schema) -> RenderResult {
+        let name = self.hoist(schema)?;
+
+        self.configs.insert(name.clone());
+
+        Ok(name)
+    }
+
+    fn render_tuple(&mut self, tuple: &TupleType, _schema: &Schema) -> RenderResult {
+        let mut items = vec![];
+
+        for (index, item) in tuple.items_types.iter().enumerate() {
+            items.push(self.render_with_context(&format!("Item{index}"), item)?);
+        }
+
+        Ok(if items.len() == 1 {
+            format!("({},)", items[0])
+        } else {
+            format!("({})", items.join(", "))
+        })
+    }
+
+    fn render_union(&mut self, uni: &UnionType, schema: &Schema) -> RenderResult {
+        let mut default_index = None;
+        let mut variants_types = vec![];
+
+        for (index, item) in uni.variants_types.iter().enumerate() {
+            if !item.is_null() {
+                if uni.default_index == Some(index) {
+                    default_index = Some(variants_types.len());
+                }
+
+                variants_types.push(item.clone());
+            }
+        }
+
+        let value = match variants_types.len() {
+            0 => "serde_json::Value".into(),
+            1 => self.render_schema(&variants_types[0])?,
+            _ => {
+                let inner = UnionType {
+                    default_index,
+                    variants_types,
+                    ..uni.clone()
+                };
+                let is_config = !is_string_union(&inner);
+                let name = self.hoist(&Schema {
+                    ty: SchemaType::Union(Box::new(inner)),
+                    ..schema.clone()
+                })?;
+
+                if is_config {
+                    self.configs.insert(name.clone());
+                }
+
+                name
+            }
+        };
+
+        Ok(if uni.has_null() {
+            format!("Option<{value}>")
+        } else {
+            value
+        })
+    }
+
+    fn render_unknown(&mut self, _schema: &Schema) -> RenderResult {
+        Ok("serde_json::Value".into())
+    }
+
+    fn render(&mut self, schemas: IndexMap<String, Schema>) -> RenderResult {
+        self.references = HashSet::from_iter(schemas.keys().cloned());
+        self.names = self.references.clone();
+
+        for (name, schema) in &schemas {
+            match &schema.ty {
+                SchemaType::Struct(_) => {
+                    self.configs.insert(name.clone());
+                }
+                SchemaType::Union(uni)
+                    if !uni.has_null() && uni.variants_types.len() > 1 && !is_string_union(uni) =>
+                {
+                    self.configs.insert(name.clone());
+                }
+                _ => {}
+            };
+        }
+
+        let mut outputs = vec![];
+
+        for (name, schema) in &schemas {
+            if self.is_excluded(name) {
+                continue;
+            }
+
+            let output = self.export_schema(name, schema)?;
+
+            outputs.append(&mut self.hoisted);
+            outputs.push(output);
+        }
+
+        let mut imports = vec![];
+
+        if !self.imports.is_empty() {
+            imports.push(format!(
+                "use schematic::{{{}}};",
+                Vec::from_iter(self.imports.iter().copied()).join(", ")
+            ));
+        }
+
+        // Rendered types may be replaced, so check the final output
+        if outputs.iter().any(|output| output.contains("HashMap<")) {
+            imports.push("use std::collections::HashMap;".into());
+        }
+
+        for (import, types) in BTreeMap::from_iter(&self.options.external_types) {
+            let mut imported_types = types.to_vec();
+            imported_types.sort();
+
+            imports.push(if imported_types.len() == 1 {
+                format!("use {import}::{};", imported_types[0])
+            } else {
+                format!("use {import}::{{{}}};", imported_types.join(", "))
+            });
+        }
+
+        let mut header = vec!["// Automatically generated by schematic. DO NOT MODIFY!".to_owned()];
+
+        if !imports.is_empty() {
+            header.push(imports.join("\n"));
+        }
+
+        header.extend(outputs);
+
+        Ok(header.join("\n\n"))
+    }
+}
@@ -87,6 +87,14 @@ impl TypeScriptRenderer {
         }
     }
 
+    /// Resolve the provided references when rendering individual schemas,
+    /// instead of the schemas passed to [`SchemaRenderer::render`].
+    #[cfg(feature = "renderer_zod")]
+    pub(crate) fn with_references(mut self, references: HashSet<String>) -> Self {
+        self.references = references;
+        self
+    }
+
     fn indent(&self) -> String {
         let chars = if self.options.indent_char.is_empty() {
             "\t"
@@ -506,6 +514,10 @@ impl SchemaRenderer<String> for TypeScriptRenderer {
                 tags.push(format!("@env {env_var}"));
             }
 
+            if field.secret {
+                tags.push("@secret".into());
+            }
+
             if let SchemaType::Enum(inner) = &field.schema.ty {
                 tags.push(format!(
                     "@type {{{}}}",
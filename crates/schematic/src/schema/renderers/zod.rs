@@ -0,0 +1,780 @@
+use super::typescript::{TypeScriptOptions, TypeScriptRenderer};
+use crate::schema::{RenderResult, SchemaRenderer};
+use indexmap::IndexMap;
+use schematic_types::*;
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+/// Options to control the rendered Zod output.
+#[derive(Default)]
+pub struct ZodOptions {
+    /// List of references to exclude from exporting as a schema.
+    pub exclude_references: Vec<String>,
+
+    /// Map of relative import file paths to a list of types to import.
+    /// Will be rendered as an `import { type Name, NameSchema } from 'path';`
+    /// declaration, for both the type and its schema.
+    pub external_types: HashMap<String, Vec<String>>,
+
+    /// Character(s) to use for indentation.
+    pub indent_char: String,
+}
+
+/// Renders [Zod](https://zod.dev) (v4) schemas, and their inferred
+/// TypeScript types, from a schema.
+#[derive(Default)]
+pub struct ZodRenderer {
+    defined: HashSet<String>,
+    depth: usize,
+    options: ZodOptions,
+    recursive: bool,
+    references: HashSet<String>,
+    schemas: IndexMap<String, Schema>,
+}
+
+fn is_identifier(value: &str) -> bool {
+    value
+        .chars()
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_' || c == '$')
+        && value
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '$')
+}
+
+fn to_js_string(value: &str) -> String {
+    let value = value
+        .replace('\\', "\\\\")
+        .replace('\'', "\\'")
+        .replace('\n', "\\n");
+
+    format!("'{value}'")
+}
+
+fn lit_to_string(lit: &LiteralValue) -> String {
+    match lit {
+        LiteralValue::Bool(inner) => inner.to_string(),
+        LiteralValue::F32(inner) => inner.to_string(),
+        LiteralValue::F64(inner) => inner.to_string(),
+        LiteralValue::Int(inner) => inner.to_string(),
+        LiteralValue::UInt(inner) => inner.to_string(),
+        LiteralValue::String(inner) => to_js_string(inner),
+    }
+}
+
+fn collect_references(schema: &Schema, references: &HashSet<String>, deps: &mut Vec<String>) {
+    if let Some(name) = &schema.name
+        && references.contains(name)
+    {
+        deps.push(name.to_owned());
+        return;
+    }
+
+    match &schema.ty {
+        SchemaType::Array(inner) => collect_references(&inner.items_type, references, deps),
+        SchemaType::Object(inner) => {
+            collect_references(&inner.key_type, references, deps);
+            collect_references(&inner.value_type, references, deps);
+        }
+        SchemaType::Reference(name) => deps.push(name.to_owned()),
+        SchemaType::Struct(inner) => {
+            for field in inner.fields.values() {
+                collect_references(&field.schema, references, deps);
+            }
+        }
+        SchemaType::Tuple(inner) => {
+            for item in &inner.items_types {
+                collect_references(item, references, deps);
+            }
+        }
+        SchemaType::Union(inner) => {
+            for item in &inner.variants_types {
+                collect_references(item, references, deps);
+            }
+        }
+        _ => {}
+    };
+}
+
+impl ZodRenderer {
+    pub fn new(options: ZodOptions) -> Self {
+        Self {
+            options,
+            ..Default::default()
+        }
+    }
+
+    fn indent(&self) -> String {
+        let chars = if self.options.indent_char.is_empty() {
+            "\t"
+        } else {
+            &self.options.indent_char
+        };
+
+        chars.repeat(self.depth)
+    }
+
+    fn is_excluded(&self, name: &str) -> bool {
+        self.options.exclude_references.iter().any(|r| r == name)
+    }
+
+    fn is_external(&self, name: &str) -> bool {
+        self.options
+            .external_types
+            .values()
+            .any(|externals| externals.iter().any(|e| e == name))
+    }
+
+    /// Return true if the referenced schema can be accessed directly,
+    /// instead of lazily.
+    fn is_defined(&self, name: &str) -> bool {
+        self.defined.contains(name) || self.is_external(name)
+    }
+
+    fn wrap_in_comment(
+        &self,
+        comment: Option<&String>,
+        tags: Vec<String>,
+        value: String,
+    ) -> String {
+        let indent = self.indent();
+        let mut lines = vec![];
+
+        if let Some(comment) = comment {
+            lines.extend(comment.trim().split('\n').map(|c| c.trim().to_owned()));
+        }
+
+        if !tags.is_empty() {
+            if !lines.is_empty() {
+                lines.push("".to_owned());
+            }
+
+            lines.extend(tags);
+        }
+
+        if lines.is_empty() {
+            return value;
+        }
+
+        if lines.len() == 1 {
+            return format!("{indent}/** {} */\n{value}", lines[0]);
+        }
+
+        let mut out = vec![format!("{indent}/**")];
+
+        for line in lines {
+            if line.is_empty() {
+                out.push(format!("{indent} *"));
+            } else {
+                out.push(format!("{indent} * {line}"));
+            }
+        }
+
+        out.push(format!("{indent} */"));
+
+        format!("{}\n{value}", out.join("\n"))
+    }
+
+    fn render_deprecated(&self, deprecated: Option<&String>) -> Vec<String> {
+        match deprecated {
+            Some(deprecated) if deprecated.is_empty() => vec!["@deprecated".into()],
+            Some(deprecated) => vec![format!("@deprecated {deprecated}")],
+            None => vec![],
+        }
+    }
+
+    /// Render a list of items, splitting them across multiple lines if any
+    /// of them span multiple lines.
+    fn render_list(&mut self, items: &[Box<Schema>]) -> RenderResult {
+        self.depth += 1;
+
+        let mut values = vec![];
+
+        for item in items {
+            values.push(self.render_schema(item)?);
+        }
+
+        let indent = self.indent();
+
+        self.depth -= 1;
+
+        Ok(if values.iter().any(|value| value.contains('\n')) {
+            format!(
+                "[\n{}\n{}]",
+                values
+                    .iter()
+                    .map(|value| format!("{indent}{value},"))
+                    .collect::<Vec<_>>()
+                    .join("\n"),
+                self.indent()
+            )
+        } else {
+            format!("[{}]", values.join(", "))
+        })
+    }
+
+    fn render_string_enum(&self, values: &[&String]) -> String {
+        format!(
+            "z.enum([{}])",
+            values
+                .iter()
+                .map(|value| to_js_string(value))
+                .collect::<Vec<_>>()
+                .join(", ")
+        )
+    }
+
+    fn render_literals(&self, values: &[LiteralValue]) -> String {
+        let items = values
+            .iter()
+            .map(|value| format!("z.literal({})", lit_to_string(value)))
+            .collect::<Vec<_>>();
+
+        if items.len() == 1 {
+            items[0].clone()
+        } else {
+            format!("z.union([{}])", items.join(", "))
+        }
+    }
+
+    fn render_number_constraints<T: ToString>(
+        &self,
+        out: &mut String,
+        min: Option<&T>,
+        max: Option<&T>,
+        min_exclusive: Option<&T>,
+        max_exclusive: Option<&T>,
+        multiple_of: Option<&T>,
+    ) {
+        if let Some(min) = min {
+            out.push_str(&format!(".min({})", min.to_string()));
+        }
+
+        if let Some(min) = min_exclusive {
+            out.push_str(&format!(".gt({})", min.to_string()));
+        }
+
+        if let Some(max) = max {
+            out.push_str(&format!(".max({})", max.to_string()));
+        }
+
+        if let Some(max) = max_exclusive {
+            out.push_str(&format!(".lt({})", max.to_string()));
+        }
+
+        if let Some(multiple) = multiple_of {
+            out.push_str(&format!(".multipleOf({})", multiple.to_string()));
+        }
+    }
+
+    fn resolve_struct<'a>(&'a self, schema: &'a Schema) -> Option<&'a StructType> {
+        let schema = match schema.name.as_ref().or(match &schema.ty {
+            SchemaType::Reference(name) => Some(name),
+            _ => None,
+        }) {
+            // Lazy schemas can't be discriminated
+            Some(name) if self.references.contains(name) => {
+                if !self.defined.contains(name) {
+                    return None;
+                }
+
+                self.schemas.get(name)?
+            }
+            _ => schema,
+        };
+
+        match &schema.ty {
+            SchemaType::Struct(inner) => Some(inner),
+            _ => None,
+        }
+    }
+
+    /// Find a required field with a distinct string literal in every
+    /// variant, that can be used to discriminate the union.
+    fn find_discriminator(&self, items: &[Box<Schema>]) -> Option<String> {
+        let structs = items
+            .iter()
+            .map(|item| self.resolve_struct(item))
+            .collect::<Option<Vec<_>>>()?;
+
+        let get_tag = |structure: &StructType, key: &str| {
+            structure
+                .fields
+                .get(key)
+                .filter(|field| !field.optional && !field.nullable)
+                .and_then(|field| match &field.schema.ty {
+                    SchemaType::Literal(lit) => match &lit.value {
+                        LiteralValue::String(value) => Some(value.to_owned()),
+                        _ => None,
+                    },
+                    _ => None,
+                })
+        };
+
+        structs.first()?.fields.keys().find_map(|key| {
+            let mut values = HashSet::new();
+
+            for structure in &structs {
+                if !values.insert(get_tag(structure, key)?) {
+                    return None;
+                }
+            }
+
+            Some(key.to_owned())
+        })
+    }
+
+    fn render_field(&mut self, field: &SchemaField) -> RenderResult {
+        let mut value = self.render_schema(&field.schema)?;
+
+        if let Some(default) = field.schema.get_default() {
+            value.push_str(&format!(".default({})", lit_to_string(default)));
+        } else if field.optional {
+            value.push_str(".optional()");
+        }
+
+        if let Some(comment) = &field.comment {
+            value.push_str(&format!(".describe({})", to_js_string(comment.trim())));
+        }
+
+        Ok(value)
+    }
+
+    fn export_schema(&mut self, name: &str, schema: &Schema) -> RenderResult {
+        self.recursive = false;
+
+        let mut value = self.render_schema_without_reference(schema)?;
+
+        if let Some(description) = &schema.description {
+            value.push_str(&format!(".describe({})", to_js_string(description.trim())));
+        }
+
+        let tags = self.render_deprecated(schema.deprecated.as_ref());
+        let mut out = vec![];
+
+        // Recursive schemas can't be inferred, so the type must be declared
+        // up front, and the schema annotated with it
+        if self.recursive {
+            let mut types = TypeScriptRenderer::new(TypeScriptOptions {
+                exclude_aliases: true,
+                indent_char: self.options.indent_char.clone(),
+                ..TypeScriptOptions::default()
+            })
+            .with_references(self.references.clone());
+
+            let ty = types.render_schema_without_reference(schema)?;
+
+            out.push(self.wrap_in_comment(
+                schema.description.as_ref(),
+                tags.clone(),
+                if matches!(schema.ty, SchemaType::Struct(_)) {
+                    format!("export interface {name} {ty}")
+                } else {
+                    format!("export type {name} = {ty};")
+                },
+            ));
+            out.push(self.wrap_in_comment(
+                None,
+                tags,
+                format!("export const {name}Schema: z.ZodType<{name}> = {value};"),
+            ));
+        } else {
+            out.push(self.wrap_in_comment(
+                None,
+                tags.clone(),
+                format!("export const {name}Schema = {value};"),
+            ));
+            out.push(self.wrap_in_comment(
+                schema.description.as_ref(),
+                tags,
+                format!("export type {name} = z.infer<typeof {name}Schema>;"),
+            ));
+        }
+
+        Ok(out.join("\n\n"))
+    }
+
+    /// Sort the schemas so that references are defined before they're used.
+    fn sort_schemas(&self, schemas: &IndexMap<String, Schema>) -> Vec<String> {
+        fn visit(
+            name: &str,
+            schemas: &IndexMap<String, Schema>,
+            references: &HashSet<String>,
+            visited: &mut HashSet<String>,
+            sorted: &mut Vec<String>,
+        ) {
+            if !visited.insert(name.to_owned()) {
+                return;
+            }
+
+            if let Some(schema) = schemas.get(name) {
+                let mut deps = vec![];
+
+                collect_references(
+                    &Schema {
+                        name: None,
+                        ..schema.to_owned()
+                    },
+                    references,
+                    &mut deps,
+                );
+
+                for dep in deps {
+                    visit(&dep, schemas, references, visited, sorted);
+                }
+
+                sorted.push(name.to_owned());
+            }
+        }
+
+        let mut visited = HashSet::new();
+        let mut sorted = vec![];
+
+        for name in schemas.keys() {
+            visit(name, schemas, &self.references, &mut visited, &mut sorted);
+        }
+
+        sorted
+    }
+}
+
+impl SchemaRenderer<String> for ZodRenderer {
+    fn is_reference(&self, name: &str) -> bool {
+        self.references.contains(name) || self.is_external(name)
+    }
+
+    fn render_array(&mut self, array: &ArrayType, _schema: &Schema) -> RenderResult {
+        let mut out = format!("z.array({})", self.render_schema(&array.items_type)?);
+
+        if let Some(min) = array.min_length {
+            out.push_str(&format!(".min({min})"));
+        }
+
+        if let Some(max) = array.max_length {
+            out.push_str(&format!(".max({max})"));
+        }
+
+        if array.unique.is_some_and(|unique| unique) {
+            out.push_str(
+                ".refine((items) => new Set(items).size === items.length, { message: 'Items must be unique' })",
+            );
+        }
+
+        Ok(out)
+    }
+
+    fn render_boolean(&mut self, _boolean: &BooleanType, _schema: &Schema) -> RenderResult {
+        Ok("z.boolean()".into())
+    }
+
+    fn render_enum(&mut self, enu: &EnumType, _schema: &Schema) -> RenderResult {
+        // Map using variants instead of values (when available),
+        // so that the fallback variant is included
+        let variants = match &enu.variants {
+            Some(variants) => variants
+                .values()
+                .filter(|variant| !variant.hidden)
+                .map(|variant| &variant.schema)
+                .collect::<Vec<_>>(),
+            None => vec![],
+        };
+
+        let mut values = vec![];
+        let mut fallback = false;
+
+        if variants.is_empty() {
+            values.extend(enu.values.iter().cloned());
+        } else {
+            for variant in variants {
+                match &variant.ty {
+                    SchemaType::Literal(lit) => values.push(lit.value.clone()),
+                    _ => fallback = true,
+                };
+            }
+        }
+
+        let strings = values
+            .iter()
+            .filter_map(|value| match value {
+                LiteralValue::String(inner) => Some(inner),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+
+        let out = if !strings.is_empty() && strings.len() == values.len() {
+            self.render_string_enum(&strings)
+        } else {
+            self.render_literals(&values)
+        };
+
+        Ok(if fallback {
+            format!("z.union([{out}, z.string()])")
+        } else {
+            out
+        })
+    }
+
+    fn render_float(&mut self, float: &FloatType, _schema: &Schema) -> RenderResult {
+        if let Some(values) = &float.enum_values {
+            return Ok(self.render_literals(
+                &values
+                    .iter()
+                    .map(|value| LiteralValue::F64(*value))
+                    .collect::<Vec<_>>(),
+            ));
+        }
+
+        let mut out = "z.number()".to_owned();
+
+        self.render_number_constraints(
+            &mut out,
+            float.min.as_ref(),
+            float.max.as_ref(),
+            float.min_exclusive.as_ref(),
+            float.max_exclusive.as_ref(),
+            float.multiple_of.as_ref(),
+        );
+
+        Ok(out)
+    }
+
+    fn render_integer(&mut self, integer: &IntegerType, _schema: &Schema) -> RenderResult {
+        if let Some(values) = &integer.enum_values {
+            return Ok(self.render_literals(
+                &values
+                    .iter()
+                    .map(|value| LiteralValue::Int(*value))
+                    .collect::<Vec<_>>(),
+            ));
+        }
+
+        let mut out = "z.number().int()".to_owned();
+
+        if integer.kind.is_unsigned() && integer.min.is_none() && integer.min_exclusive.is_none() {
+            out.push_str(".nonnegative()");
+        }
+
+        self.render_number_constraints(
+            &mut out,
+            integer.min.as_ref(),
+            integer.max.as_ref(),
+            integer.min_exclusive.as_ref(),
+            integer.max_exclusive.as_ref(),
+            integer.multiple_of.as_ref(),
+        );
+
+        Ok(out)
+    }
+
+    fn render_literal(&mut self, literal: &LiteralType, _schema: &Schema) -> RenderResult {
+        Ok(format!("z.literal({})", lit_to_string(&literal.value)))
+    }
+
+    fn render_null(&mut self, _schema: &Schema) -> RenderResult {
+        Ok("z.null()".into())
+    }
+
+    fn render_object(&mut self, object: &ObjectType, _schema: &Schema) -> RenderResult {
+        // Keys are always strings when serialized
+        let key = match &object.key_type.ty {
+            SchemaType::Float(_) | SchemaType::Integer(_) => "z.string()".into(),
+            _ => self.render_schema(&object.key_type)?,
+        };
+
+        Ok(format!(
+            "z.record({key}, {})",
+            self.render_schema(&object.value_type)?
+        ))
+    }
+
+    fn render_reference(&mut self, reference: &str, _schema: &Schema) -> RenderResult {
+        if self.is_defined(reference) {
+            return Ok(format!("{reference}Schema"));
+        }
+
+        self.recursive = true;
+
+        Ok(format!("z.lazy(() => {reference}Schema)"))
+    }
+
+    fn render_string(&mut self, string: &StringType, _schema: &Schema) -> RenderResult {
+        if let Some(values) = &string.enum_values {
+            return Ok(self.render_string_enum(&values.iter().collect::<Vec<_>>()));
+        }
+
+        let mut out = "z.string()".to_owned();
+
+        match string.format.as_deref() {
+            Some("email") => out.push_str(".email()"),
+            Some("uri" | "url") => out.push_str(".url()"),
+            Some("uuid") => out.push_str(".uuid()"),
+            _ => {}
+        };
+
+        if let Some(min) = string.min_length {
+            out.push_str(&format!(".min({min})"));
+        }
+
+        if let Some(max) = string.max_length {
+            out.push_str(&format!(".max({max})"));
+        }
+
+        if let Some(pattern) = &string.pattern {
+            out.push_str(&format!(".regex(/{}/)", pattern.replace('/', "\\/")));
+        }
+
+        Ok(out)
+    }
+
+    fn render_struct(&mut self, structure: &StructType, _schema: &Schema) -> RenderResult {
+        self.depth += 1;
+
+        let indent = self.indent();
+        let mut out = vec![];
+        let mut flattened = vec![];
+
+        for (name, field) in &structure.fields {
+            if field.hidden {
+                continue;
+            }
+
+            // Merged into the object after its fields
+            if field.flatten {
+                flattened.push(field);
+                continue;
+            }
+
+            let key = if is_identifier(name) {
+                name.to_owned()
+            } else {
+                to_js_string(name)
+            };
+            let value = self.render_field(field)?;
+
+            out.push(self.wrap_in_comment(
+                None,
+                self.render_deprecated(field.deprecated.as_ref()),
+                format!("{indent}{key}: {value},"),
+            ));
+        }
+
+        self.depth -= 1;
+
+        let mut value = if out.is_empty() {
+            "z.object({})".to_owned()
+        } else {
+            format!("z.object({{\n{}\n{}}})", out.join("\n"), self.indent())
+        };
+
+        for field in flattened {
+            match field.schema.get_nonnull_schema().map(|schema| &schema.ty) {
+                Some(SchemaType::Object(object)) => {
+                    value.push_str(&format!(
+                        ".catchall({})",
+                        self.render_schema(&object.value_type)?
+                    ));
+                }
+                _ => {
+                    value.push_str(&format!(".and({})", self.render_schema(&field.schema)?));
+                }
+            };
+        }
+
+        Ok(value)
+    }
+
+    fn render_tuple(&mut self, tuple: &TupleType, _schema: &Schema) -> RenderResult {
+        Ok(format!(
+            "z.tuple({})",
+            self.render_list(&tuple.items_types)?
+        ))
+    }
+
+    fn render_union(&mut self, uni: &UnionType, _schema: &Schema) -> RenderResult {
+        let items = uni
+            .variants_types
+            .iter()
+            .filter(|item| !item.is_null())
+            .cloned()
+            .collect::<Vec<_>>();
+
+        let strings = items
+            .iter()
+            .filter_map(|item| match &item.ty {
+                SchemaType::Literal(lit) => match &lit.value {
+                    LiteralValue::String(value) => Some(value),
+                    _ => None,
+                },
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+
+        let out = match items.len() {
+            0 => return Ok("z.null()".into()),
+            1 => self.render_schema(&items[0])?,
+            // Unions of strings can be represented as an enum
+            len if strings.len() == len => self.render_string_enum(&strings),
+            _ => match self.find_discriminator(&items) {
+                Some(key) => format!(
+                    "z.discriminatedUnion({}, {})",
+                    to_js_string(&key),
+                    self.render_list(&items)?
+                ),
+                None => format!("z.union({})", self.render_list(&items)?),
+            },
+        };
+
+        Ok(if uni.has_null() {
+            format!("{out}.nullable()")
+        } else {
+            out
+        })
+    }
+
+    fn render_unknown(&mut self, _schema: &Schema) -> RenderResult {
+        Ok("z.unknown()".into())
+    }
+
+    fn render(&mut self, schemas: IndexMap<String, Schema>) -> RenderResult {
+        self.references = HashSet::from_iter(schemas.keys().cloned());
+
+        let mut outputs = vec![
+            "// Automatically generated by schematic. DO NOT MODIFY!".to_string(),
+            "/* eslint-disable */".to_string(),
+        ];
+
+        let mut imports = vec!["import { z } from 'zod';".to_owned()];
+
+        for (import, types) in BTreeMap::from_iter(&self.options.external_types) {
+            let mut imported_types = types.to_vec();
+            imported_types.sort();
+
+            imports.push(format!(
+                "import {{ {} }} from '{import}';",
+                imported_types
+                    .iter()
+                    .map(|name| format!("type {name}, {name}Schema"))
+                    .collect::<Vec<_>>()
+                    .join(", "),
+            ));
+        }
+
+        outputs.push(imports.join("\n"));
+
+        // Retained for resolving the variants of discriminated unions
+        self.schemas = schemas;
+
+        for name in self.sort_schemas(&self.schemas) {
+            if self.is_excluded(&name) {
+                continue;
+            }
+
+            let schema = self.schemas[&name].clone();
+
+            outputs.push(self.export_schema(&name, &schema)?);
+
+            self.defined.insert(name);
+        }
+
+        Ok(outputs.join("\n\n"))
+    }
+}
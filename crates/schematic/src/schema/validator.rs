@@ -0,0 +1,668 @@
+use super::SchemaGenerator;
+use crate::config::{Path, ValidateError};
+use garde::rules::pattern::regex::Regex;
+use indexmap::IndexMap;
+use schematic_types::*;
+use serde_json::{Number, Value};
+use std::collections::HashSet;
+
+/// Options to control schema validation.
+#[derive(Clone, Debug)]
+pub struct SchemaValidatorOptions {
+    /// Allows struct fields that are not defined in the schema.
+    pub allow_unknown_fields: bool,
+
+    /// Marks all non-optional and non-nullable struct fields as required.
+    pub mark_struct_fields_required: bool,
+}
+
+impl Default for SchemaValidatorOptions {
+    fn default() -> Self {
+        Self {
+            allow_unknown_fields: false,
+            mark_struct_fields_required: true,
+        }
+    }
+}
+
+/// Validates arbitrary JSON values against a [`Schema`], without requiring
+/// a Rust type. References are resolved through the provided schemas,
+/// typically from a [`SchemaGenerator`].
+pub struct SchemaValidator<'a> {
+    pub options: SchemaValidatorOptions,
+
+    references: &'a IndexMap<String, Schema>,
+}
+
+impl<'a> SchemaValidator<'a> {
+    /// Create a new validator that resolves references with the provided schemas.
+    pub fn new(references: &'a IndexMap<String, Schema>) -> Self {
+        Self::with_options(references, SchemaValidatorOptions::default())
+    }
+
+    /// Create a new validator that resolves references with the provided schemas,
+    /// and custom options.
+    pub fn with_options(
+        references: &'a IndexMap<String, Schema>,
+        options: SchemaValidatorOptions,
+    ) -> Self {
+        Self {
+            options,
+            references,
+        }
+    }
+
+    /// Create a new validator that resolves references with the schemas
+    /// collected by the provided [`SchemaGenerator`].
+    pub fn from_generator(generator: &'a SchemaGenerator) -> Self {
+        Self::new(&generator.schemas)
+    }
+
+    /// Validate the value against the provided schema, and return all errors.
+    /// Each error includes a [`Path`] to the invalid value.
+    pub fn validate(&self, schema: &Schema, value: &Value) -> Vec<ValidateError> {
+        let mut errors = vec![];
+
+        self.validate_schema(schema, value, &Path::default(), &mut errors);
+
+        errors
+    }
+
+    /// Validate the value against the schema with the provided name,
+    /// and return all errors.
+    pub fn validate_by_name(&self, name: &str, value: &Value) -> Vec<ValidateError> {
+        match self.references.get(name) {
+            Some(schema) => self.validate(schema, value),
+            None => vec![unknown_reference(name, Path::default())],
+        }
+    }
+
+    fn resolve<'s>(&'s self, schema: &'s Schema) -> Option<&'s Schema> {
+        match &schema.ty {
+            SchemaType::Reference(name) => self.references.get(name),
+            _ => Some(schema),
+        }
+    }
+
+    fn validate_schema(
+        &self,
+        schema: &Schema,
+        value: &Value,
+        path: &Path,
+        errors: &mut Vec<ValidateError>,
+    ) {
+        match &schema.ty {
+            SchemaType::Null => {
+                if !value.is_null() {
+                    errors.push(mismatch("null", value, path));
+                }
+            }
+            SchemaType::Unknown => {}
+            SchemaType::Array(inner) => self.validate_array(inner, value, path, errors),
+            SchemaType::Boolean(_) => {
+                if !value.is_boolean() {
+                    errors.push(mismatch("boolean", value, path));
+                }
+            }
+            SchemaType::Enum(inner) => validate_enum(inner, value, path, errors),
+            SchemaType::Float(inner) => validate_float(inner, value, path, errors),
+            SchemaType::Integer(inner) => validate_integer(inner, value, path, errors),
+            SchemaType::Literal(inner) => {
+                if !literal_matches(&inner.value, value) {
+                    errors.push(ValidateError::with_path(
+                        format!("expected {}, received {value}", inner.value),
+                        path.clone(),
+                    ));
+                }
+            }
+            SchemaType::Object(inner) => self.validate_object(inner, value, path, errors),
+            SchemaType::Reference(name) => match self.references.get(name) {
+                Some(reference) => self.validate_schema(reference, value, path, errors),
+                None => errors.push(unknown_reference(name, path.clone())),
+            },
+            SchemaType::Struct(inner) => self.validate_struct(inner, value, path, errors),
+            SchemaType::String(inner) => validate_string(inner, value, path, errors),
+            SchemaType::Tuple(inner) => self.validate_tuple(inner, value, path, errors),
+            SchemaType::Union(inner) => self.validate_union(schema, inner, value, path, errors),
+        };
+    }
+
+    fn validate_array(
+        &self,
+        array: &ArrayType,
+        value: &Value,
+        path: &Path,
+        errors: &mut Vec<ValidateError>,
+    ) {
+        let Some(items) = value.as_array() else {
+            errors.push(mismatch("array", value, path));
+            return;
+        };
+
+        validate_length(
+            items.len(),
+            array.min_length,
+            array.max_length,
+            path,
+            errors,
+        );
+
+        if array.unique.is_some_and(|unique| unique) {
+            let mut seen = HashSet::new();
+
+            if !items.iter().all(|item| seen.insert(item.to_string())) {
+                errors.push(ValidateError::with_path(
+                    "items must be unique",
+                    path.clone(),
+                ));
+            }
+        }
+
+        for (index, item) in items.iter().enumerate() {
+            self.validate_schema(&array.items_type, item, &path.join_index(index), errors);
+        }
+    }
+
+    fn validate_object(
+        &self,
+        object: &ObjectType,
+        value: &Value,
+        path: &Path,
+        errors: &mut Vec<ValidateError>,
+    ) {
+        let Some(map) = value.as_object() else {
+            errors.push(mismatch("object", value, path));
+            return;
+        };
+
+        validate_length(
+            map.len(),
+            object.min_length,
+            object.max_length,
+            path,
+            errors,
+        );
+
+        for key in object.required.iter().flatten() {
+            if !map.contains_key(key) {
+                errors.push(ValidateError::required().prepend_path(path.join_key(key)));
+            }
+        }
+
+        // Keys are always strings, so only validate against string-like types
+        let validate_keys = self.resolve(&object.key_type).is_some_and(|key_type| {
+            matches!(
+                key_type.ty,
+                SchemaType::Enum(_) | SchemaType::Literal(_) | SchemaType::String(_)
+            )
+        });
+
+        for (key, item) in map {
+            let item_path = path.join_key(key);
+
+            if validate_keys {
+                self.validate_schema(
+                    &object.key_type,
+                    &Value::String(key.to_owned()),
+                    &item_path,
+                    errors,
+                );
+            }
+
+            self.validate_schema(&object.value_type, item, &item_path, errors);
+        }
+    }
+
+    fn validate_struct(
+        &self,
+        structure: &StructType,
+        value: &Value,
+        path: &Path,
+        errors: &mut Vec<ValidateError>,
+    ) {
+        let Some(map) = value.as_object() else {
+            errors.push(mismatch("object", value, path));
+            return;
+        };
+
+        let mut known_keys = HashSet::new();
+        let mut flattened = vec![];
+
+        for (name, field) in &structure.fields {
+            if field.flatten {
+                flattened.push(&field.schema);
+                continue;
+            }
+
+            let found = std::iter::once(name)
+                .chain(field.aliases.iter())
+                .find_map(|key| map.get_key_value(key));
+
+            known_keys.insert(name.as_str());
+            known_keys.extend(field.aliases.iter().map(|alias| alias.as_str()));
+
+            match found {
+                Some((key, item)) => {
+                    self.validate_schema(&field.schema, item, &path.join_key(key), errors);
+                }
+                None => {
+                    let required = structure
+                        .required
+                        .as_ref()
+                        .is_some_and(|required| required.contains(name))
+                        || (self.options.mark_struct_fields_required
+                            && !field.optional
+                            && !field.nullable
+                            && !field.schema.is_nullable()
+                            && !field.hidden);
+
+                    if required {
+                        errors.push(ValidateError::required().prepend_path(path.join_key(name)));
+                    }
+                }
+            };
+        }
+
+        // Remaining keys are validated against flattened fields, if any
+        for (key, item) in map {
+            if known_keys.contains(key.as_str()) {
+                continue;
+            }
+
+            let item_path = path.join_key(key);
+            let mut handled = false;
+
+            for schema in &flattened {
+                match self.resolve(schema).map(|schema| &schema.ty) {
+                    Some(SchemaType::Object(inner)) => {
+                        self.validate_schema(&inner.value_type, item, &item_path, errors);
+                        handled = true;
+                    }
+                    Some(SchemaType::Struct(inner)) => {
+                        if let Some(field) = inner.fields.get(key) {
+                            self.validate_schema(&field.schema, item, &item_path, errors);
+                            handled = true;
+                        }
+                    }
+                    _ => {
+                        handled = true;
+                    }
+                };
+
+                if handled {
+                    break;
+                }
+            }
+
+            if !handled && !self.options.allow_unknown_fields {
+                errors.push(ValidateError::with_path("unknown field", item_path));
+            }
+        }
+    }
+
+    fn validate_tuple(
+        &self,
+        tuple: &TupleType,
+        value: &Value,
+        path: &Path,
+        errors: &mut Vec<ValidateError>,
+    ) {
+        let Some(items) = value.as_array() else {
+            errors.push(mismatch("array", value, path));
+            return;
+        };
+
+        if items.len() != tuple.items_types.len() {
+            errors.push(ValidateError::with_path(
+                format!(
+                    "expected {} items, received {}",
+                    tuple.items_types.len(),
+                    items.len()
+                ),
+                path.clone(),
+            ));
+
+            return;
+        }
+
+        for (index, (item_type, item)) in tuple.items_types.iter().zip(items).enumerate() {
+            self.validate_schema(item_type, item, &path.join_index(index), errors);
+        }
+    }
+
+    fn validate_union(
+        &self,
+        schema: &Schema,
+        union: &UnionType,
+        value: &Value,
+        path: &Path,
+        errors: &mut Vec<ValidateError>,
+    ) {
+        let mut matched = 0;
+        let mut candidates = vec![];
+
+        for variant in &union.variants_types {
+            let mut variant_errors = vec![];
+
+            self.validate_schema(variant, value, path, &mut variant_errors);
+
+            if variant_errors.is_empty() {
+                matched += 1;
+            } else if self.is_same_kind(variant, value) {
+                candidates.push(variant_errors);
+            }
+        }
+
+        match (&union.operator, matched) {
+            (UnionOperator::AnyOf, 1..) | (UnionOperator::OneOf, 1) => {}
+            (UnionOperator::OneOf, _) if matched > 1 => {
+                errors.push(ValidateError::with_path(
+                    format!("expected only one of {schema}, but matched {matched}"),
+                    path.clone(),
+                ));
+            }
+            // When only a single variant is the same kind, its errors are
+            // more meaningful than a generic mismatch
+            _ if candidates.len() == 1 => {
+                errors.extend(candidates.remove(0));
+            }
+            _ => {
+                errors.push(mismatch(&schema.to_string(), value, path));
+            }
+        };
+    }
+
+    fn is_same_kind(&self, schema: &Schema, value: &Value) -> bool {
+        let Some(schema) = self.resolve(schema) else {
+            return false;
+        };
+
+        match (&schema.ty, value) {
+            (SchemaType::Null, Value::Null)
+            | (SchemaType::Boolean(_), Value::Bool(_))
+            | (SchemaType::Float(_) | SchemaType::Integer(_), Value::Number(_))
+            | (SchemaType::String(_), Value::String(_))
+            | (SchemaType::Array(_) | SchemaType::Tuple(_), Value::Array(_))
+            | (SchemaType::Object(_) | SchemaType::Struct(_), Value::Object(_)) => true,
+            (SchemaType::Union(inner), _) => inner
+                .variants_types
+                .iter()
+                .any(|variant| self.is_same_kind(variant, value)),
+            _ => false,
+        }
+    }
+}
+
+fn kind_of(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "boolean",
+        Value::Number(_) => "number",
+        Value::String(_) => "string",
+        Value::Array(_) => "array",
+        Value::Object(_) => "object",
+    }
+}
+
+fn mismatch(expected: &str, value: &Value, path: &Path) -> ValidateError {
+    ValidateError::with_path(
+        format!("expected {expected}, received {}", kind_of(value)),
+        path.clone(),
+    )
+}
+
+fn unknown_reference(name: &str, path: Path) -> ValidateError {
+    ValidateError::with_path(format!("unknown schema reference `{name}`"), path)
+}
+
+fn literal_matches(literal: &LiteralValue, value: &Value) -> bool {
+    match (literal, value) {
+        (LiteralValue::Bool(lit), Value::Bool(val)) => lit == val,
+        (LiteralValue::F32(lit), Value::Number(val)) => val.as_f64() == Some(*lit as f64),
+        (LiteralValue::F64(lit), Value::Number(val)) => val.as_f64() == Some(*lit),
+        (LiteralValue::Int(lit), Value::Number(val)) => val.as_i64() == Some(*lit as i64),
+        (LiteralValue::UInt(lit), Value::Number(val)) => val.as_u64() == Some(*lit as u64),
+        (LiteralValue::String(lit), Value::String(val)) => lit == val,
+        _ => false,
+    }
+}
+
+fn join_values<T: ToString>(values: impl IntoIterator<Item = T>) -> String {
+    values
+        .into_iter()
+        .map(|value| value.to_string())
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn validate_enum(
+    enumerable: &EnumType,
+    value: &Value,
+    path: &Path,
+    errors: &mut Vec<ValidateError>,
+) {
+    // Enums with complex variants can't be validated by value
+    if enumerable.values.is_empty() {
+        return;
+    }
+
+    if !enumerable
+        .values
+        .iter()
+        .any(|literal| literal_matches(literal, value))
+    {
+        errors.push(ValidateError::with_path(
+            format!(
+                "expected one of {}, received {value}",
+                join_values(&enumerable.values)
+            ),
+            path.clone(),
+        ));
+    }
+}
+
+fn validate_length(
+    length: usize,
+    min: Option<usize>,
+    max: Option<usize>,
+    path: &Path,
+    errors: &mut Vec<ValidateError>,
+) {
+    if let Some(min) = min
+        && length < min
+    {
+        errors.push(ValidateError::with_path(
+            format!("length must be at least {min}"),
+            path.clone(),
+        ));
+    }
+
+    if let Some(max) = max
+        && length > max
+    {
+        errors.push(ValidateError::with_path(
+            format!("length must be at most {max}"),
+            path.clone(),
+        ));
+    }
+}
+
+struct NumberBounds {
+    min: Option<f64>,
+    max: Option<f64>,
+    min_exclusive: Option<f64>,
+    max_exclusive: Option<f64>,
+    multiple_of: Option<f64>,
+}
+
+fn validate_number_bounds(
+    number: f64,
+    bounds: NumberBounds,
+    path: &Path,
+    errors: &mut Vec<ValidateError>,
+) {
+    let mut push = |message: String| {
+        errors.push(ValidateError::with_path(message, path.clone()));
+    };
+
+    if let Some(min) = bounds.min
+        && number < min
+    {
+        push(format!("must be greater than or equal to {min}"));
+    }
+
+    if let Some(max) = bounds.max
+        && number > max
+    {
+        push(format!("must be less than or equal to {max}"));
+    }
+
+    if let Some(min) = bounds.min_exclusive
+        && number <= min
+    {
+        push(format!("must be greater than {min}"));
+    }
+
+    if let Some(max) = bounds.max_exclusive
+        && number >= max
+    {
+        push(format!("must be less than {max}"));
+    }
+
+    if let Some(multiple) = bounds.multiple_of
+        && multiple != 0.0
+        && (number / multiple).fract() != 0.0
+    {
+        push(format!("must be a multiple of {multiple}"));
+    }
+}
+
+fn validate_float(float: &FloatType, value: &Value, path: &Path, errors: &mut Vec<ValidateError>) {
+    let Some(number) = value.as_f64() else {
+        errors.push(mismatch("number", value, path));
+        return;
+    };
+
+    if let Some(values) = &float.enum_values
+        && !values.contains(&number)
+    {
+        errors.push(ValidateError::with_path(
+            format!("expected one of {}, received {number}", join_values(values)),
+            path.clone(),
+        ));
+    }
+
+    validate_number_bounds(
+        number,
+        NumberBounds {
+            min: float.min,
+            max: float.max,
+            min_exclusive: float.min_exclusive,
+            max_exclusive: float.max_exclusive,
+            multiple_of: float.multiple_of,
+        },
+        path,
+        errors,
+    );
+}
+
+fn as_integer(value: &Number) -> Option<i128> {
+    value
+        .as_i64()
+        .map(i128::from)
+        .or_else(|| value.as_u64().map(i128::from))
+}
+
+fn validate_integer(
+    integer: &IntegerType,
+    value: &Value,
+    path: &Path,
+    errors: &mut Vec<ValidateError>,
+) {
+    let Some(number) = value.as_number().and_then(as_integer) else {
+        errors.push(mismatch("integer", value, path));
+        return;
+    };
+
+    if integer.kind.is_unsigned() && number < 0 {
+        errors.push(ValidateError::with_path(
+            "must not be negative",
+            path.clone(),
+        ));
+    }
+
+    if let Some(values) = &integer.enum_values
+        && !values.iter().any(|item| *item as i128 == number)
+    {
+        errors.push(ValidateError::with_path(
+            format!("expected one of {}, received {number}", join_values(values)),
+            path.clone(),
+        ));
+    }
+
+    let to_f64 = |value: Option<isize>| value.map(|value| value as f64);
+
+    validate_number_bounds(
+        number as f64,
+        NumberBounds {
+            min: to_f64(integer.min),
+            max: to_f64(integer.max),
+            min_exclusive: to_f64(integer.min_exclusive),
+            max_exclusive: to_f64(integer.max_exclusive),
+            multiple_of: to_f64(integer.multiple_of),
+        },
+        path,
+        errors,
+    );
+}
+
+fn validate_string(
+    string: &StringType,
+    value: &Value,
+    path: &Path,
+    errors: &mut Vec<ValidateError>,
+) {
+    let Some(string_value) = value.as_str() else {
+        errors.push(mismatch("string", value, path));
+        return;
+    };
+
+    if let Some(values) = &string.enum_values
+        && !values.iter().any(|item| item == string_value)
+    {
+        errors.push(ValidateError::with_path(
+            format!(
+                "expected one of {}, received {value}",
+                join_values(values.iter().map(|item| format!("\"{item}\"")))
+            ),
+            path.clone(),
+        ));
+    }
+
+    validate_length(
+        string_value.chars().count(),
+        string.min_length,
+        string.max_length,
+        path,
+        errors,
+    );
+
+    if let Some(pattern) = &string.pattern {
+        match Regex::new(pattern) {
+            Ok(regex) => {
+                if !regex.is_match(string_value) {
+                    errors.push(ValidateError::with_path(
+                        format!("does not match pattern /{pattern}/"),
+                        path.clone(),
+                    ));
+                }
+            }
+            Err(_) => {
+                errors.push(ValidateError::with_path(
+                    format!("invalid schema pattern /{pattern}/"),
+                    path.clone(),
+                ));
+            }
+        };
+    }
+}
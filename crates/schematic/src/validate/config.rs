@@ -0,0 +1,192 @@
+use super::{ConfigValidator, ValidateError};
+use crate::config::{PartialConfig, Path, PathSegment, reveal_secrets};
+use serde::Serialize;
+use serde_content::{Data, Serializer, Value};
+
+fn normalize(name: &str) -> String {
+    name.replace(['_', '-'], "").to_lowercase()
+}
+
+fn create_path(field: &str) -> Path {
+    Path::new(
+        field
+            .split('.')
+            .map(|key| PathSegment::Key(key.to_owned()))
+            .collect(),
+    )
+}
+
+fn find_value<'v>(value: &'v Value<'v>, field: &str) -> Option<&'v Value<'v>> {
+    let mut current = value;
+
+    for key in field.split('.') {
+        while let Value::Option(Some(inner)) = current {
+            current = inner;
+        }
+
+        current = match current {
+            Value::Struct(inner) => match &inner.data {
+                Data::Struct { fields } => fields
+                    .iter()
+                    .find(|(name, _)| name == key || normalize(name) == normalize(key))
+                    .map(|(_, value)| value)?,
+                _ => return None,
+            },
+            Value::Map(entries) => entries
+                .iter()
+                .find(|(name, _)| matches!(name, Value::String(name) if name == key))
+                .map(|(_, value)| value)?,
+            _ => return None,
+        };
+    }
+
+    Some(current)
+}
+
+fn has_value(value: Option<&Value>) -> bool {
+    match value {
+        Some(Value::Option(Some(inner))) => has_value(Some(inner)),
+        Some(Value::Option(None) | Value::Unit) | None => false,
+        Some(_) => true,
+    }
+}
+
+/// Defaults have been applied when finalizing, so a setting is only set
+/// when it has a value that differs from its default value.
+fn is_set(value: Option<&Value>, default: Option<&Value>) -> bool {
+    has_value(value) && value != default
+}
+
+fn is_enabled(value: Option<&Value>) -> bool {
+    match value {
+        Some(Value::Option(Some(inner))) => is_enabled(Some(inner)),
+        Some(Value::Bool(inner)) => *inner,
+        Some(Value::String(inner)) => !inner.is_empty(),
+        Some(Value::Seq(inner)) => !inner.is_empty(),
+        Some(Value::Map(inner)) => !inner.is_empty(),
+        other => has_value(other),
+    }
+}
+
+fn serialize<D: Serialize>(data: &D) -> Result<Value<'static>, Vec<ValidateError>> {
+    // Secrets are redacted when serialized, which would change their value
+    reveal_secrets(|| Serializer::new().serialize(data))
+        .map_err(|error| vec![ValidateError::new(error.to_string())])
+}
+
+fn serialize_defaults<D: PartialConfig + Serialize>(
+    context: &D::Context,
+) -> Result<Value<'static>, Vec<ValidateError>> {
+    let defaults =
+        D::default_values(context).map_err(|error| vec![ValidateError::new(error.to_string())])?;
+
+    serialize(&defaults)
+}
+
+fn quote_fields(fields: &[String]) -> String {
+    fields
+        .iter()
+        .map(|field| format!("`{field}`"))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Validate the `field` setting is set when the `condition` setting is enabled
+/// (`true`, or a non-empty value). Settings are dot-notated paths from the current
+/// configuration. Only runs when finalizing.
+pub fn required_if<D: PartialConfig<Context = C> + Serialize, C>(
+    field: &str,
+    condition: &str,
+) -> ConfigValidator<D, C> {
+    let field = field.to_owned();
+    let condition = condition.to_owned();
+
+    Box::new(move |data, context, finalize| {
+        if !finalize {
+            return Ok(());
+        }
+
+        let value = serialize(data)?;
+        let defaults = serialize_defaults::<D>(context)?;
+
+        if is_enabled(find_value(&value, &condition))
+            && !is_set(find_value(&value, &field), find_value(&defaults, &field))
+        {
+            return Err(vec![ValidateError::with_path(
+                format!("this setting is required when `{condition}` is enabled"),
+                create_path(&field),
+            )]);
+        }
+
+        Ok(())
+    })
+}
+
+/// Validate that only 1 of the provided settings is set. Settings are dot-notated
+/// paths from the current configuration. Only runs when finalizing.
+pub fn mutually_exclusive<D: PartialConfig<Context = C> + Serialize, C>(
+    fields: &[&str],
+) -> ConfigValidator<D, C> {
+    let fields = fields
+        .iter()
+        .map(|field| field.to_string())
+        .collect::<Vec<_>>();
+
+    Box::new(move |data, context, finalize| {
+        if !finalize {
+            return Ok(());
+        }
+
+        let value = serialize(data)?;
+        let defaults = serialize_defaults::<D>(context)?;
+        let set_fields = fields
+            .iter()
+            .filter(|field| is_set(find_value(&value, field), find_value(&defaults, field)))
+            .collect::<Vec<_>>();
+
+        if set_fields.len() > 1 {
+            let message = format!("only one of {} can be set", quote_fields(&fields));
+
+            return Err(set_fields
+                .into_iter()
+                .map(|field| ValidateError::with_path(&message, create_path(field)))
+                .collect());
+        }
+
+        Ok(())
+    })
+}
+
+/// Validate that at least 1 of the provided settings is set. Settings are dot-notated
+/// paths from the current configuration. Only runs when finalizing.
+pub fn at_least_one_of<D: PartialConfig<Context = C> + Serialize, C>(
+    fields: &[&str],
+) -> ConfigValidator<D, C> {
+    let fields = fields
+        .iter()
+        .map(|field| field.to_string())
+        .collect::<Vec<_>>();
+
+    Box::new(move |data, context, finalize| {
+        if !finalize {
+            return Ok(());
+        }
+
+        let value = serialize(data)?;
+        let defaults = serialize_defaults::<D>(context)?;
+
+        if !fields
+            .iter()
+            .any(|field| is_set(find_value(&value, field), find_value(&defaults, field)))
+        {
+            let message = format!("at least one of {} must be set", quote_fields(&fields));
+
+            return Err(fields
+                .iter()
+                .map(|field| ValidateError::with_path(&message, create_path(field)))
+                .collect());
+        }
+
+        Ok(())
+    })
+}
@@ -0,0 +1,155 @@
+use super::{ValidateError, ValidateResult, Validator};
+use std::borrow::Cow;
+use std::fs;
+use std::path::Path;
+
+/// Context that provides a root directory for resolving relative paths
+/// within the filesystem validators. When no root is provided, relative
+/// paths are resolved against the current working directory.
+pub trait FsContext {
+    fn root_dir(&self) -> Option<&Path> {
+        None
+    }
+}
+
+impl FsContext for () {}
+
+fn resolve<'a>(path: &'a Path, context: &impl FsContext) -> Cow<'a, Path> {
+    match context.root_dir() {
+        Some(root) if path.is_relative() => Cow::Owned(root.join(path)),
+        _ => Cow::Borrowed(path),
+    }
+}
+
+/// Validate a path exists.
+pub fn exists<T: AsRef<Path>, D, C: FsContext>(
+    value: &T,
+    _data: &D,
+    context: &C,
+    _finalize: bool,
+) -> ValidateResult {
+    let path = value.as_ref();
+
+    if !resolve(path, context).exists() {
+        return Err(ValidateError::new(format!(
+            "path `{}` does not exist",
+            path.display()
+        )));
+    }
+
+    Ok(())
+}
+
+/// Validate a path exists and is a file.
+pub fn is_file<T: AsRef<Path>, D, C: FsContext>(
+    value: &T,
+    _data: &D,
+    context: &C,
+    _finalize: bool,
+) -> ValidateResult {
+    let path = value.as_ref();
+
+    if !resolve(path, context).is_file() {
+        return Err(ValidateError::new(format!(
+            "path `{}` is not a file",
+            path.display()
+        )));
+    }
+
+    Ok(())
+}
+
+/// Validate a path exists and is a directory.
+pub fn is_dir<T: AsRef<Path>, D, C: FsContext>(
+    value: &T,
+    _data: &D,
+    context: &C,
+    _finalize: bool,
+) -> ValidateResult {
+    let path = value.as_ref();
+
+    if !resolve(path, context).is_dir() {
+        return Err(ValidateError::new(format!(
+            "path `{}` is not a directory",
+            path.display()
+        )));
+    }
+
+    Ok(())
+}
+
+/// Validate a path exists and can be read by the current process.
+pub fn is_readable<T: AsRef<Path>, D, C: FsContext>(
+    value: &T,
+    _data: &D,
+    context: &C,
+    _finalize: bool,
+) -> ValidateResult {
+    let path = value.as_ref();
+    let abs_path = resolve(path, context);
+
+    let readable = if abs_path.is_dir() {
+        fs::read_dir(&abs_path).is_ok()
+    } else {
+        fs::File::open(&abs_path).is_ok()
+    };
+
+    if !readable {
+        return Err(ValidateError::new(format!(
+            "path `{}` is not readable",
+            path.display()
+        )));
+    }
+
+    Ok(())
+}
+
+/// Validate a path is absolute.
+pub fn is_absolute<T: AsRef<Path>, D, C>(
+    value: &T,
+    _data: &D,
+    _context: &C,
+    _finalize: bool,
+) -> ValidateResult {
+    let path = value.as_ref();
+
+    if !path.is_absolute() {
+        return Err(ValidateError::new(format!(
+            "path `{}` must be absolute",
+            path.display()
+        )));
+    }
+
+    Ok(())
+}
+
+/// Validate a path has one of the provided file extensions (without a leading dot).
+/// Extensions are compared case-insensitively.
+pub fn extension_in<T: AsRef<Path>, D, C>(extensions: &[&str]) -> Validator<T, D, C> {
+    let extensions = extensions
+        .iter()
+        .map(|ext| ext.trim_start_matches('.').to_lowercase())
+        .collect::<Vec<_>>();
+
+    Box::new(move |value, _, _, _| {
+        let path = value.as_ref();
+
+        if path
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .is_some_and(|ext| extensions.contains(&ext.to_lowercase()))
+        {
+            return Ok(());
+        }
+
+        Err(ValidateError::new(format!(
+            "path `{}` must have one of the extensions {}",
+            path.display(),
+            extensions
+                .iter()
+                .map(|ext| format!("`.{ext}`"))
+                .collect::<Vec<_>>()
+                .join(", ")
+        )))
+    })
+}
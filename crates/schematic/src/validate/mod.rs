@@ -1,15 +1,22 @@
+mod config;
 #[cfg(feature = "validate_email")]
 mod email;
 #[cfg(feature = "extends")]
 mod extends;
+pub mod fs;
 mod ip;
 mod length;
+pub mod net;
 mod number;
 mod string;
 #[cfg(feature = "validate_url")]
 mod url;
 
-pub use crate::config::{ValidateError, ValidateResult, Validator};
+pub use crate::config::{
+    ConfigValidator, ValidateConfigResult, ValidateError, ValidateResult, ValidateSeverity,
+    Validator,
+};
+pub use config::*;
 #[cfg(feature = "validate_email")]
 pub use email::*;
 #[cfg(feature = "extends")]
@@ -0,0 +1,109 @@
+use super::{ValidateError, ValidateResult, Validator};
+use std::net::{IpAddr, SocketAddr};
+
+fn is_hostname(value: &str) -> bool {
+    let value = value.strip_suffix('.').unwrap_or(value);
+
+    !value.is_empty()
+        && value.len() <= 253
+        && value.split('.').all(|label| {
+            !label.is_empty()
+                && label.len() <= 63
+                && !label.starts_with('-')
+                && !label.ends_with('-')
+                && label
+                    .chars()
+                    .all(|ch| ch.is_ascii_alphanumeric() || ch == '-')
+        })
+}
+
+/// Validate a string is a hostname, as defined by RFC 1123.
+pub fn hostname<T: AsRef<str>, D, C>(
+    value: &T,
+    _data: &D,
+    _context: &C,
+    _finalize: bool,
+) -> ValidateResult {
+    let value = value.as_ref();
+
+    if !is_hostname(value) {
+        return Err(ValidateError::new(format!(
+            "`{value}` is not a valid hostname"
+        )));
+    }
+
+    Ok(())
+}
+
+/// Validate a number is a usable port (1-65535).
+pub fn port<T: TryInto<u16> + Copy, D, C>(
+    value: &T,
+    _data: &D,
+    _context: &C,
+    _finalize: bool,
+) -> ValidateResult {
+    match (*value).try_into() {
+        Ok(port) if port > 0 => Ok(()),
+        _ => Err(ValidateError::new("must be a port between 1 and 65535")),
+    }
+}
+
+/// Validate a number is a port within the provided bounds (inclusive).
+pub fn port_in_range<T: TryInto<u16> + Copy, D, C>(min: u16, max: u16) -> Validator<T, D, C> {
+    Box::new(move |value, _, _, _| match (*value).try_into() {
+        Ok(port) if port >= min.max(1) && port <= max => Ok(()),
+        _ => Err(ValidateError::new(format!(
+            "must be a port between {} and {max}",
+            min.max(1)
+        ))),
+    })
+}
+
+/// Validate a string is a socket address, composed of an IP address and port.
+pub fn socket_addr<T: AsRef<str>, D, C>(
+    value: &T,
+    _data: &D,
+    _context: &C,
+    _finalize: bool,
+) -> ValidateResult {
+    let value = value.as_ref();
+
+    if value.parse::<SocketAddr>().is_err() {
+        return Err(ValidateError::new(format!(
+            "`{value}` is not a valid socket address"
+        )));
+    }
+
+    Ok(())
+}
+
+/// Validate a string is an IP v4 or v6 network in CIDR notation.
+pub fn cidr<T: AsRef<str>, D, C>(
+    value: &T,
+    _data: &D,
+    _context: &C,
+    _finalize: bool,
+) -> ValidateResult {
+    let value = value.as_ref();
+
+    let valid = value.split_once('/').is_some_and(|(ip, prefix)| {
+        let max_prefix = match ip.parse::<IpAddr>() {
+            Ok(IpAddr::V4(_)) => 32,
+            Ok(IpAddr::V6(_)) => 128,
+            Err(_) => return false,
+        };
+
+        prefix.chars().all(|ch| ch.is_ascii_digit())
+            && prefix
+                .parse::<u8>()
+                .is_ok_and(|prefix| prefix <= max_prefix)
+    });
+
+    if !valid {
+        return Err(ValidateError::new(format!(
+            "`{value}` is not a valid CIDR network"
+        )));
+    }
+
+    Ok(())
+}
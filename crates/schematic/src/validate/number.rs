@@ -11,3 +11,17 @@ pub fn in_range<T: Bounds + Display + 'static, D, C>(
         garde::rules::range::apply(value, (Some(min), Some(max))).map_err(map_err)
     })
 }
+
+/// Validate a numeric value is at least the provided bound.
+pub fn min_value<T: Bounds + Display + 'static, D, C>(min: T::Size) -> Validator<T, D, C> {
+    Box::new(move |value, _, _, _| {
+        garde::rules::range::apply(value, (Some(min), None)).map_err(map_err)
+    })
+}
+
+/// Validate a numeric value is at most the provided bound.
+pub fn max_value<T: Bounds + Display + 'static, D, C>(max: T::Size) -> Validator<T, D, C> {
+    Box::new(move |value, _, _, _| {
+        garde::rules::range::apply(value, (None, Some(max))).map_err(map_err)
+    })
+}
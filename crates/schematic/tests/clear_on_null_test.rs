@@ -0,0 +1,232 @@
+#![allow(dead_code)]
+
+use schematic::*;
+use std::path::PathBuf;
+
+fn uppercase(value: String, _: &()) -> TransformResult<String> {
+    Ok(value.to_uppercase())
+}
+
+#[derive(Debug, Config)]
+pub struct ClearableConfig {
+    #[setting(clear_on_null)]
+    name: Option<String>,
+    kept: Option<String>,
+    #[setting(clear_on_null, default = "info")]
+    level: Option<String>,
+    #[setting(clear_on_null, merge = merge::append_vec)]
+    plugins: Option<Vec<String>>,
+    #[setting(clear_on_null, transform = uppercase)]
+    shout: Option<String>,
+    #[setting(clear_on_null, env = "CLEARABLE_PORT")]
+    port: Option<usize>,
+    #[setting(clear_on_null)]
+    boxed: Option<Box<usize>>,
+    #[setting(clear_on_null, relative_path)]
+    path: Option<PathBuf>,
+}
+
+#[derive(Debug, Config)]
+pub struct ClearableRequiredConfig {
+    #[setting(clear_on_null, required)]
+    name: Option<String>,
+}
+
+#[test]
+fn partial_fields_are_tri_state() {
+    let partial = PartialClearableConfig::default();
+
+    assert_eq!(partial.name, None);
+    assert_eq!(partial.level, None);
+
+    let defaults = PartialClearableConfig::default_values(&())
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(defaults.name, None);
+    assert_eq!(defaults.level, Some(Some("info".into())));
+}
+
+#[test]
+fn merges_explicit_null() {
+    let mut partial = PartialClearableConfig {
+        name: Some(Some("a".into())),
+        ..Default::default()
+    };
+
+    partial
+        .merge(
+            &(),
+            PartialClearableConfig {
+                name: Some(None),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+    assert_eq!(partial.name, Some(None));
+
+    // Unset values keep the explicit null
+    partial
+        .merge(&(), PartialClearableConfig::default())
+        .unwrap();
+
+    assert_eq!(partial.name, Some(None));
+
+    assert_eq!(ClearableConfig::from_partial(partial).name, None);
+}
+
+#[cfg(feature = "yaml")]
+mod yaml {
+    use super::*;
+
+    const BASE: &str = r"
+name: a
+kept: b
+level: debug
+plugins: [a]
+shout: hello
+boxed: 1
+";
+
+    #[test]
+    fn clears_inherited_values() {
+        let result = ConfigLoader::<ClearableConfig>::new()
+            .code(BASE, "base.yml")
+            .unwrap()
+            .code("name: null\nkept: null\nboxed: ~\nshout:", "code.yml")
+            .unwrap()
+            .load()
+            .unwrap();
+
+        assert_eq!(result.config.name, None);
+        assert_eq!(result.config.kept, Some("b".into()));
+        assert_eq!(result.config.boxed, None);
+        assert_eq!(result.config.shout, None);
+        assert_eq!(result.config.level, Some("debug".into()));
+    }
+
+    #[test]
+    fn clears_default_values() {
+        let result = ConfigLoader::<ClearableConfig>::new()
+            .code("level: null", "code.yml")
+            .unwrap()
+            .load()
+            .unwrap();
+
+        assert_eq!(result.config.level, None);
+
+        let result = ConfigLoader::<ClearableConfig>::new()
+            .code("name: a", "code.yml")
+            .unwrap()
+            .load()
+            .unwrap();
+
+        assert_eq!(result.config.level, Some("info".into()));
+    }
+
+    #[test]
+    fn keeps_explicit_null_in_partial() {
+        let partial = ConfigLoader::<ClearableConfig>::new()
+            .code(BASE, "base.yml")
+            .unwrap()
+            .code("name: null", "code.yml")
+            .unwrap()
+            .load_partial(&())
+            .unwrap();
+
+        assert_eq!(partial.name, Some(None));
+        assert_eq!(partial.kept, Some("b".into()));
+        assert_eq!(partial.port, None);
+    }
+
+    #[test]
+    fn merges_with_strategy_after_clearing() {
+        let result = ConfigLoader::<ClearableConfig>::new()
+            .code(BASE, "base.yml")
+            .unwrap()
+            .code("plugins: [b]", "code.yml")
+            .unwrap()
+            .load()
+            .unwrap();
+
+        assert_eq!(result.config.plugins, Some(vec!["a".into(), "b".into()]));
+
+        let result = ConfigLoader::<ClearableConfig>::new()
+            .code(BASE, "base.yml")
+            .unwrap()
+            .code("plugins: null", "one.yml")
+            .unwrap()
+            .code("plugins: [b]", "two.yml")
+            .unwrap()
+            .load()
+            .unwrap();
+
+        assert_eq!(result.config.plugins, Some(vec!["b".into()]));
+    }
+
+    #[test]
+    fn transforms_values() {
+        let result = ConfigLoader::<ClearableConfig>::new()
+            .code(BASE, "base.yml")
+            .unwrap()
+            .load()
+            .unwrap();
+
+        assert_eq!(result.config.shout, Some("HELLO".into()));
+    }
+
+    #[test]
+    fn resolves_relative_paths() {
+        let result = ConfigLoader::<ClearableConfig>::new()
+            .set_base_dir("/root")
+            .code("path: ./file", "code.yml")
+            .unwrap()
+            .load()
+            .unwrap();
+
+        assert_eq!(result.config.path, Some(PathBuf::from("/root/./file")));
+    }
+
+    #[test]
+    fn preserves_explicit_null_when_interpolating() {
+        let result = ConfigLoader::<ClearableConfig>::new()
+            .set_interpolate(true)
+            .code(BASE, "base.yml")
+            .unwrap()
+            .code("level: null\nkept: ${name}", "code.yml")
+            .unwrap()
+            .load()
+            .unwrap();
+
+        // Would fallback to the default if the null was lost
+        assert_eq!(result.config.level, None);
+        assert_eq!(result.config.kept, Some("a".into()));
+    }
+
+    #[test]
+    fn loads_env_vars() {
+        let result = ConfigLoader::<ClearableConfig>::new()
+            .set_env_provider(MemoryEnv::from_iter([("CLEARABLE_PORT", "3000")]))
+            .code("port: null", "code.yml")
+            .unwrap()
+            .load()
+            .unwrap();
+
+        assert_eq!(result.config.port, Some(3000));
+    }
+
+    #[test]
+    fn errors_if_required_and_null() {
+        let error = ConfigLoader::<ClearableRequiredConfig>::new()
+            .code("name: a", "base.yml")
+            .unwrap()
+            .code("name: null", "code.yml")
+            .unwrap()
+            .load()
+            .err()
+            .unwrap();
+
+        assert!(error.to_full_string().contains("this setting is required"));
+    }
+}
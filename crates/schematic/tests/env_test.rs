@@ -2,7 +2,7 @@
 
 use schematic::*;
 use serial_test::serial;
-use std::{env, path::PathBuf};
+use std::{collections::HashMap, env, path::PathBuf};
 
 #[derive(Debug, Config)]
 pub struct EnvVars {
@@ -231,6 +231,245 @@ fn loads_from_prefixed() {
     assert_eq!(result.config.list2, vec![1, 2, 3]);
 }
 
+#[derive(Debug, Config)]
+pub struct EnvNestedServer {
+    host: String,
+    max_connections: usize,
+    hosts: Vec<String>,
+    labels: HashMap<String, String>,
+}
+
+#[derive(Debug, Config)]
+pub struct EnvNestedWorker {
+    name: String,
+    #[setting(default = 1)]
+    threads: usize,
+}
+
+#[derive(Debug, Config)]
+#[config(env_prefix = "APP_", env_nested_delimiter = "__")]
+pub struct EnvNested {
+    name: String,
+    tags: Vec<String>,
+    #[setting(parse_env = schematic::env::split_semicolon)]
+    ids: Vec<usize>,
+    #[setting(nested)]
+    server: EnvNestedServer,
+    #[setting(nested)]
+    workers: Vec<EnvNestedWorker>,
+    #[setting(nested)]
+    pools: HashMap<String, EnvNestedWorker>,
+}
+
+fn reset_nested_vars() {
+    for (key, _) in env::vars() {
+        if key.starts_with("APP_") {
+            unsafe { env::remove_var(key) };
+        }
+    }
+}
+
+#[test]
+#[serial]
+fn loads_nested_env_vars() {
+    reset_nested_vars();
+
+    unsafe {
+        env::set_var("APP_NAME", "app");
+        env::set_var("APP_TAGS", "x,y");
+        env::set_var("APP_IDS", "1;2");
+        env::set_var("APP_SERVER__HOST", "localhost");
+        env::set_var("APP_SERVER__MAX_CONNECTIONS", "10");
+        env::set_var("APP_SERVER__HOSTS__1", "b");
+        env::set_var("APP_SERVER__HOSTS__0", "a");
+        env::set_var("APP_SERVER__LABELS__team", "infra");
+        env::set_var("APP_UNKNOWN__KEY", "ignored");
+    };
+
+    let result = ConfigLoader::<EnvNested>::new().load().unwrap();
+
+    assert_eq!(result.config.name, "app");
+    assert_eq!(result.config.tags, vec!["x", "y"]);
+    assert_eq!(result.config.ids, vec![1, 2]);
+    assert_eq!(result.config.server.host, "localhost");
+    assert_eq!(result.config.server.max_connections, 10);
+    assert_eq!(result.config.server.hosts, vec!["a", "b"]);
+    assert_eq!(
+        result.config.server.labels,
+        HashMap::from_iter([("team".into(), "infra".into())])
+    );
+
+    reset_nested_vars();
+}
+
+#[test]
+#[serial]
+fn loads_nested_env_vars_for_nested_collections() {
+    reset_nested_vars();
+
+    unsafe {
+        env::set_var("APP_WORKERS__0__NAME", "first");
+        env::set_var("APP_WORKERS__1__NAME", "second");
+        env::set_var("APP_WORKERS__1__THREADS", "4");
+        env::set_var("APP_POOLS__default__NAME", "pool");
+    };
+
+    let result = ConfigLoader::<EnvNested>::new().load().unwrap();
+
+    assert_eq!(result.config.workers.len(), 2);
+    assert_eq!(result.config.workers[0].name, "first");
+    assert_eq!(result.config.workers[0].threads, 1);
+    assert_eq!(result.config.workers[1].name, "second");
+    assert_eq!(result.config.workers[1].threads, 4);
+    assert_eq!(result.config.pools.get("default").unwrap().name, "pool");
+
+    reset_nested_vars();
+}
+
+#[test]
+#[serial]
+fn nested_env_vars_take_precedence() {
+    reset_nested_vars();
+
+    unsafe { env::set_var("APP_SERVER__HOST", "env") };
+
+    let result = ConfigLoader::<EnvNested>::new()
+        .code("server:\n  host: code\n  maxConnections: 5", "code.yaml")
+        .unwrap()
+        .load()
+        .unwrap();
+
+    assert_eq!(result.config.server.host, "env");
+    assert_eq!(result.config.server.max_connections, 5);
+
+    reset_nested_vars();
+}
+
+#[test]
+#[serial]
+#[should_panic(expected = "Invalid environment variable APP_SERVER__MAX_CONNECTIONS.")]
+fn errors_on_nested_parse_fail() {
+    reset_nested_vars();
+
+    unsafe { env::set_var("APP_SERVER__MAX_CONNECTIONS", "abc") };
+
+    ConfigLoader::<EnvNested>::new().load().unwrap();
+}
+
+#[test]
+fn loads_from_memory_provider() {
+    let env = MemoryEnv::from_iter([
+        ("ENV_STRING", "foo"),
+        ("ENV_NUMBER", "123"),
+        ("ENV_BOOL", "true"),
+    ]);
+
+    let result = ConfigLoader::<EnvVars>::new()
+        .set_env_provider(env)
+        .load()
+        .unwrap();
+
+    assert!(result.config.boolean);
+    assert_eq!(result.config.string, "foo");
+    assert_eq!(result.config.number, 123);
+}
+
+#[test]
+fn loads_nested_from_memory_provider() {
+    let env = MemoryEnv::from_iter([
+        ("ENV_STRING", "foo"),
+        ("APP_SERVER__HOST", "localhost"),
+        ("APP_WORKERS__0__NAME", "first"),
+    ]);
+
+    let nested = PartialEnvVarsBase::env_values_with(&(), &env)
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(nested.nested.unwrap().string.unwrap(), "foo");
+
+    let partial = PartialEnvNested::default()
+        .finalize_with_env(&(), &env)
+        .unwrap();
+
+    assert_eq!(partial.server.unwrap().host.unwrap(), "localhost");
+    assert_eq!(partial.workers.unwrap()[0].name.as_deref(), Some("first"));
+}
+
+#[test]
+#[should_panic(expected = "Invalid environment variable ENV_NUMBER.")]
+fn errors_on_memory_provider_parse_fail() {
+    ConfigLoader::<EnvVars>::new()
+        .set_env_provider(MemoryEnv::from_iter([("ENV_NUMBER", "abc")]))
+        .load()
+        .unwrap();
+}
+
+#[derive(Debug, Config)]
+#[config(env_prefix = "APP_", env_nested_delimiter = "__")]
+pub struct EnvNestedSimilar {
+    #[setting(nested)]
+    foo_bar: EnvNestedWorker,
+    #[setting(nested)]
+    foobar: EnvNestedWorker,
+}
+
+#[test]
+fn nested_env_vars_match_names_by_segment() {
+    let env = MemoryEnv::from_iter([
+        ("APP_FOO_BAR__NAME", "separated"),
+        ("APP_FOOBAR__NAME", "joined"),
+    ]);
+
+    let result = ConfigLoader::<EnvNestedSimilar>::new()
+        .set_env_provider(env)
+        .load()
+        .unwrap();
+
+    assert_eq!(result.config.foo_bar.name, "separated");
+    assert_eq!(result.config.foobar.name, "joined");
+}
+
+#[derive(Default)]
+pub struct EnvMergeContext {
+    separator: String,
+}
+
+fn join_with_context(
+    prev: Vec<String>,
+    next: Vec<String>,
+    context: &EnvMergeContext,
+) -> MergeResult<Vec<String>> {
+    Ok(Some(vec![format!(
+        "{}{}{}",
+        prev.join(""),
+        context.separator,
+        next.join("")
+    )]))
+}
+
+#[derive(Debug, Config)]
+#[config(context = EnvMergeContext, env_prefix = "APP_", env_nested_delimiter = "__")]
+pub struct EnvNestedContext {
+    #[setting(env = "APP_TAGS", parse_env = schematic::env::split_comma, merge = join_with_context)]
+    tags: Vec<String>,
+}
+
+#[test]
+fn nested_env_vars_merge_with_context() {
+    let env = MemoryEnv::from_iter([("APP_TAGS", "a"), ("APP_TAGS__0", "b")]);
+
+    let context = EnvMergeContext {
+        separator: "+".into(),
+    };
+
+    let partial = PartialEnvNestedContext::env_values_with(&context, &env)
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(partial.tags.unwrap(), vec!["b+a"]);
+}
+
 #[cfg(feature = "renderer_json_schema")]
 #[test]
 fn generates_json_schema() {
@@ -166,3 +166,101 @@ mod yaml {
         )
     }
 }
+
+#[cfg(all(feature = "json", feature = "yaml"))]
+mod aggregate {
+    use super::*;
+
+    fn validate_positive<D, C>(value: &usize, _: &D, _: &C, _: bool) -> Result<(), ValidateError> {
+        if *value == 0 {
+            return Err(ValidateError::new("must be positive"));
+        }
+
+        Ok(())
+    }
+
+    #[derive(Debug, Config)]
+    pub struct ValidatedConfig {
+        setting: bool,
+        #[setting(validate = validate_positive)]
+        count: usize,
+    }
+
+    #[test]
+    fn fails_on_first_by_default() {
+        let error = ConfigLoader::<BaseConfig>::new()
+            .code(r#"{ "setting": 123 }"#, "code.json")
+            .unwrap()
+            .code("setting: 456", "code.yaml")
+            .unwrap()
+            .load()
+            .err()
+            .unwrap();
+
+        assert!(matches!(error, ConfigError::Parser { .. }));
+    }
+
+    #[test]
+    fn collects_errors_from_all_sources() {
+        let error = ConfigLoader::<BaseConfig>::new()
+            .set_aggregate_errors(true)
+            .code(r#"{ "setting": 123 }"#, "code.json")
+            .unwrap()
+            .code("setting: true", "code.yaml")
+            .unwrap()
+            .code("nested:\n  setting: 456", "code.yaml")
+            .unwrap()
+            .load()
+            .err()
+            .unwrap();
+
+        let ConfigError::Multiple { errors, .. } = &error else {
+            panic!("Expected multiple errors");
+        };
+
+        assert_eq!(errors.len(), 2);
+        assert!(
+            errors
+                .iter()
+                .all(|error| matches!(error, ConfigError::Parser { .. }))
+        );
+
+        assert_eq!(
+            strip_ansi(&error.to_full_string()),
+            "Failed to load BaseConfig, encountered 2 errors.\nFailed to parse BaseConfig. setting: invalid type: integer `123`, expected a boolean at line 1 column 16\nFailed to parse BaseConfig. nested.setting: invalid type: integer `456`, expected a boolean"
+        );
+    }
+
+    #[test]
+    fn collects_parse_and_validate_errors() {
+        let error = ConfigLoader::<ValidatedConfig>::new()
+            .set_aggregate_errors(true)
+            .code(r#"{ "count": 0 }"#, "code.json")
+            .unwrap()
+            .code("setting: 456", "code.yaml")
+            .unwrap()
+            .load()
+            .err()
+            .unwrap();
+
+        let ConfigError::Multiple { errors, .. } = &error else {
+            panic!("Expected multiple errors");
+        };
+
+        assert_eq!(errors.len(), 2);
+        assert!(matches!(errors[0], ConfigError::Validator { .. }));
+        assert!(matches!(errors[1], ConfigError::Parser { .. }));
+    }
+
+    #[test]
+    fn loads_when_no_errors() {
+        let result = ConfigLoader::<BaseConfig>::new()
+            .set_aggregate_errors(true)
+            .code(r#"{ "setting": true }"#, "code.json")
+            .unwrap()
+            .load()
+            .unwrap();
+
+        assert!(result.config.setting);
+    }
+}
@@ -103,6 +103,35 @@ fn can_create_file_source_with_parent() {
     );
 }
 
+#[test]
+fn can_create_file_source_with_code_parent() {
+    let parent = Source::Code {
+        path: PathBuf::from("/root/config.yml"),
+        code: String::new(),
+    };
+
+    let source = Source::new("./some/path/config.yml", Some(&parent)).unwrap();
+
+    assert_eq!(
+        source,
+        Source::File {
+            path: PathBuf::from("/root/some/path/config.yml"),
+            required: true,
+        }
+    );
+
+    // Relative code paths are not backed by a file
+    let parent = Source::Code {
+        path: PathBuf::from("config.yml"),
+        code: String::new(),
+    };
+
+    assert!(matches!(
+        Source::new("./some/path/config.yml", Some(&parent)),
+        Err(ConfigError::ExtendsFromParentFileOnly)
+    ));
+}
+
 #[cfg(feature = "json")]
 #[test]
 fn loads_json_files() {
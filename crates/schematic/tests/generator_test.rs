@@ -146,6 +146,18 @@ struct TemplateConfig {
     empty_object: HashMap<String, usize>,
 }
 
+#[derive(Clone, Config)]
+struct ConstraintsConfig {
+    #[setting(validate(length(min = 3, max = 10), pattern = "^[a-z]+$"))]
+    name: String,
+    #[setting(default = 8080, validate(range(min = 1, max = 65535)))]
+    port: usize,
+    #[setting(validate(range(min = 0.5, max = 1.0)))]
+    ratio: Option<f64>,
+    #[setting(validate(length(min = 1)))]
+    tags: Vec<String>,
+}
+
 fn create_generator() -> SchemaGenerator {
     let mut generator = SchemaGenerator::default();
     generator.add::<GenConfig>();
@@ -158,6 +170,56 @@ fn create_template_generator() -> SchemaGenerator {
     generator
 }
 
+#[cfg(feature = "importer_json_schema")]
+fn create_imported_generator() -> SchemaGenerator {
+    let result = schematic::schema::JsonSchemaImporter::default()
+        .import(&serde_json::json!({
+            "title": "imported",
+            "type": "object",
+            "properties": {
+                "$schema": { "type": "string" },
+                "type": { "enum": ["app", "library", "3rd-party"], "default": "app" },
+                "self": { "type": "boolean", "default": true },
+                "kebab-case": { "type": "integer", "minimum": 0, "default": 3 },
+                "snake_case": { "type": "number", "default": 1 },
+                "api_url": { "type": "string" },
+                "mode": { "anyOf": [{ "const": "fast" }, { "const": "slow" }, { "type": "null" }] },
+                "port": { "type": ["integer", "null"] },
+                "level": { "enum": [1, 2, 3] },
+                "point": { "prefixItems": [{ "type": "number" }, { "type": "string" }] },
+                "server": {
+                    "description": "Inline server settings.",
+                    "type": "object",
+                    "properties": { "host": { "type": "string", "default": "localhost" } },
+                },
+                "projects": {
+                    "anyOf": [
+                        { "type": "array", "items": { "type": "string" } },
+                        { "$ref": "#/definitions/project" },
+                        { "type": "object", "additionalProperties": { "$ref": "#/definitions/project" } },
+                    ],
+                },
+            },
+            "required": ["$schema"],
+            "definitions": {
+                "project": {
+                    "description": "A project.\n\nWith multiple lines.",
+                    "type": "object",
+                    "properties": { "root": { "type": "string", "deprecated": true } },
+                },
+            },
+        }))
+        .unwrap();
+
+    let mut generator = SchemaGenerator::default();
+
+    for schema in result.schemas.values() {
+        generator.add_schema(schema);
+    }
+
+    generator
+}
+
 fn create_template_options() -> TemplateOptions {
     TemplateOptions {
         comment_fields: vec!["float32".into(), "map".into()],
@@ -172,6 +234,183 @@ fn create_template_options() -> TemplateOptions {
     }
 }
 
+#[cfg(feature = "renderer_env_vars")]
+mod env_vars {
+    use super::*;
+    use schematic::schema::env_vars::*;
+
+    /// Server settings.
+    #[derive(Clone, Debug, Config)]
+    #[config(env_prefix = "APP_SERVER_")]
+    struct EnvServerConfig {
+        /// Host to bind to.
+        #[setting(default = "localhost")]
+        host: String,
+        /// Port to listen on.
+        #[setting(default = 8080)]
+        port: usize,
+    }
+
+    #[derive(Clone, Config)]
+    #[config(env_prefix = "APP_")]
+    struct EnvConfig {
+        /// Enable debug logging.
+        #[setting(default = true)]
+        debug: bool,
+        /// The level of logging.
+        log_level: BasicEnum,
+        /// Hosts that can connect,
+        /// separated by commas.
+        #[setting(parse_env = schematic::env::split_comma)]
+        allowed_hosts: Vec<String>,
+        /// Timeout in seconds.
+        #[deprecated = "Use `server.timeout` instead."]
+        #[setting(default = 1.5)]
+        timeout: f64,
+        /// Token for authentication.
+        #[setting(secret, default = "abc")]
+        token: String,
+        #[setting(exclude)]
+        internal: String,
+        #[setting(nested)]
+        server: Option<EnvServerConfig>,
+    }
+
+    fn generate(options: EnvVarsOptions) -> String {
+        let sandbox = create_empty_sandbox();
+        let file = sandbox.path().join("env.md");
+
+        let mut generator = SchemaGenerator::default();
+        generator.add::<EnvConfig>();
+        generator
+            .generate(&file, EnvVarsRenderer::new(options))
+            .unwrap();
+
+        fs::read_to_string(file).unwrap()
+    }
+
+    #[test]
+    fn markdown() {
+        assert_snapshot!(generate(EnvVarsOptions::default()));
+    }
+
+    #[test]
+    fn dotenv() {
+        assert_snapshot!(generate(EnvVarsOptions {
+            format: EnvVarsFormat::Dotenv,
+            ..EnvVarsOptions::default()
+        }));
+    }
+
+    #[derive(Clone, Debug, Config)]
+    struct EnvWorkerConfig {
+        /// Name of the worker.
+        name: String,
+        /// Number of threads.
+        #[setting(default = 1)]
+        threads: usize,
+    }
+
+    #[derive(Clone, Config)]
+    #[config(env_prefix = "APP_", env_nested_delimiter = "__")]
+    struct EnvNestedConfig {
+        /// Command to run, like `cmd`.
+        #[setting(default = "`cmd`")]
+        command: String,
+        /// Hosts that can connect.
+        allowed_hosts: Vec<String>,
+        /// Labels to attach.
+        labels: HashMap<String, String>,
+        #[setting(nested)]
+        pool: EnvWorkerConfig,
+        #[setting(nested)]
+        workers: Vec<EnvWorkerConfig>,
+    }
+
+    fn generate_nested(options: EnvVarsOptions) -> String {
+        let sandbox = create_empty_sandbox();
+        let file = sandbox.path().join("env.md");
+
+        let mut generator = SchemaGenerator::default();
+        generator.add::<EnvNestedConfig>();
+        generator
+            .generate(&file, EnvVarsRenderer::new(options))
+            .unwrap();
+
+        fs::read_to_string(file).unwrap()
+    }
+
+    #[test]
+    fn markdown_nested() {
+        assert_snapshot!(generate_nested(EnvVarsOptions::default()));
+    }
+
+    #[test]
+    fn dotenv_nested() {
+        assert_snapshot!(generate_nested(EnvVarsOptions {
+            format: EnvVarsFormat::Dotenv,
+            ..EnvVarsOptions::default()
+        }));
+    }
+}
+
+#[cfg(feature = "renderer_go")]
+mod go {
+    use super::*;
+    use schematic::schema::go::*;
+
+    fn generate_with(generator: SchemaGenerator, options: GoOptions) -> String {
+        let sandbox = create_empty_sandbox();
+        let file = sandbox.path().join("config.go");
+
+        generator.generate(&file, GoRenderer::new(options)).unwrap();
+
+        fs::read_to_string(file).unwrap()
+    }
+
+    #[test]
+    fn defaults() {
+        assert_snapshot!(generate_with(create_generator(), GoOptions::default()));
+    }
+
+    #[test]
+    fn settings() {
+        assert_snapshot!(generate_with(
+            create_template_generator(),
+            GoOptions::default()
+        ));
+    }
+
+    #[test]
+    fn external_types() {
+        assert_snapshot!(generate_with(
+            create_generator(),
+            GoOptions {
+                exclude_references: vec!["BasicEnum".into(), "AnotherConfig".into()],
+                external_types: HashMap::from_iter([
+                    ("github.com/acme/app/types".into(), vec!["BasicEnum".into()]),
+                    (
+                        "github.com/acme/app/nested".into(),
+                        vec!["AnotherConfig".into()]
+                    ),
+                ]),
+                package_name: "settings".into(),
+                tags: vec!["json".into()],
+                ..GoOptions::default()
+            }
+        ));
+    }
+
+    #[cfg(feature = "importer_json_schema")]
+    #[test]
+    fn imported_json_schema() {
+        assert_snapshot!(generate_with(
+            create_imported_generator(),
+            GoOptions::default()
+        ));
+    }
+}
+
 #[cfg(feature = "renderer_json_schema")]
 mod json_schema {
     use super::*;
@@ -256,6 +495,320 @@ mod json_schema {
 
         assert_snapshot!(fs::read_to_string(file).unwrap());
     }
+
+    #[test]
+    fn validate_constraints() {
+        let sandbox = create_empty_sandbox();
+        let file = sandbox.path().join("schema.json");
+
+        let mut generator = SchemaGenerator::default();
+        generator.add::<ConstraintsConfig>();
+        generator
+            .generate(&file, JsonSchemaRenderer::default())
+            .unwrap();
+
+        assert_snapshot!(fs::read_to_string(file).unwrap());
+    }
+}
+
+#[cfg(feature = "renderer_markdown")]
+mod markdown {
+    use super::*;
+    use schematic::schema::markdown::*;
+
+    fn generate(generator: SchemaGenerator, options: MarkdownOptions) -> String {
+        let sandbox = create_empty_sandbox();
+        let file = sandbox.path().join("config.md");
+
+        generator
+            .generate(&file, MarkdownRenderer::new(options))
+            .unwrap();
+
+        fs::read_to_string(file).unwrap()
+    }
+
+    #[test]
+    fn defaults() {
+        assert_snapshot!(generate(
+            create_template_generator(),
+            MarkdownOptions::default()
+        ));
+    }
+
+    #[test]
+    fn expand_nested() {
+        assert_snapshot!(generate(
+            create_generator(),
+            MarkdownOptions {
+                expand_nested: true,
+                header: "<!-- Generated -->\n\n".into(),
+                ..MarkdownOptions::default()
+            }
+        ));
+    }
+
+    #[test]
+    fn constraints() {
+        let mut generator = SchemaGenerator::default();
+        generator.add::<ConstraintsConfig>();
+
+        assert_snapshot!(generate(generator, MarkdownOptions::default()));
+    }
+
+    #[test]
+    fn hide_fields() {
+        assert_snapshot!(generate(
+            create_template_generator(),
+            MarkdownOptions {
+                hide_fields: vec!["skipped".into(), "one.two.skipped".into(), "nested".into()],
+                ..MarkdownOptions::default()
+            }
+        ));
+    }
+
+    #[test]
+    fn only_fields() {
+        assert_snapshot!(generate(
+            create_template_generator(),
+            MarkdownOptions {
+                only_fields: vec!["boolean".into(), "one.two.opt".into()],
+                ..MarkdownOptions::default()
+            }
+        ));
+    }
+
+    #[cfg(feature = "importer_json_schema")]
+    #[test]
+    fn imported_json_schema() {
+        assert_snapshot!(generate(
+            create_imported_generator(),
+            MarkdownOptions::default()
+        ));
+    }
+}
+
+#[cfg(feature = "renderer_pkl")]
+mod pkl {
+    use super::*;
+    use schematic::schema::pkl::*;
+
+    fn generate_with(generator: SchemaGenerator, options: PklOptions) -> String {
+        let sandbox = create_empty_sandbox();
+        let file = sandbox.path().join("Config.pkl");
+
+        generator
+            .generate(&file, PklRenderer::new(options))
+            .unwrap();
+
+        fs::read_to_string(file).unwrap()
+    }
+
+    #[test]
+    fn defaults() {
+        assert_snapshot!(generate_with(create_generator(), PklOptions::default()));
+    }
+
+    #[test]
+    fn settings() {
+        assert_snapshot!(generate_with(
+            create_template_generator(),
+            PklOptions::default()
+        ));
+    }
+
+    #[test]
+    fn constraints() {
+        let mut generator = SchemaGenerator::default();
+        generator.add::<ConstraintsConfig>();
+
+        assert_snapshot!(generate_with(
+            generator,
+            PklOptions {
+                module_name: Some("acme.Constraints".into()),
+                ..PklOptions::default()
+            }
+        ));
+    }
+
+    #[test]
+    fn external_types() {
+        assert_snapshot!(generate_with(
+            create_generator(),
+            PklOptions {
+                exclude_references: vec!["BasicEnum".into(), "AnotherConfig".into()],
+                external_types: HashMap::from_iter([
+                    (
+                        "package://acme.com/types@1.0.0#/Types.pkl".into(),
+                        vec!["BasicEnum".into()]
+                    ),
+                    (
+                        "modulepath:/Nested.pkl".into(),
+                        vec!["AnotherConfig".into()]
+                    ),
+                ]),
+                indent_char: "    ".into(),
+                ..PklOptions::default()
+            }
+        ));
+    }
+}
+
+#[cfg(feature = "renderer_python")]
+mod python {
+    use super::*;
+    use schematic::schema::python::*;
+
+    /// A recursive tree.
+    #[derive(Clone, Config)]
+    pub struct TreeConfig {
+        /// Child nodes.
+        #[setting(nested)]
+        children: Vec<TreeConfig>,
+        #[setting(rename = "type", default = "leaf")]
+        kind: String,
+    }
+
+    #[derive(Clone, Config)]
+    pub struct AliasedConfig {
+        #[setting(alias = "oldName")]
+        new_name: String,
+        #[setting(nested)]
+        tree: Option<TreeConfig>,
+        union: Option<IndexMap<String, usize>>,
+    }
+
+    fn generate_with(generator: SchemaGenerator, options: PythonOptions) -> String {
+        let sandbox = create_empty_sandbox();
+        let file = sandbox.path().join("config.py");
+
+        generator
+            .generate(&file, PythonRenderer::new(options))
+            .unwrap();
+
+        fs::read_to_string(file).unwrap()
+    }
+
+    fn create_aliased_generator() -> SchemaGenerator {
+        let mut generator = SchemaGenerator::default();
+        generator.add::<AliasedConfig>();
+        generator
+    }
+
+    #[test]
+    fn defaults() {
+        assert_snapshot!(generate_with(create_generator(), PythonOptions::default()));
+    }
+
+    #[test]
+    fn enums() {
+        assert_snapshot!(generate_with(
+            create_template_generator(),
+            PythonOptions {
+                enum_format: PythonEnumFormat::Enum,
+                ..PythonOptions::default()
+            }
+        ));
+    }
+
+    #[test]
+    fn typed_dict() {
+        assert_snapshot!(generate_with(
+            create_template_generator(),
+            PythonOptions {
+                format: PythonFormat::TypedDict,
+                ..PythonOptions::default()
+            }
+        ));
+    }
+
+    #[test]
+    fn aliases_and_recursion() {
+        assert_snapshot!(generate_with(
+            create_aliased_generator(),
+            PythonOptions::default()
+        ));
+    }
+
+    #[test]
+    fn aliases_and_recursion_typed_dict() {
+        assert_snapshot!(generate_with(
+            create_aliased_generator(),
+            PythonOptions {
+                format: PythonFormat::TypedDict,
+                indent_char: "\t".into(),
+                ..PythonOptions::default()
+            }
+        ));
+    }
+
+    #[test]
+    fn external_types() {
+        assert_snapshot!(generate_with(
+            create_generator(),
+            PythonOptions {
+                exclude_references: vec!["AnotherConfig".into()],
+                external_types: HashMap::from_iter([(
+                    "app.types".into(),
+                    vec!["AnotherConfig".into()]
+                )]),
+                ..PythonOptions::default()
+            }
+        ));
+    }
+}
+
+#[cfg(feature = "renderer_rust")]
+mod rust {
+    use super::*;
+    use schematic::schema::rust::*;
+
+    fn generate_with(generator: SchemaGenerator, options: RustOptions) -> String {
+        let sandbox = create_empty_sandbox();
+        let file = sandbox.path().join("config.rs");
+
+        generator
+            .generate(&file, RustRenderer::new(options))
+            .unwrap();
+
+        fs::read_to_string(file).unwrap()
+    }
+
+    #[test]
+    fn defaults() {
+        assert_snapshot!(generate_with(create_generator(), RustOptions::default()));
+    }
+
+    #[test]
+    fn settings() {
+        assert_snapshot!(generate_with(
+            create_template_generator(),
+            RustOptions::default()
+        ));
+    }
+
+    #[test]
+    fn exclude_refs() {
+        assert_snapshot!(generate_with(
+            create_generator(),
+            RustOptions {
+                exclude_references: vec!["BasicEnum".into(), "AnotherConfig".into()],
+                external_types: HashMap::from_iter([(
+                    "crate::types".into(),
+                    vec!["BasicEnum".into(), "AnotherConfig".into()]
+                )]),
+                indent_char: "\t".into(),
+            }
+        ));
+    }
+
+    #[cfg(feature = "importer_json_schema")]
+    #[test]
+    fn imported_json_schema() {
+        assert_snapshot!(generate_with(
+            create_imported_generator(),
+            RustOptions::default()
+        ));
+    }
 }
 
 #[cfg(all(feature = "renderer_template", feature = "json"))]
@@ -537,3 +1090,96 @@ mod typescript {
         }));
     }
 }
+
+#[cfg(feature = "renderer_zod")]
+mod zod {
+    use super::*;
+    use schematic::schema::zod::*;
+
+    /// A recursive node.
+    #[derive(Clone, Config)]
+    pub struct NodeConfig {
+        /// Child nodes.
+        #[setting(nested)]
+        children: Vec<NodeConfig>,
+        #[setting(default = "root")]
+        name: String,
+    }
+
+    #[derive(Clone, Config)]
+    #[config(serde(tag = "kind", content = "value"))]
+    pub enum SourceConfig {
+        Path(String),
+        #[setting(nested)]
+        Node(NodeConfig),
+    }
+
+    #[derive(Clone, Config)]
+    pub struct RecursiveConfig {
+        #[setting(nested)]
+        root: NodeConfig,
+        #[setting(nested)]
+        source: Option<SourceConfig>,
+    }
+
+    fn generate_with(generator: SchemaGenerator, options: ZodOptions) -> String {
+        let sandbox = create_empty_sandbox();
+        let file = sandbox.path().join("schemas.ts");
+
+        generator
+            .generate(&file, ZodRenderer::new(options))
+            .unwrap();
+
+        fs::read_to_string(file).unwrap()
+    }
+
+    #[test]
+    fn defaults() {
+        assert_snapshot!(generate_with(create_generator(), ZodOptions::default()));
+    }
+
+    #[test]
+    fn settings() {
+        assert_snapshot!(generate_with(
+            create_template_generator(),
+            ZodOptions::default()
+        ));
+    }
+
+    #[test]
+    fn constraints() {
+        let mut generator = SchemaGenerator::default();
+        generator.add::<ConstraintsConfig>();
+
+        assert_snapshot!(generate_with(generator, ZodOptions::default()));
+    }
+
+    #[test]
+    fn recursion_and_unions() {
+        let mut generator = SchemaGenerator::default();
+        generator.add::<RecursiveConfig>();
+
+        assert_snapshot!(generate_with(
+            generator,
+            ZodOptions {
+                indent_char: "  ".into(),
+                ..ZodOptions::default()
+            }
+        ));
+    }
+
+    #[test]
+    fn external_types() {
+        assert_snapshot!(generate_with(
+            create_generator(),
+            ZodOptions {
+                exclude_references: vec!["BasicEnum".into(), "AnotherConfig".into()],
+                external_types: HashMap::from_iter([(
+                    "./types".into(),
+                    vec!["BasicEnum".into(), "AnotherConfig".into()]
+                )]),
+                ..ZodOptions::default()
+            }
+        ));
+    }
+}
@@ -0,0 +1,277 @@
+#![allow(dead_code)]
+
+use schematic::*;
+use serial_test::serial;
+use starbase_sandbox::create_empty_sandbox;
+use std::collections::HashMap;
+use std::env;
+
+#[derive(Debug, Config)]
+pub struct ServerConfig {
+    #[setting(default = "localhost")]
+    host: String,
+    #[setting(default = 8080)]
+    port: usize,
+}
+
+#[derive(Config)]
+pub struct InterpolateConfig {
+    #[setting(nested)]
+    server: ServerConfig,
+    url: String,
+    name: String,
+    #[setting(interpolate = false)]
+    raw: String,
+    list: Vec<String>,
+    map: HashMap<String, String>,
+    #[setting(secret)]
+    token: Option<String>,
+}
+
+/// Only accepts digits, to fail after interpolation.
+#[derive(Clone, Debug, Default, PartialEq, serde::Serialize)]
+#[serde(transparent)]
+pub struct Digits(String);
+
+impl<'de> serde::Deserialize<'de> for Digits {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let value = String::deserialize(deserializer)?;
+
+        if value.contains("${") || value.chars().all(|c| c.is_ascii_digit()) {
+            Ok(Self(value))
+        } else {
+            Err(serde::de::Error::custom("expected digits"))
+        }
+    }
+}
+
+impl Schematic for Digits {}
+
+#[derive(Debug, Config)]
+pub struct DigitsInnerConfig {
+    code: Digits,
+}
+
+#[derive(Debug, Config)]
+pub struct DigitsConfig {
+    name: String,
+    #[setting(nested)]
+    inner: DigitsInnerConfig,
+}
+
+#[derive(Debug, Config)]
+pub struct CycleConfig {
+    a: String,
+    b: String,
+    c: String,
+}
+
+#[cfg(feature = "yaml")]
+mod yaml {
+    use super::*;
+
+    fn load<T: Config>(code: &str) -> Result<T, ConfigError> {
+        ConfigLoader::<T>::new()
+            .set_interpolate(true)
+            .code(code, "code.yml")?
+            .load()
+            .map(|result| result.config)
+    }
+
+    #[test]
+    fn does_nothing_when_disabled() {
+        let config = ConfigLoader::<InterpolateConfig>::new()
+            .code("name: ${server.host}", "code.yml")
+            .unwrap()
+            .load()
+            .unwrap()
+            .config;
+
+        assert_eq!(config.name, "${server.host}");
+    }
+
+    #[test]
+    fn resolves_references() {
+        let config = load::<InterpolateConfig>(
+            r"
+server:
+  host: example.com
+url: https://${server.host}:${server.port}/api
+list:
+  - ${server.host}
+  - ${list[0]}/nested
+map:
+  key: ${url}
+",
+        )
+        .unwrap();
+
+        assert_eq!(config.url, "https://example.com:8080/api");
+        assert_eq!(config.list, vec!["example.com", "example.com/nested"]);
+        assert_eq!(
+            config.map.get("key").unwrap(),
+            "https://example.com:8080/api"
+        );
+    }
+
+    #[test]
+    fn resolves_references_to_defaults() {
+        let config = load::<InterpolateConfig>("url: http://${server.host}").unwrap();
+
+        assert_eq!(config.url, "http://localhost");
+    }
+
+    #[test]
+    #[serial]
+    fn resolves_env_vars() {
+        unsafe { env::set_var("INTERPOLATE_NAME", "from-env") };
+
+        let config = load::<InterpolateConfig>("name: ${env:INTERPOLATE_NAME}").unwrap();
+
+        unsafe { env::remove_var("INTERPOLATE_NAME") };
+
+        assert_eq!(config.name, "from-env");
+    }
+
+    #[test]
+    fn resolves_env_vars_from_provider() {
+        let config = ConfigLoader::<InterpolateConfig>::new()
+            .code("name: ${env:INTERPOLATE_PROVIDED}", "code.yml")
+            .unwrap()
+            .set_env_provider(MemoryEnv::from_iter([(
+                "INTERPOLATE_PROVIDED",
+                "from-provider",
+            )]))
+            .set_interpolate(true)
+            .load()
+            .unwrap()
+            .config;
+
+        assert_eq!(config.name, "from-provider");
+    }
+
+    #[test]
+    fn errors_for_missing_env_var() {
+        let error = load::<InterpolateConfig>("name: ${env:INTERPOLATE_MISSING}").unwrap_err();
+
+        assert!(matches!(error, ConfigError::InterpolateFailed { .. }));
+        assert!(
+            error
+                .to_string()
+                .contains("Environment variable INTERPOLATE_MISSING is not set.")
+        );
+    }
+
+    #[test]
+    fn resolves_files() {
+        let sandbox = create_empty_sandbox();
+        sandbox.create_file("secret.txt", "hunter2\n");
+
+        let config = ConfigLoader::<InterpolateConfig>::new()
+            .set_interpolate(true)
+            .set_root(sandbox.path())
+            .code("token: ${file:secret.txt}", "code.yml")
+            .unwrap()
+            .load()
+            .unwrap()
+            .config;
+
+        assert_eq!(config.token.unwrap(), "hunter2");
+    }
+
+    #[test]
+    fn resolves_files_relative_to_source() {
+        let sandbox = create_empty_sandbox();
+        sandbox.create_file("secret.txt", "wrong");
+        sandbox.create_file("configs/secret.txt", "hunter2");
+        sandbox.create_file("configs/app.yml", "token: ${file:secret.txt}");
+
+        let config = ConfigLoader::<InterpolateConfig>::new()
+            .set_interpolate(true)
+            .set_root(sandbox.path())
+            .file(sandbox.path().join("configs/app.yml"))
+            .unwrap()
+            .load()
+            .unwrap()
+            .config;
+
+        assert_eq!(config.token.unwrap(), "hunter2");
+    }
+
+    #[test]
+    fn resolves_partials() {
+        let partial = ConfigLoader::<InterpolateConfig>::new()
+            .set_interpolate(true)
+            .code("name: ${server.host}", "code.yml")
+            .unwrap()
+            .load_partial(&())
+            .unwrap();
+
+        assert_eq!(partial.name.unwrap(), "localhost");
+    }
+
+    #[test]
+    fn supports_escaping() {
+        let config = load::<InterpolateConfig>("name: $${server.host} costs $5").unwrap();
+
+        assert_eq!(config.name, "${server.host} costs $5");
+    }
+
+    #[test]
+    fn can_opt_out() {
+        let config = load::<InterpolateConfig>("raw: ${server.host}").unwrap();
+
+        assert_eq!(config.raw, "${server.host}");
+    }
+
+    #[test]
+    fn can_opt_out_when_referenced() {
+        let config = load::<InterpolateConfig>("raw: ${server.host}\nname: ${raw}").unwrap();
+
+        assert_eq!(config.raw, "${server.host}");
+        assert_eq!(config.name, "${server.host}");
+    }
+
+    #[test]
+    fn errors_with_path_when_deserializing() {
+        let error = load::<DigitsConfig>("name: abc\ninner:\n  code: ${name}").unwrap_err();
+
+        let ConfigError::InterpolateFailed { path, message } = &error else {
+            panic!("Expected interpolate error");
+        };
+
+        assert_eq!(path.to_string(), "inner.code");
+        assert_eq!(message, "expected digits");
+    }
+
+    #[test]
+    fn errors_for_unknown_reference() {
+        let error = load::<InterpolateConfig>("name: ${server.unknown}").unwrap_err();
+
+        let ConfigError::InterpolateUnknownReference { path, reference } = &error else {
+            panic!("Expected unknown reference error");
+        };
+
+        assert_eq!(path.to_string(), "name");
+        assert_eq!(reference, "server.unknown");
+    }
+
+    #[test]
+    fn errors_for_cycles() {
+        let error = load::<CycleConfig>("a: ${b}\nb: ${c}\nc: ${a}").unwrap_err();
+
+        let ConfigError::InterpolateCycle { path, chain } = &error else {
+            panic!("Expected cycle error");
+        };
+
+        assert_eq!(path.to_string(), "c");
+        assert_eq!(chain, "a -> b -> c -> a");
+    }
+
+    #[test]
+    fn errors_for_self_reference() {
+        let error = load::<CycleConfig>("a: ${a}").unwrap_err();
+
+        assert!(matches!(error, ConfigError::InterpolateCycle { .. }));
+    }
+}
@@ -0,0 +1,485 @@
+#![cfg(feature = "importer_json_schema")]
+#![allow(dead_code)]
+
+use schematic::schema::*;
+use schematic::*;
+use serde_json::{Value, json};
+use starbase_sandbox::{assert_snapshot, create_empty_sandbox};
+use std::fs;
+
+fn import(document: Value) -> JsonSchemaImport {
+    JsonSchemaImporter::default().import(&document).unwrap()
+}
+
+fn root_fields(result: &JsonSchemaImport) -> &StructType {
+    match &result.root().unwrap().ty {
+        SchemaType::Struct(inner) => inner,
+        other => panic!("expected a struct, received {other:?}"),
+    }
+}
+
+fn unsupported(result: &JsonSchemaImport) -> Vec<String> {
+    result
+        .unsupported
+        .iter()
+        .map(|keyword| keyword.to_string())
+        .collect()
+}
+
+#[test]
+fn imports_draft07_definitions() {
+    let result = import(json!({
+        "$schema": "http://json-schema.org/draft-07/schema#",
+        "title": "app config",
+        "type": "object",
+        "properties": {
+            "server": { "$ref": "#/definitions/server-config" },
+            "level": { "$ref": "#/definitions/LogLevel" },
+        },
+        "required": ["server"],
+        "definitions": {
+            "server-config": {
+                "type": "object",
+                "properties": {
+                    "host": { "type": "string", "description": "Host name." },
+                },
+            },
+            "LogLevel": { "enum": ["info", "debug"], "default": "info" },
+        },
+    }));
+
+    assert_eq!(
+        result.schemas.keys().collect::<Vec<_>>(),
+        ["ServerConfig", "LogLevel", "AppConfig"]
+    );
+    assert!(result.unsupported.is_empty());
+
+    let root = root_fields(&result);
+
+    assert_eq!(
+        root.fields["server"].schema.ty,
+        SchemaType::Reference("ServerConfig".into())
+    );
+    assert!(!root.fields["server"].optional);
+    assert!(root.fields["level"].optional);
+
+    let SchemaType::Struct(server) = &result.schemas["ServerConfig"].ty else {
+        panic!();
+    };
+
+    assert_eq!(server.fields["host"].comment.as_deref(), Some("Host name."));
+
+    let SchemaType::Enum(level) = &result.schemas["LogLevel"].ty else {
+        panic!();
+    };
+
+    assert_eq!(
+        level.values,
+        [
+            LiteralValue::String("info".into()),
+            LiteralValue::String("debug".into())
+        ]
+    );
+    assert_eq!(level.default_index, Some(0));
+}
+
+#[test]
+fn imports_2020_12_defs_and_recursion() {
+    let result = import(json!({
+        "$schema": "https://json-schema.org/draft/2020-12/schema",
+        "$ref": "#/$defs/tree",
+        "$defs": {
+            "tree": {
+                "type": "object",
+                "properties": {
+                    "point": { "prefixItems": [{ "type": "number" }, { "type": "number" }] },
+                    "children": { "type": "array", "items": { "$ref": "#/$defs/tree" } },
+                },
+            },
+            "unused": { "type": "boolean" },
+        },
+    }));
+
+    // Root only references a definition, so the definition is the root
+    assert_eq!(
+        result.schemas.keys().collect::<Vec<_>>(),
+        ["Unused", "Tree"]
+    );
+
+    let root = root_fields(&result);
+
+    assert_eq!(
+        root.fields["children"].schema.ty,
+        SchemaType::Array(Box::new(ArrayType::new(Schema::new(
+            SchemaType::Reference("Tree".into())
+        ))))
+    );
+    assert_eq!(
+        root.fields["point"].schema.ty,
+        SchemaType::Tuple(Box::new(TupleType::new([
+            Schema::float(FloatType::new_kind(FloatKind::F64)),
+            Schema::float(FloatType::new_kind(FloatKind::F64)),
+        ])))
+    );
+}
+
+#[test]
+fn imports_unions_and_literals() {
+    let result = import(json!({
+        "type": "object",
+        "properties": {
+            "any": { "anyOf": [{ "type": "string" }, { "type": "integer" }] },
+            "one": {
+                "type": "object",
+                "oneOf": [
+                    { "properties": { "kind": { "const": "a" } } },
+                    { "properties": { "kind": { "const": "b" } } },
+                ],
+            },
+            "nullable": { "type": ["boolean", "null"], "default": true },
+            "values": { "enum": [1, 2.5, null] },
+        },
+    }));
+
+    let root = root_fields(&result);
+
+    let SchemaType::Union(any) = &root.fields["any"].schema.ty else {
+        panic!();
+    };
+
+    assert_eq!(any.operator, UnionOperator::AnyOf);
+    assert_eq!(any.variants_types.len(), 2);
+
+    // Sibling keywords apply to each variant
+    let SchemaType::Union(one) = &root.fields["one"].schema.ty else {
+        panic!();
+    };
+
+    assert_eq!(one.operator, UnionOperator::OneOf);
+
+    for (variant, kind) in one.variants_types.iter().zip(["a", "b"]) {
+        let SchemaType::Struct(inner) = &variant.ty else {
+            panic!();
+        };
+
+        assert_eq!(
+            inner.fields["kind"].schema.ty,
+            SchemaType::Literal(Box::new(LiteralType::new(LiteralValue::String(
+                kind.into()
+            ))))
+        );
+    }
+
+    let nullable = &root.fields["nullable"];
+
+    assert!(nullable.nullable);
+    assert_eq!(
+        nullable.schema.ty,
+        SchemaType::Union(Box::new(UnionType {
+            default_index: Some(0),
+            ..UnionType::new_any([Schema::boolean(BooleanType::new(true)), Schema::null()])
+        }))
+    );
+
+    assert_eq!(
+        root.fields["values"].schema.ty,
+        SchemaType::Union(Box::new(UnionType::new_any([
+            Schema::enumerable(EnumType::new([
+                LiteralValue::UInt(1),
+                LiteralValue::F64(2.5)
+            ])),
+            Schema::null()
+        ])))
+    );
+}
+
+#[test]
+fn imports_constraints() {
+    let result = import(json!({
+        "properties": {
+            "name": { "type": "string", "minLength": 3, "maxLength": 10, "pattern": "^[a-z]+$", "format": "hostname" },
+            "port": { "type": "integer", "minimum": 1, "exclusiveMaximum": 65536, "default": 8080 },
+            "ratio": { "type": "number", "minimum": 0, "maximum": 1, "exclusiveMinimum": true },
+            "tags": { "type": "array", "items": { "type": "string" }, "minItems": 1, "uniqueItems": true },
+            "env": { "type": "object", "additionalProperties": { "type": "string" }, "maxProperties": 5 },
+        },
+    }));
+
+    let root = root_fields(&result);
+
+    assert_eq!(
+        root.fields["name"].schema.ty,
+        SchemaType::String(Box::new(StringType {
+            format: Some("hostname".into()),
+            max_length: Some(10),
+            min_length: Some(3),
+            pattern: Some("^[a-z]+$".into()),
+            ..StringType::default()
+        }))
+    );
+    assert_eq!(
+        root.fields["port"].schema.ty,
+        SchemaType::Integer(Box::new(IntegerType {
+            default: Some(LiteralValue::UInt(8080)),
+            kind: IntegerKind::U64,
+            max_exclusive: Some(65536),
+            min: Some(1),
+            ..IntegerType::default()
+        }))
+    );
+    assert_eq!(
+        root.fields["ratio"].schema.ty,
+        SchemaType::Float(Box::new(FloatType {
+            kind: FloatKind::F64,
+            max: Some(1.0),
+            min: Some(0.0),
+            min_exclusive: Some(0.0),
+            ..FloatType::default()
+        }))
+    );
+    assert_eq!(
+        root.fields["tags"].schema.ty,
+        SchemaType::Array(Box::new(ArrayType {
+            min_length: Some(1),
+            unique: Some(true),
+            ..ArrayType::new(Schema::string(StringType::default()))
+        }))
+    );
+    assert_eq!(
+        root.fields["env"].schema.ty,
+        SchemaType::Object(Box::new(ObjectType {
+            max_length: Some(5),
+            ..ObjectType::new(
+                Schema::string(StringType::default()),
+                Schema::string(StringType::default())
+            )
+        }))
+    );
+}
+
+#[test]
+fn merges_all_of() {
+    let result = import(json!({
+        "allOf": [
+            { "$ref": "#/definitions/base" },
+            { "properties": { "extra": { "type": "boolean" } }, "required": ["extra"] },
+        ],
+        "definitions": {
+            "base": {
+                "type": "object",
+                "properties": { "name": { "type": "string" } },
+                "required": ["name"],
+            },
+        },
+    }));
+
+    let root = root_fields(&result);
+
+    assert_eq!(root.fields.keys().collect::<Vec<_>>(), ["extra", "name"]);
+    assert!(!root.fields["extra"].optional);
+    assert!(!root.fields["name"].optional);
+    assert!(result.unsupported.is_empty());
+}
+
+#[test]
+fn merges_reference_siblings() {
+    let result = import(json!({
+        "properties": {
+            "base": { "$ref": "#/$defs/base", "description": "Base." },
+            "named": { "$ref": "#/$defs/base", "required": ["name"] },
+            "remote": { "$ref": "https://example.com/schema.json", "minLength": 1 },
+        },
+        "$defs": {
+            "base": {
+                "type": "object",
+                "properties": { "name": { "type": "string" } },
+            },
+        },
+    }));
+
+    let root = root_fields(&result);
+
+    assert_eq!(
+        root.fields["base"].schema.ty,
+        SchemaType::Reference("Base".into())
+    );
+
+    let SchemaType::Struct(named) = &root.fields["named"].schema.ty else {
+        panic!();
+    };
+
+    assert!(!named.fields["name"].optional);
+    assert_eq!(
+        unsupported(&result),
+        [
+            "minLength at #/properties/remote",
+            "$ref at #/properties/remote"
+        ]
+    );
+}
+
+#[test]
+fn reports_unsupported_keywords() {
+    let result = import(json!({
+        "type": "object",
+        "properties": {
+            "map": { "type": "object", "patternProperties": { "^a": { "type": "string" } } },
+            "remote": { "$ref": "https://example.com/schema.json" },
+            "conditional": { "if": { "type": "string" }, "then": { "minLength": 1 } },
+            "list": { "type": "array", "items": { "type": "string" }, "default": ["a"] },
+            "never": false,
+        },
+        "additionalProperties": { "type": "string" },
+    }));
+
+    assert_eq!(
+        unsupported(&result),
+        [
+            "additionalProperties at #",
+            "patternProperties at #/properties/map",
+            "$ref at #/properties/remote",
+            "if at #/properties/conditional",
+            "then at #/properties/conditional",
+            "default at #/properties/list",
+            "false at #/properties/never",
+        ]
+    );
+}
+
+#[test]
+fn reports_struct_and_tuple_constraints() {
+    let result = import(json!({
+        "type": "object",
+        "properties": {
+            "closed": { "properties": {}, "additionalProperties": false },
+            "open": { "properties": {}, "additionalProperties": true, "propertyNames": { "pattern": "^a" } },
+            "sized": { "properties": {}, "minProperties": 1, "maxProperties": 2 },
+            "pair": { "type": "array", "prefixItems": [{ "type": "string" }, { "type": "string" }], "minItems": 2, "maxItems": 2 },
+            "partial": { "type": "array", "prefixItems": [{ "type": "string" }, { "type": "string" }], "minItems": 1, "uniqueItems": true },
+        },
+    }));
+
+    assert_eq!(
+        unsupported(&result),
+        [
+            "additionalProperties at #/properties/open",
+            "propertyNames at #/properties/open",
+            "maxProperties at #/properties/sized",
+            "minProperties at #/properties/sized",
+            "minItems at #/properties/partial",
+            "uniqueItems at #/properties/partial",
+        ]
+    );
+}
+
+#[test]
+fn errors_for_unknown_references() {
+    let error = JsonSchemaImporter::default()
+        .import(&json!({ "$ref": "#/definitions/missing" }))
+        .unwrap_err();
+
+    assert_eq!(
+        error.to_string(),
+        "Unknown reference #/definitions/missing at #."
+    );
+}
+
+#[test]
+fn supports_custom_root_name() {
+    let result = JsonSchemaImporter::new(JsonSchemaImporterOptions {
+        root_name: Some("Config".into()),
+    })
+    .import(&json!({ "title": "Other", "type": "object", "properties": {} }))
+    .unwrap();
+
+    assert_eq!(result.schemas.keys().collect::<Vec<_>>(), ["Config"]);
+
+    // Root that only references a definition
+    let result = JsonSchemaImporter::new(JsonSchemaImporterOptions {
+        root_name: Some("Config".into()),
+    })
+    .import(&json!({
+        "$ref": "#/$defs/tree",
+        "$defs": {
+            "tree": {
+                "type": "object",
+                "properties": {
+                    "children": { "type": "array", "items": { "$ref": "#/$defs/tree" } },
+                },
+            },
+        },
+    }))
+    .unwrap();
+
+    assert_eq!(result.schemas.keys().collect::<Vec<_>>(), ["Config"]);
+    assert_eq!(
+        root_fields(&result).fields["children"].schema.ty,
+        SchemaType::Array(Box::new(ArrayType::new(Schema::new(
+            SchemaType::Reference("Config".into())
+        ))))
+    );
+}
+
+#[cfg(all(feature = "renderer_json_schema", feature = "renderer_typescript"))]
+mod round_trip {
+    use super::*;
+    use std::collections::HashMap;
+
+    derive_enum!(
+        #[derive(ConfigEnum, Default)]
+        pub enum LogLevel {
+            #[default]
+            Info,
+            Debug,
+        }
+    );
+
+    #[derive(Config)]
+    pub struct ServerConfig {
+        /// Host name to bind to.
+        host: String,
+        #[setting(default = 8080, validate(range(min = 1, max = 65535)))]
+        port: u16,
+    }
+
+    #[derive(Config)]
+    pub struct AppConfig {
+        /// Name of the application.
+        #[setting(validate(length(min = 3)))]
+        name: String,
+        level: LogLevel,
+        ratio: Option<f32>,
+        tags: Vec<String>,
+        env: HashMap<String, String>,
+        #[setting(nested)]
+        server: ServerConfig,
+    }
+
+    fn generate<O>(schemas: IndexMap<String, Schema>, renderer: impl SchemaRenderer<O>) -> String {
+        let sandbox = create_empty_sandbox();
+        let file = sandbox.path().join("output");
+        let mut generator = SchemaGenerator::default();
+
+        for schema in schemas.values() {
+            generator.add_schema(schema);
+        }
+
+        generator.generate(&file, renderer).unwrap();
+
+        fs::read_to_string(file).unwrap()
+    }
+
+    #[test]
+    fn renders_imported_json_schema_as_typescript() {
+        let mut generator = SchemaGenerator::default();
+        generator.add::<AppConfig>();
+
+        let json_schema = generate(generator.schemas, JsonSchemaRenderer::default());
+        let result = import(serde_json::from_str(&json_schema).unwrap());
+
+        // References cannot contain a default value
+        assert_eq!(unsupported(&result), ["default at #/properties/level"]);
+
+        assert_snapshot!(generate(result.schemas, TypeScriptRenderer::default()));
+    }
+}
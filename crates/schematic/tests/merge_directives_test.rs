@@ -0,0 +1,288 @@
+#![allow(dead_code)]
+
+use schematic::*;
+use std::collections::BTreeMap;
+
+#[derive(Debug, Config)]
+pub struct DirectiveServer {
+    #[setting(default = "localhost")]
+    host: String,
+    #[setting(default = 8080)]
+    port: usize,
+}
+
+#[derive(Debug, Config)]
+pub struct DirectivesConfig {
+    #[setting(merge = merge::append_vec)]
+    plugins: Vec<String>,
+    tags: Vec<String>,
+    #[setting(merge = merge::merge_btreemap)]
+    aliases: BTreeMap<String, String>,
+    #[setting(default = "info")]
+    level: String,
+    #[setting(nested)]
+    server: DirectiveServer,
+}
+
+fn strip_ansi(s: &str) -> String {
+    String::from_utf8(strip_ansi_escapes::strip(s)).unwrap()
+}
+
+#[cfg(feature = "yaml")]
+mod yaml {
+    use super::*;
+
+    const BASE: &str = r"
+plugins: [a, b]
+tags: [one, two]
+aliases:
+  foo: bar
+  baz: qux
+level: debug
+server:
+  host: example.com
+  port: 3000
+";
+
+    fn load(code: &str) -> Result<ConfigLoadResult<DirectivesConfig>, ConfigError> {
+        ConfigLoader::<DirectivesConfig>::new()
+            .code(BASE, "base.yml")?
+            .code(code, "code.yml")?
+            .load()
+    }
+
+    #[test]
+    fn merges_without_directives() {
+        let result = load("plugins: [c]\ntags: [three]").unwrap();
+
+        assert_eq!(result.config.plugins, vec!["a", "b", "c"]);
+        assert_eq!(result.config.tags, vec!["three"]);
+        assert!(result.layers[1].directives.is_empty());
+    }
+
+    #[test]
+    fn replaces_with_tag() {
+        let result = load("plugins: !replace [c]").unwrap();
+
+        assert_eq!(result.config.plugins, vec!["c"]);
+        assert_eq!(
+            result.layers[1].directives[0].path,
+            vec!["plugins".to_owned()]
+        );
+    }
+
+    #[test]
+    fn resets_with_tag() {
+        let result = load("level: !reset\nserver: !reset").unwrap();
+
+        assert_eq!(result.config.level, "info");
+        assert_eq!(result.config.server.host, "localhost");
+        assert_eq!(result.config.server.port, 8080);
+    }
+
+    #[test]
+    fn resets_nested_with_tag() {
+        let result = load("server:\n  port: !reset").unwrap();
+
+        assert_eq!(result.config.server.host, "example.com");
+        assert_eq!(result.config.server.port, 8080);
+    }
+
+    #[test]
+    fn resets_with_value_using_tag() {
+        let result = load("aliases: !reset\n  new: value").unwrap();
+
+        assert_eq!(
+            result.config.aliases,
+            BTreeMap::from_iter([("new".to_owned(), "value".to_owned())])
+        );
+    }
+
+    #[test]
+    fn appends_and_prepends_with_tags() {
+        let result = load("tags: !append [three]").unwrap();
+
+        assert_eq!(result.config.tags, vec!["one", "two", "three"]);
+
+        let result = load("tags: !prepend [zero]").unwrap();
+
+        assert_eq!(result.config.tags, vec!["zero", "one", "two"]);
+    }
+
+    #[test]
+    fn removes_with_tag() {
+        let result = load("aliases: !remove [foo]\ntags: !remove [one]").unwrap();
+
+        assert_eq!(
+            result.config.aliases,
+            BTreeMap::from_iter([("baz".to_owned(), "qux".to_owned())])
+        );
+        assert_eq!(result.config.tags, vec!["two"]);
+    }
+
+    #[test]
+    fn supports_map_syntax() {
+        let result = load("plugins:\n  $merge: replace\n  value: [c]").unwrap();
+
+        assert_eq!(result.config.plugins, vec!["c"]);
+    }
+
+    #[test]
+    fn errors_for_unknown_directive() {
+        let error = load("plugins:\n  $merge: unknown").err().unwrap();
+
+        assert_eq!(
+            strip_ansi(&error.to_full_string()),
+            "Failed to parse DirectivesConfig. plugins: Unknown merge directive `unknown`, expected one of append, prepend, remove, replace, reset."
+        );
+    }
+
+    #[test]
+    fn errors_for_missing_value() {
+        let error = load("plugins: !replace").err().unwrap();
+
+        assert_eq!(
+            strip_ansi(&error.to_full_string()),
+            "Failed to parse DirectivesConfig. plugins: The `replace` merge directive requires a value."
+        );
+    }
+
+    #[test]
+    fn errors_for_non_list_value() {
+        let error = load("plugins: !append c").err().unwrap();
+
+        assert_eq!(
+            strip_ansi(&error.to_full_string()),
+            "Failed to parse DirectivesConfig. plugins: The `append` merge directive requires a list value."
+        );
+    }
+
+    #[test]
+    fn errors_within_lists() {
+        let error = load("tags: [!reset a]").err().unwrap();
+
+        assert_eq!(
+            strip_ansi(&error.to_full_string()),
+            "Failed to parse DirectivesConfig. tags: Merge directives are not supported within lists."
+        );
+    }
+
+    #[test]
+    fn errors_when_removing_from_non_list() {
+        let error = load("server:\n  host: !remove [a]").err().unwrap();
+
+        assert_eq!(
+            strip_ansi(&error.to_full_string()),
+            "Failed to parse DirectivesConfig. server.host: Unable to remove items, the inherited value is not a list or map."
+        );
+    }
+
+    #[test]
+    fn errors_with_span() {
+        let error = load("tags: [a]\nplugins: !prepend b").err().unwrap();
+
+        let ConfigError::Parser { error, .. } = error else {
+            panic!("Expected a parser error");
+        };
+
+        assert_eq!(error.span, Some((10, 7).into()));
+    }
+}
+
+#[cfg(feature = "json")]
+mod json {
+    use super::*;
+
+    const BASE: &str = r#"{
+    "plugins": ["a", "b"],
+    "tags": ["one", "two"],
+    "aliases": { "foo": "bar", "baz": "qux" }
+}"#;
+
+    fn load(code: &str) -> Result<ConfigLoadResult<DirectivesConfig>, ConfigError> {
+        ConfigLoader::<DirectivesConfig>::new()
+            .code(BASE, "base.json")?
+            .code(code, "code.json")?
+            .load()
+    }
+
+    #[test]
+    fn applies_directives() {
+        let result = load(
+            r#"{
+    // Comments are supported
+    "plugins": { "$merge": "replace", "value": ["c"] },
+    "tags": { "$merge": "append", "value": ["three"] },
+    "aliases": { "$merge": "remove", "value": ["foo"] }
+}"#,
+        )
+        .unwrap();
+
+        assert_eq!(result.config.plugins, vec!["c"]);
+        assert_eq!(result.config.tags, vec!["one", "two", "three"]);
+        assert_eq!(
+            result.config.aliases,
+            BTreeMap::from_iter([("baz".to_owned(), "qux".to_owned())])
+        );
+    }
+
+    #[test]
+    fn errors_for_unknown_field() {
+        let error = load(r#"{ "plugins": { "$merge": "replace", "values": [] } }"#)
+            .err()
+            .unwrap();
+
+        assert_eq!(
+            strip_ansi(&error.to_full_string()),
+            "Failed to parse DirectivesConfig. plugins: Unknown field `values` in merge directive, expected `$merge` or `value`."
+        );
+    }
+
+    #[test]
+    fn errors_for_invalid_type_after_stripping() {
+        let error = load(r#"{ "plugins": { "$merge": "replace", "value": [1] } }"#)
+            .err()
+            .unwrap();
+
+        let ConfigError::Parser { error, .. } = error else {
+            panic!("Expected a parser error");
+        };
+
+        assert_eq!(error.path, "plugins[0]");
+        assert_eq!(error.span, Some((3, 7).into()));
+    }
+}
+
+#[cfg(feature = "toml")]
+mod toml {
+    use super::*;
+
+    const BASE: &str = r#"
+plugins = ["a", "b"]
+tags = ["one", "two"]
+level = "debug"
+"#;
+
+    fn load(code: &str) -> Result<ConfigLoadResult<DirectivesConfig>, ConfigError> {
+        ConfigLoader::<DirectivesConfig>::new()
+            .code(BASE, "base.toml")?
+            .code(code, "code.toml")?
+            .load()
+    }
+
+    #[test]
+    fn applies_directives() {
+        let result = load(
+            r#"
+plugins = { "$merge" = "replace", value = ["c"] }
+tags = { "$merge" = "prepend", value = ["zero"] }
+level = { "$merge" = "reset" }
+"#,
+        )
+        .unwrap();
+
+        assert_eq!(result.config.plugins, vec!["c"]);
+        assert_eq!(result.config.tags, vec!["zero", "one", "two"]);
+        assert_eq!(result.config.level, "info");
+    }
+}
@@ -151,6 +151,185 @@ optNested:
     assert_eq!(result.config.opt_nested.as_ref().unwrap().other, 10);
 }
 
+#[derive(Debug, Config)]
+pub struct MergePlugin {
+    enabled: bool,
+    path: String,
+}
+
+#[derive(Debug, Config)]
+pub struct MergeDeep {
+    #[setting(nested, merge = deep)]
+    plugins: HashMap<String, MergePlugin>,
+    #[setting(nested, merge = deep)]
+    sorted_plugins: BTreeMap<String, MergePlugin>,
+    #[setting(nested, merge = deep)]
+    list: Vec<MergePlugin>,
+    #[setting(nested)]
+    replaced: HashMap<String, MergePlugin>,
+}
+
+#[test]
+fn deep_merges_nested_collections() {
+    let result = ConfigLoader::<MergeDeep>::new()
+        .code(
+            r"
+plugins:
+  foo:
+    path: foo.wasm
+  bar:
+    path: bar.wasm
+sortedPlugins:
+  foo:
+    path: foo.wasm
+list:
+  - path: a.wasm
+replaced:
+  foo:
+    path: foo.wasm
+",
+            "base.yaml",
+        )
+        .unwrap()
+        .code(
+            r"
+plugins:
+  foo:
+    enabled: true
+  baz:
+    path: baz.wasm
+sortedPlugins:
+  foo:
+    enabled: true
+list:
+  - enabled: true
+  - path: b.wasm
+replaced:
+  foo:
+    enabled: true
+",
+            "next.yaml",
+        )
+        .unwrap()
+        .load()
+        .unwrap();
+
+    let plugins = &result.config.plugins;
+
+    assert_eq!(plugins.len(), 3);
+    assert!(plugins["foo"].enabled);
+    assert_eq!(plugins["foo"].path, "foo.wasm");
+    assert_eq!(plugins["bar"].path, "bar.wasm");
+    assert_eq!(plugins["baz"].path, "baz.wasm");
+
+    assert!(result.config.sorted_plugins["foo"].enabled);
+    assert_eq!(result.config.sorted_plugins["foo"].path, "foo.wasm");
+
+    assert_eq!(result.config.list.len(), 2);
+    assert!(result.config.list[0].enabled);
+    assert_eq!(result.config.list[0].path, "a.wasm");
+    assert_eq!(result.config.list[1].path, "b.wasm");
+
+    // Without deep, the entry is replaced
+    assert!(result.config.replaced["foo"].enabled);
+    assert_eq!(result.config.replaced["foo"].path, "");
+}
+
+#[derive(Debug, Config)]
+pub struct MergeTask {
+    id: String,
+    command: String,
+    #[setting(merge = merge::append_vec)]
+    args: Vec<String>,
+}
+
+#[derive(Debug, Config)]
+pub struct MergeKeyed {
+    #[setting(nested, merge_key = "id")]
+    tasks: Vec<MergeTask>,
+}
+
+#[test]
+fn merges_nested_lists_by_key() {
+    let result = ConfigLoader::<MergeKeyed>::new()
+        .code(
+            r"
+tasks:
+  - id: build
+    command: cargo
+    args: [build]
+  - id: test
+    command: cargo
+    args: [test]
+  - id: lint
+    command: clippy
+",
+            "base.yaml",
+        )
+        .unwrap()
+        .code(
+            r"
+tasks:
+  - id: test
+    args: [--all]
+  - id: format
+    command: rustfmt
+",
+            "next.yaml",
+        )
+        .unwrap()
+        .load()
+        .unwrap();
+
+    let tasks = &result.config.tasks;
+
+    assert_eq!(
+        tasks
+            .iter()
+            .map(|task| task.id.as_str())
+            .collect::<Vec<_>>(),
+        ["build", "test", "lint", "format"]
+    );
+    assert_eq!(tasks[1].command, "cargo");
+    assert_eq!(tasks[1].args, ["test", "--all"]);
+    assert_eq!(tasks[3].command, "rustfmt");
+}
+
+#[test]
+fn removes_nested_list_items_by_key() {
+    let result = ConfigLoader::<MergeKeyed>::new()
+        .code(
+            "tasks:\n  - id: build\n    command: cargo\n  - id: lint\n    command: clippy",
+            "base.yaml",
+        )
+        .unwrap()
+        .code("tasks: !remove [lint]", "next.yaml")
+        .unwrap()
+        .code(
+            r#"{ "tasks": { "$merge": "remove", "value": ["build"] } }"#,
+            "last.json",
+        )
+        .unwrap()
+        .load()
+        .unwrap();
+
+    assert!(result.config.tasks.is_empty());
+}
+
+#[test]
+fn keeps_keys_with_bang_prefix() {
+    let result = ConfigLoader::<MergeKeyed>::new()
+        .code("tasks:\n  - id: build\n    command: cargo", "base.yaml")
+        .unwrap()
+        .code("tasks:\n  - id: '!build'\n    command: echo", "next.yaml")
+        .unwrap()
+        .load()
+        .unwrap();
+
+    assert_eq!(result.config.tasks.len(), 2);
+    assert_eq!(result.config.tasks[1].id, "!build");
+}
+
 mod helpers {
     use super::*;
 
@@ -244,4 +423,29 @@ mod helpers {
             Some(HashSet::from_iter(["a", "b", "c", "d"]))
         );
     }
+
+    #[test]
+    fn merge_nested_hashmap() {
+        let prev: HashMap<_, _> = HashMap::from_iter([(
+            "a".to_string(),
+            PartialMergePlugin {
+                enabled: Some(true),
+                path: None,
+            },
+        )]);
+        let next = HashMap::from_iter([(
+            "a".to_string(),
+            PartialMergePlugin {
+                enabled: None,
+                path: Some("a.wasm".into()),
+            },
+        )]);
+
+        let result = merge::merge_nested_hashmap(prev, next, &())
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(result["a"].enabled, Some(true));
+        assert_eq!(result["a"].path.as_deref(), Some("a.wasm"));
+    }
 }
@@ -0,0 +1,135 @@
+#![allow(dead_code)]
+
+use schematic::*;
+use starbase_sandbox::create_empty_sandbox;
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+#[derive(Debug, Config)]
+pub struct NestedPaths {
+    #[setting(relative_path)]
+    dir: PathBuf,
+}
+
+#[derive(Debug, Config)]
+pub struct RelativePaths {
+    #[setting(extend)]
+    extends: Option<String>,
+    #[setting(relative_path)]
+    path: PathBuf,
+    #[setting(relative_path)]
+    optional: Option<PathBuf>,
+    #[setting(relative_path)]
+    list: Vec<PathBuf>,
+    #[setting(relative_path)]
+    map: HashMap<String, PathBuf>,
+    unresolved: PathBuf,
+    #[setting(nested)]
+    nested: NestedPaths,
+    #[setting(nested)]
+    nested_list: Vec<NestedPaths>,
+}
+
+#[cfg(feature = "yaml")]
+mod yaml {
+    use super::*;
+
+    #[test]
+    fn resolves_against_file_directory() {
+        let sandbox = create_empty_sandbox();
+        sandbox.create_file(
+            "configs/app.yml",
+            r"
+path: ./cache
+optional: ../optional
+list: [a, b]
+map:
+  key: c
+unresolved: ./unresolved
+nested:
+  dir: nested
+nestedList:
+  - dir: item
+",
+        );
+
+        let root = sandbox.path().join("configs");
+        let config = ConfigLoader::<RelativePaths>::new()
+            .file(root.join("app.yml"))
+            .unwrap()
+            .load()
+            .unwrap()
+            .config;
+
+        assert_eq!(config.path, root.join("./cache"));
+        assert_eq!(config.optional.unwrap(), root.join("../optional"));
+        assert_eq!(config.list, vec![root.join("a"), root.join("b")]);
+        assert_eq!(config.map.get("key").unwrap(), &root.join("c"));
+        assert_eq!(config.unresolved, PathBuf::from("./unresolved"));
+        assert_eq!(config.nested.dir, root.join("nested"));
+        assert_eq!(config.nested_list[0].dir, root.join("item"));
+    }
+
+    #[test]
+    fn resolves_each_layer_against_its_own_file() {
+        let sandbox = create_empty_sandbox();
+        sandbox.create_file("shared/base.yml", "path: base\nlist: [shared]");
+        sandbox.create_file(
+            "project/app.yml",
+            "extends: ../shared/base.yml\nlist: [project]",
+        );
+
+        let config = ConfigLoader::<RelativePaths>::new()
+            .file(sandbox.path().join("project/app.yml"))
+            .unwrap()
+            .load()
+            .unwrap()
+            .config;
+
+        assert_eq!(config.path, sandbox.path().join("project/../shared/base"));
+        assert_eq!(config.list, vec![sandbox.path().join("project/project")]);
+    }
+
+    #[test]
+    fn preserves_absolute_paths() {
+        let sandbox = create_empty_sandbox();
+        let abs = sandbox.path().join("absolute");
+
+        sandbox.create_file("app.yml", format!("path: {}", abs.display()));
+
+        let config = ConfigLoader::<RelativePaths>::new()
+            .file(sandbox.path().join("app.yml"))
+            .unwrap()
+            .load()
+            .unwrap()
+            .config;
+
+        assert_eq!(config.path, abs);
+    }
+
+    #[test]
+    fn leaves_code_relative_without_base_dir() {
+        let config = ConfigLoader::<RelativePaths>::new()
+            .code("path: ./cache", "code.yml")
+            .unwrap()
+            .load()
+            .unwrap()
+            .config;
+
+        assert_eq!(config.path, PathBuf::from("./cache"));
+    }
+
+    #[test]
+    fn resolves_code_against_base_dir() {
+        let base = PathBuf::from("/base");
+        let config = ConfigLoader::<RelativePaths>::new()
+            .set_base_dir(&base)
+            .code("path: ./cache", "code.yml")
+            .unwrap()
+            .load()
+            .unwrap()
+            .config;
+
+        assert_eq!(config.path, base.join("./cache"));
+    }
+}
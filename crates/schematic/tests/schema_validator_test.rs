@@ -0,0 +1,235 @@
+#![cfg(feature = "validate_schema")]
+#![allow(dead_code)]
+
+use schematic::schema::*;
+use schematic::*;
+use serde_json::{Value, json};
+use std::collections::HashMap;
+
+derive_enum!(
+    #[derive(ConfigEnum, Default)]
+    pub enum LogLevel {
+        #[default]
+        Info,
+        Debug,
+    }
+);
+
+#[derive(Config)]
+pub struct ServerConfig {
+    host: String,
+    #[setting(default = 8080, validate(range(min = 1, max = 65535)))]
+    port: u16,
+}
+
+#[derive(Config)]
+pub struct TreeConfig {
+    name: Option<String>,
+    #[setting(nested)]
+    children: Vec<TreeConfig>,
+}
+
+#[derive(Config)]
+pub struct AppConfig {
+    #[setting(validate(length(min = 3), pattern = "^[a-z]+$"))]
+    name: String,
+    #[setting(alias = "log")]
+    level: LogLevel,
+    ratio: Option<f32>,
+    tags: Vec<String>,
+    env: HashMap<String, String>,
+    #[setting(nested)]
+    server: ServerConfig,
+    #[setting(nested)]
+    tree: Option<TreeConfig>,
+}
+
+fn validate(value: Value) -> Vec<String> {
+    let mut generator = SchemaGenerator::default();
+    generator.add::<AppConfig>();
+
+    SchemaValidator::from_generator(&generator)
+        .validate_by_name("AppConfig", &value)
+        .into_iter()
+        .map(|error| format!("{}: {}", error.path, error.message))
+        .collect()
+}
+
+#[test]
+fn passes_for_valid_document() {
+    let errors = validate(json!({
+        "name": "app",
+        "log": "debug",
+        "ratio": null,
+        "tags": ["a"],
+        "env": { "KEY": "value" },
+        "server": { "host": "localhost" },
+        "tree": { "children": [{ "name": "leaf", "children": [] }] },
+    }));
+
+    assert_eq!(errors, Vec::<String>::new());
+}
+
+#[test]
+fn errors_for_invalid_types() {
+    let errors = validate(json!({
+        "name": 123,
+        "level": true,
+        "ratio": "half",
+        "tags": [1, "b"],
+        "env": { "KEY": false },
+        "server": "localhost",
+    }));
+
+    assert_eq!(
+        errors,
+        vec![
+            "env.KEY: expected string, received boolean",
+            "level: expected one of \"info\", \"debug\", received true",
+            "name: expected string, received number",
+            "ratio: expected f32 | null, received string",
+            "server: expected object, received string",
+            "tags[0]: expected string, received number",
+        ]
+    );
+}
+
+#[test]
+fn errors_for_required_and_unknown_fields() {
+    let errors = validate(json!({
+        "name": "app",
+        "unknown": true,
+        "server": { "port": 80 },
+    }));
+
+    assert_eq!(
+        errors,
+        vec![
+            "env: this setting is required",
+            "level: this setting is required",
+            "server.host: this setting is required",
+            "tags: this setting is required",
+            "unknown: unknown field",
+        ]
+    );
+
+    let mut generator = SchemaGenerator::default();
+    generator.add::<ServerConfig>();
+
+    let validator = SchemaValidator::with_options(
+        &generator.schemas,
+        SchemaValidatorOptions {
+            allow_unknown_fields: true,
+            mark_struct_fields_required: false,
+        },
+    );
+
+    assert!(
+        validator
+            .validate_by_name("ServerConfig", &json!({ "unknown": true }))
+            .is_empty()
+    );
+}
+
+#[test]
+fn errors_for_constraints() {
+    let errors = validate(json!({
+        "name": "A",
+        "level": "info",
+        "tags": [],
+        "env": {},
+        "server": { "host": "localhost", "port": 0 },
+    }));
+
+    assert_eq!(
+        errors,
+        vec![
+            "name: length must be at least 3",
+            "name: does not match pattern /^[a-z]+$/",
+            "server.port: must be greater than or equal to 1",
+        ]
+    );
+
+    let errors = validate(json!({
+        "name": "app",
+        "level": "info",
+        "tags": [],
+        "env": {},
+        "server": { "host": "localhost", "port": -1 },
+    }));
+
+    assert_eq!(
+        errors,
+        vec![
+            "server.port: must not be negative",
+            "server.port: must be greater than or equal to 1",
+        ]
+    );
+}
+
+#[test]
+fn resolves_references() {
+    let errors = validate(json!({
+        "name": "app",
+        "level": "info",
+        "tags": [],
+        "env": {},
+        "server": { "host": "localhost" },
+        "tree": { "children": [{ "children": [{ "name": 1, "children": [] }] }] },
+    }));
+
+    assert_eq!(
+        errors,
+        vec!["tree.children[0].children[0].name: expected string | null, received number"]
+    );
+
+    let schema = Schema::new(SchemaType::Reference("Missing".into()));
+
+    assert_eq!(
+        SchemaValidator::new(&Default::default())
+            .validate(&schema, &json!(1))
+            .into_iter()
+            .map(|error| error.message)
+            .collect::<Vec<_>>(),
+        vec!["unknown schema reference `Missing`"]
+    );
+}
+
+#[test]
+fn validates_unions() {
+    let schema = Schema::union(UnionType::new_any([
+        Schema::string(StringType::default()),
+        Schema::array(ArrayType::new(Schema::string(StringType::default()))),
+    ]));
+    let references = IndexMap::default();
+    let validator = SchemaValidator::new(&references);
+
+    assert!(validator.validate(&schema, &json!("a")).is_empty());
+    assert!(validator.validate(&schema, &json!(["a"])).is_empty());
+
+    // Reports errors from the variant of the same kind
+    assert_eq!(
+        validator.validate(&schema, &json!([1]))[0].to_string(),
+        validator.validate(
+            &Schema::array(ArrayType::new(Schema::string(StringType::default()))),
+            &json!([1])
+        )[0]
+        .to_string()
+    );
+
+    assert_eq!(
+        validator.validate(&schema, &json!(1))[0].message,
+        "expected string | [string], received number"
+    );
+
+    let schema = Schema::union(UnionType::new_one([
+        Schema::integer(IntegerType::default()),
+        Schema::float(FloatType::default()),
+    ]));
+
+    assert_eq!(
+        validator.validate(&schema, &json!(1))[0].message,
+        "expected only one of usize | f32, but matched 2"
+    );
+    assert!(validator.validate(&schema, &json!(1.5)).is_empty());
+}
@@ -0,0 +1,208 @@
+#![allow(dead_code)]
+
+use schematic::*;
+
+fn validate_token<D, C>(value: &String, _: &D, _: &C, _: bool) -> ValidateResult {
+    if !value.starts_with("tok_") {
+        return Err(ValidateError::new(format!(
+            "{value} must start with a tok_ prefix"
+        )));
+    }
+
+    Ok(())
+}
+
+fn validate_pin<D, C>(value: &usize, _: &D, _: &C, _: bool) -> ValidateResult {
+    if *value < 1000 {
+        return Err(ValidateError::new(format!("{value} must be 4 digits")));
+    }
+
+    Ok(())
+}
+
+#[derive(Config)]
+pub struct SecretConfig {
+    pub username: String,
+    #[setting(secret, validate = validate_token)]
+    pub token: String,
+    #[setting(secret)]
+    pub password: Option<String>,
+}
+
+#[derive(Config)]
+pub struct SecretTupleConfig(String, #[setting(secret)] String);
+
+#[derive(Config)]
+pub enum SecretEnumConfig {
+    #[setting(default)]
+    Anonymous,
+    Username(String),
+    #[setting(secret)]
+    Token(String),
+}
+
+#[derive(Config)]
+pub struct SecretPinConfig {
+    #[setting(secret, validate = validate_pin)]
+    pub pin: usize,
+}
+
+#[test]
+fn redacts_debug_output() {
+    let partial = PartialSecretConfig {
+        username: Some("user".into()),
+        token: Some("tok_123".into()),
+        password: None,
+    };
+
+    assert_eq!(
+        format!("{partial:?}"),
+        r#"PartialSecretConfig { username: Some("user"), token: Some("[REDACTED]"), password: None }"#
+    );
+}
+
+#[test]
+fn redacts_final_debug_output() {
+    let config = SecretConfig {
+        username: "user".into(),
+        token: "tok_123".into(),
+        password: Some("hunter2".into()),
+    };
+
+    assert_eq!(
+        format!("{config:?}"),
+        r#"SecretConfig { username: "user", token: "[REDACTED]", password: "[REDACTED]" }"#
+    );
+}
+
+#[test]
+fn redacts_tuple_debug_output() {
+    let partial = PartialSecretTupleConfig(Some("user".into()), Some("hunter2".into()));
+    let config = SecretTupleConfig("user".into(), "hunter2".into());
+
+    assert_eq!(
+        format!("{partial:?}"),
+        r#"PartialSecretTupleConfig(Some("user"), Some("[REDACTED]"))"#
+    );
+    assert_eq!(
+        format!("{config:?}"),
+        r#"SecretTupleConfig("user", "[REDACTED]")"#
+    );
+}
+
+#[test]
+fn redacts_enum_debug_output() {
+    assert_eq!(
+        format!("{:?}", PartialSecretEnumConfig::Token("tok_123".into())),
+        r#"Token("[REDACTED]")"#
+    );
+    assert_eq!(
+        format!("{:?}", SecretEnumConfig::Token("tok_123".into())),
+        r#"Token("[REDACTED]")"#
+    );
+    assert_eq!(
+        format!("{:?}", SecretEnumConfig::Username("user".into())),
+        r#"Username("user")"#
+    );
+    assert_eq!(format!("{:?}", SecretEnumConfig::Anonymous), "Anonymous");
+}
+
+#[cfg(feature = "json")]
+#[test]
+fn redacts_serialized_enums() {
+    let json = serde_json::to_string(&PartialSecretEnumConfig::Token("tok_123".into())).unwrap();
+
+    assert_eq!(json, r#"{"token":"[REDACTED]"}"#);
+}
+
+#[cfg(feature = "json")]
+#[test]
+fn redacts_serialized_layers() {
+    let result = ConfigLoader::<SecretConfig>::new()
+        .code(
+            r#"{ "username": "user", "token": "tok_123", "password": "hunter2" }"#,
+            "code.json",
+        )
+        .unwrap()
+        .load()
+        .unwrap();
+
+    assert_eq!(result.config.token, "tok_123");
+    assert_eq!(result.config.password.as_deref(), Some("hunter2"));
+
+    let layer = serde_json::to_string(&result.layers[0].partial).unwrap();
+
+    assert_eq!(
+        layer,
+        r#"{"username":"user","token":"[REDACTED]","password":"[REDACTED]"}"#
+    );
+}
+
+#[cfg(feature = "json")]
+#[test]
+fn can_reveal_secrets() {
+    let partial = PartialSecretConfig {
+        token: Some("tok_123".into()),
+        ..Default::default()
+    };
+
+    let (debug, json) = reveal_secrets(|| {
+        (
+            format!("{partial:?}"),
+            serde_json::to_string(&partial).unwrap(),
+        )
+    });
+
+    assert!(debug.contains("tok_123"));
+    assert_eq!(json, r#"{"token":"tok_123"}"#);
+
+    // Only revealed for the duration of the operation
+    assert!(is_redacting_secrets());
+    assert!(!format!("{partial:?}").contains("tok_123"));
+}
+
+#[cfg(feature = "json")]
+#[test]
+fn redacts_validation_messages() {
+    let error = ConfigLoader::<SecretConfig>::new()
+        .code(r#"{ "token": "abc123" }"#, "code.json")
+        .unwrap()
+        .load()
+        .err()
+        .unwrap();
+
+    let message = error.to_full_string();
+
+    assert!(message.contains("[REDACTED] must start with a tok_ prefix"));
+    assert!(!message.contains("abc123"));
+}
+
+#[cfg(feature = "json")]
+#[test]
+fn redacts_non_string_validation_messages() {
+    let error = ConfigLoader::<SecretPinConfig>::new()
+        .code(r#"{ "pin": 123 }"#, "code.json")
+        .unwrap()
+        .load()
+        .err()
+        .unwrap();
+
+    let message = error.to_full_string();
+
+    assert!(message.contains("[REDACTED] must be 4 digits"));
+    assert!(!message.contains("123"));
+}
+
+#[cfg(feature = "schema")]
+#[test]
+fn marks_schema_fields_as_secret() {
+    let schema = SchemaBuilder::build_root::<SecretConfig>();
+
+    let SchemaType::Struct(inner) = schema.ty else {
+        panic!("Expected a struct");
+    };
+
+    assert!(!inner.fields.get("username").unwrap().secret);
+    assert!(inner.fields.get("token").unwrap().secret);
+    assert!(inner.fields.get("password").unwrap().secret);
+}
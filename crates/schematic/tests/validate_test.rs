@@ -1,7 +1,9 @@
 #![allow(dead_code)]
 
 use schematic::*;
+use starbase_sandbox::create_empty_sandbox;
 use std::collections::HashMap;
+use std::path::{Path, PathBuf};
 
 fn test_string<T, C>(value: &str, _: &T, _: &C, _: bool) -> ValidateResult {
     if value.is_empty() {
@@ -238,3 +240,530 @@ fn errors_if_required_and_empty() {
         "Failed to validate ValidateEnumRequired. \n  required: this setting is required"
     );
 }
+
+fn validate_workers(
+    config: &PartialValidateContainer,
+    _: &(),
+    _: bool,
+) -> Result<(), Vec<ValidateError>> {
+    if let (Some(min), Some(max)) = (config.min_workers, config.max_workers)
+        && min > max
+    {
+        return Err(vec![
+            ValidateError::with_segment(
+                "must be less than or equal to `max_workers`",
+                PathSegment::Key("min_workers".into()),
+            ),
+            ValidateError::with_segment(
+                "must be greater than or equal to `min_workers`",
+                PathSegment::Key("max_workers".into()),
+            ),
+        ]);
+    }
+
+    Ok(())
+}
+
+#[derive(Debug, Config)]
+pub struct ValidateTls {
+    enabled: bool,
+    cert: Option<String>,
+    key: Option<String>,
+}
+
+#[derive(Config)]
+#[config(validate(
+    validate_workers,
+    validate::required_if("tls.cert", "tls.enabled"),
+    validate::mutually_exclusive(&["token", "password"]),
+    validate::at_least_one_of(&["host", "socket_path"]),
+))]
+pub struct ValidateContainer {
+    #[setting(default = 1)]
+    min_workers: usize,
+    #[setting(default = 4)]
+    max_workers: usize,
+    #[setting(nested)]
+    tls: ValidateTls,
+    token: Option<String>,
+    #[setting(secret)]
+    password: Option<String>,
+    host: Option<String>,
+    socket_path: Option<String>,
+}
+
+#[derive(Config)]
+#[config(validate(
+    validate::required_if("cert", "tls"),
+    validate::mutually_exclusive(&["token", "password"]),
+    validate::at_least_one_of(&["host", "socket_path"]),
+))]
+pub struct ValidateContainerRequired {
+    tls: bool,
+    cert: String,
+    token: String,
+    password: String,
+    host: String,
+    socket_path: String,
+}
+
+#[derive(Config)]
+#[config(validate = validate::at_least_one_of(&["socket_path"]))]
+pub struct ValidateContainerSingle {
+    socket_path: Option<String>,
+}
+
+#[derive(Config)]
+pub struct ValidateContainerParent {
+    #[setting(nested)]
+    child: ValidateContainerSingle,
+}
+
+#[test]
+fn runs_container_validators() {
+    let result = ConfigLoader::<ValidateContainer>::new()
+        .code(
+            r#"{ "host": "localhost", "tls": { "enabled": true, "cert": "a.pem" } }"#,
+            "code.json",
+        )
+        .unwrap()
+        .load();
+
+    assert!(result.is_ok());
+}
+
+#[test]
+fn errors_for_container_validators() {
+    let error = ConfigLoader::<ValidateContainer>::new()
+        .code(
+            r#"{ "minWorkers": 8, "tls": { "enabled": true }, "token": "a", "password": "b" }"#,
+            "code.json",
+        )
+        .unwrap()
+        .load()
+        .err()
+        .unwrap();
+
+    assert_eq!(
+        error.to_full_string(),
+        "Failed to validate ValidateContainer. \n  min_workers: must be less than or equal to `max_workers`\n  max_workers: must be greater than or equal to `min_workers`\n  tls.cert: this setting is required when `tls.enabled` is enabled\n  token: only one of `token`, `password` can be set\n  password: only one of `token`, `password` can be set\n  host: at least one of `host`, `socket_path` must be set\n  socket_path: at least one of `host`, `socket_path` must be set"
+    );
+}
+
+#[test]
+fn container_validators_only_run_combinators_when_finalizing() {
+    let partial = ConfigLoader::<ValidateContainer>::new()
+        .code(r#"{ "token": "a", "password": "b" }"#, "code.json")
+        .unwrap()
+        .load_partial(&())
+        .unwrap();
+
+    assert!(partial.validate(&(), false).is_ok());
+    assert!(partial.validate(&(), true).is_err());
+}
+
+#[test]
+fn container_validators_compare_required_settings_to_defaults() {
+    let load = |code: &str| {
+        ConfigLoader::<ValidateContainerRequired>::new()
+            .code(code, "code.json")
+            .unwrap()
+            .load()
+    };
+
+    assert_eq!(
+        load(r#"{}"#).err().unwrap().to_full_string(),
+        "Failed to validate ValidateContainerRequired. \n  host: at least one of `host`, `socket_path` must be set\n  socket_path: at least one of `host`, `socket_path` must be set"
+    );
+
+    assert!(load(r#"{ "host": "localhost", "tls": true, "cert": "a.pem", "token": "a" }"#).is_ok());
+
+    assert_eq!(
+        load(r#"{ "host": "localhost", "tls": true, "token": "a", "password": "b" }"#)
+            .err()
+            .unwrap()
+            .to_full_string(),
+        "Failed to validate ValidateContainerRequired. \n  cert: this setting is required when `tls` is enabled\n  token: only one of `token`, `password` can be set\n  password: only one of `token`, `password` can be set"
+    );
+}
+
+#[test]
+fn errors_for_nested_container_validators() {
+    let error = ConfigLoader::<ValidateContainerParent>::new()
+        .code(r#"{}"#, "code.json")
+        .unwrap()
+        .load()
+        .err()
+        .unwrap();
+
+    assert_eq!(
+        error.to_full_string(),
+        "Failed to validate ValidateContainerParent. \n  child.socket_path: at least one of `socket_path` must be set"
+    );
+}
+
+#[derive(Config)]
+pub struct ValidateRules {
+    #[setting(
+        default = "abc",
+        validate(length(min = 3, max = 10), pattern = "^[a-z]+$")
+    )]
+    name: String,
+    #[setting(default = 8080, validate(range(min = 1, max = 65535)))]
+    port: usize,
+    #[setting(validate(range(min = 0.5)))]
+    ratio: Option<f64>,
+    #[setting(validate(length(max = 2), validate::not_empty))]
+    tags: Vec<String>,
+}
+
+#[test]
+fn runs_declarative_rules() {
+    let result = ConfigLoader::<ValidateRules>::new()
+        .code(
+            r#"{ "name": "schematic", "port": 3000, "ratio": 1.5, "tags": ["a"] }"#,
+            "code.json",
+        )
+        .unwrap()
+        .load();
+
+    assert!(result.is_ok());
+}
+
+#[test]
+fn errors_for_declarative_rules() {
+    let error = ConfigLoader::<ValidateRules>::new()
+        .code(
+            r#"{ "name": "AB", "port": 0, "ratio": 0.1, "tags": [] }"#,
+            "code.json",
+        )
+        .unwrap()
+        .load()
+        .err()
+        .unwrap();
+
+    assert_eq!(
+        error.to_full_string(),
+        "Failed to validate ValidateRules. \n  name: length is lower than 3\n  name: does not match pattern /^[a-z]+$/\n  port: lower than 1\n  ratio: lower than 0.5\n  tags: must not be empty"
+    );
+
+    let error = ConfigLoader::<ValidateRules>::new()
+        .code(r#"{ "tags": ["a", "b", "c"] }"#, "code.json")
+        .unwrap()
+        .load()
+        .err()
+        .unwrap();
+
+    assert_eq!(
+        error.to_full_string(),
+        "Failed to validate ValidateRules. \n  tags: length is greater than 2"
+    );
+}
+
+#[cfg(feature = "schema")]
+#[test]
+fn populates_schema_constraints_from_rules() {
+    let schema = SchemaBuilder::build_root::<ValidateRules>();
+
+    let SchemaType::Struct(inner) = schema.ty else {
+        panic!("Expected a struct");
+    };
+
+    let SchemaType::String(name) = &inner.fields.get("name").unwrap().schema.ty else {
+        panic!("Expected a string");
+    };
+
+    assert_eq!(name.min_length, Some(3));
+    assert_eq!(name.max_length, Some(10));
+    assert_eq!(name.pattern.as_deref(), Some("^[a-z]+$"));
+
+    let SchemaType::Integer(port) = &inner.fields.get("port").unwrap().schema.ty else {
+        panic!("Expected an integer");
+    };
+
+    assert_eq!(port.min, Some(1));
+    assert_eq!(port.max, Some(65535));
+
+    let SchemaType::Union(ratio) = &inner.fields.get("ratio").unwrap().schema.ty else {
+        panic!("Expected a union");
+    };
+
+    let SchemaType::Float(ratio) = &ratio.variants_types[0].ty else {
+        panic!("Expected a float");
+    };
+
+    assert_eq!(ratio.min, Some(0.5));
+    assert_eq!(ratio.max, None);
+
+    let SchemaType::Array(tags) = &inner.fields.get("tags").unwrap().schema.ty else {
+        panic!("Expected an array");
+    };
+
+    assert_eq!(tags.min_length, None);
+    assert_eq!(tags.max_length, Some(2));
+}
+
+#[derive(Default)]
+pub struct FsTestContext {
+    root: PathBuf,
+}
+
+impl validate::fs::FsContext for FsTestContext {
+    fn root_dir(&self) -> Option<&Path> {
+        Some(&self.root)
+    }
+}
+
+#[derive(Config)]
+#[config(context = FsTestContext)]
+pub struct ValidateFs {
+    #[setting(validate = validate::fs::is_file)]
+    file: Option<PathBuf>,
+    #[setting(validate = validate::fs::is_dir)]
+    dir: Option<PathBuf>,
+    #[setting(validate(validate::fs::exists, validate::fs::extension_in(&["yml", "yaml"])))]
+    config: Option<String>,
+    #[setting(validate = validate::fs::is_absolute)]
+    abs: Option<PathBuf>,
+}
+
+#[test]
+fn validates_paths_relative_to_context_root() {
+    let sandbox = create_empty_sandbox();
+    sandbox.create_file("file.txt", "");
+    sandbox.create_file("dir/config.YAML", "");
+
+    let context = FsTestContext {
+        root: sandbox.path().to_path_buf(),
+    };
+
+    let result = ConfigLoader::<ValidateFs>::new()
+        .code(
+            r#"{ "file": "file.txt", "dir": "dir", "config": "dir/config.YAML", "abs": "/root" }"#,
+            "code.json",
+        )
+        .unwrap()
+        .load_with_context(&context);
+
+    assert!(result.is_ok());
+
+    let error = ConfigLoader::<ValidateFs>::new()
+        .code(
+            r#"{ "file": "dir", "dir": "file.txt", "config": "file.txt", "abs": "file.txt" }"#,
+            "code.json",
+        )
+        .unwrap()
+        .load_with_context(&context)
+        .err()
+        .unwrap();
+
+    assert_eq!(
+        error.to_full_string(),
+        "Failed to validate ValidateFs. \n  file: path `dir` is not a file\n  dir: path `file.txt` is not a directory\n  config: path `file.txt` must have one of the extensions `.yml`, `.yaml`\n  abs: path `file.txt` must be absolute"
+    );
+
+    let error = ConfigLoader::<ValidateFs>::new()
+        .code(r#"{ "config": "missing.yml" }"#, "code.json")
+        .unwrap()
+        .load_with_context(&context)
+        .err()
+        .unwrap();
+
+    assert_eq!(
+        error.to_full_string(),
+        "Failed to validate ValidateFs. \n  config: path `missing.yml` does not exist"
+    );
+}
+
+#[derive(Config)]
+pub struct ValidateNet {
+    #[setting(validate = validate::net::hostname)]
+    host: Option<String>,
+    #[setting(validate = validate::net::port)]
+    port: Option<usize>,
+    #[setting(validate = validate::net::port_in_range(8000, 9000))]
+    dev_port: Option<u16>,
+    #[setting(validate = validate::net::socket_addr)]
+    listen: Option<String>,
+    #[setting(validate = validate::net::cidr)]
+    network: Option<String>,
+}
+
+#[test]
+fn validates_network_values() {
+    let result = ConfigLoader::<ValidateNet>::new()
+        .code(
+            r#"{ "host": "api.example-1.com", "port": 443, "devPort": 8080, "listen": "[::1]:3000", "network": "10.0.0.0/8" }"#,
+            "code.json",
+        )
+        .unwrap()
+        .load();
+
+    assert!(result.is_ok());
+
+    let error = ConfigLoader::<ValidateNet>::new()
+        .code(
+            r#"{ "host": "-bad_host", "port": 70000, "devPort": 80, "listen": "localhost:3000", "network": "10.0.0.0/33" }"#,
+            "code.json",
+        )
+        .unwrap()
+        .load()
+        .err()
+        .unwrap();
+
+    assert_eq!(
+        error.to_full_string(),
+        "Failed to validate ValidateNet. \n  host: `-bad_host` is not a valid hostname\n  port: must be a port between 1 and 65535\n  dev_port: must be a port between 8000 and 9000\n  listen: `localhost:3000` is not a valid socket address\n  network: `10.0.0.0/33` is not a valid CIDR network"
+    );
+}
+
+#[cfg(feature = "schema")]
+#[test]
+fn populates_schema_formats_from_validators() {
+    // Not a built-in validator, so no format is inferred
+    mod net {
+        pub fn hostname<D, C>(_: &str, _: &D, _: &C, _: bool) -> schematic::ValidateResult {
+            Ok(())
+        }
+    }
+
+    #[derive(Config)]
+    #[config(context = FsTestContext)]
+    pub struct ValidateFormats {
+        #[setting(validate = schematic::validate::fs::is_file)]
+        file: PathBuf,
+        #[setting(validate(schematic::validate::fs::is_dir))]
+        dir: Option<PathBuf>,
+        #[setting(validate = ::schematic::validate::net::port)]
+        port: u16,
+        #[setting(validate(schematic::validate::net::hostname, format = "host"))]
+        host: String,
+        #[setting(validate = validate::net::hostname)]
+        imported: String,
+        #[setting(validate = net::hostname)]
+        custom: String,
+    }
+
+    let schema = SchemaBuilder::build_root::<ValidateFormats>();
+
+    let SchemaType::Struct(inner) = schema.ty else {
+        panic!("Expected a struct");
+    };
+
+    let format = |name: &str| match &inner.fields.get(name).unwrap().schema.ty {
+        SchemaType::Integer(inner) => inner.format.clone(),
+        SchemaType::String(inner) => inner.format.clone(),
+        SchemaType::Union(inner) => match &inner.variants_types[0].ty {
+            SchemaType::String(inner) => inner.format.clone(),
+            _ => None,
+        },
+        _ => None,
+    };
+
+    assert_eq!(format("file").as_deref(), Some("file-path"));
+    assert_eq!(format("dir").as_deref(), Some("dir-path"));
+    assert_eq!(format("port").as_deref(), Some("port"));
+    assert_eq!(format("host").as_deref(), Some("host"));
+    assert_eq!(format("imported"), None);
+    assert_eq!(format("custom"), None);
+}
+
+fn warn_timeout<D, C>(value: &usize, _: &D, _: &C, _: bool) -> ValidateResult {
+    if *value > 60 {
+        return Err(ValidateError::warning("timeout is suspiciously high"));
+    }
+
+    Ok(())
+}
+
+#[derive(Config)]
+pub struct ValidateWarningsNested {
+    #[setting(validate = warn_timeout)]
+    timeout: usize,
+}
+
+#[derive(Config)]
+pub struct ValidateWarnings {
+    #[setting(validate = warn_timeout)]
+    timeout: usize,
+    #[setting(nested)]
+    nested: ValidateWarningsNested,
+    #[setting(validate = test_string)]
+    name: String,
+}
+
+#[test]
+fn collects_warnings_without_failing() {
+    let result = ConfigLoader::<ValidateWarnings>::new()
+        .code(r#"{ "timeout": 100 }"#, "one.json")
+        .unwrap()
+        .code(r#"{ "nested": { "timeout": 120 } }"#, "two.json")
+        .unwrap()
+        .load()
+        .unwrap();
+
+    assert_eq!(result.config.timeout, 100);
+    assert_eq!(result.warnings.len(), 2);
+    assert_eq!(result.warnings[0].path.to_string(), "timeout");
+    assert_eq!(result.warnings[1].path.to_string(), "nested.timeout");
+    assert_eq!(result.warnings[1].message, "timeout is suspiciously high");
+    assert_eq!(
+        miette::Diagnostic::severity(&result.warnings[0]),
+        Some(miette::Severity::Warning)
+    );
+}
+
+#[test]
+fn doesnt_include_warnings_when_failing() {
+    let error = ConfigLoader::<ValidateWarnings>::new()
+        .code(r#"{ "timeout": 100, "name": "abc" }"#, "code.json")
+        .unwrap()
+        .load()
+        .err()
+        .unwrap();
+
+    assert_eq!(
+        error.to_full_string(),
+        "Failed to validate ValidateWarnings. \n  name: invalid string"
+    );
+}
+
+#[test]
+fn promotes_warnings_to_errors() {
+    let error = ConfigLoader::<ValidateWarnings>::new()
+        .set_warnings_as_errors(true)
+        .code(r#"{ "timeout": 100 }"#, "code.json")
+        .unwrap()
+        .load()
+        .err()
+        .unwrap();
+
+    assert_eq!(
+        error.to_full_string(),
+        "Failed to validate ValidateWarnings. \n  timeout: timeout is suspiciously high"
+    );
+
+    let ConfigError::Validator { error, .. } = error else {
+        panic!("Expected a validator error");
+    };
+
+    assert_eq!(error.errors[0].severity, ValidateSeverity::Error);
+}
+
+#[test]
+fn partial_validate_ignores_warnings() {
+    let partial = PartialValidateWarnings {
+        timeout: Some(100),
+        ..Default::default()
+    };
+
+    assert!(partial.validate(&(), true).is_ok());
+    assert_eq!(
+        partial
+            .validate_with_warnings(&(), true, false)
+            .unwrap()
+            .len(),
+        1
+    );
+    assert!(partial.validate_with_warnings(&(), true, true).is_err());
+}
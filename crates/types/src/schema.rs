@@ -294,6 +294,9 @@ pub struct SchemaField {
     #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "is_false"))]
     pub read_only: bool,
 
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "is_false"))]
+    pub secret: bool,
+
     #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "is_false"))]
     pub write_only: bool,
 }
@@ -113,6 +113,90 @@ impl SchemaType {
         };
     }
 
+    /// Set the `min_length` and `max_length` constraints of the inner schema type.
+    /// Applies to arrays, objects, and strings, and the non-null variants of a union.
+    pub fn set_length_constraints(&mut self, min: Option<usize>, max: Option<usize>) {
+        match self {
+            SchemaType::Array(inner) => {
+                inner.min_length = min.or(inner.min_length);
+                inner.max_length = max.or(inner.max_length);
+            }
+            SchemaType::Object(inner) => {
+                inner.min_length = min.or(inner.min_length);
+                inner.max_length = max.or(inner.max_length);
+            }
+            SchemaType::String(inner) => {
+                inner.min_length = min.or(inner.min_length);
+                inner.max_length = max.or(inner.max_length);
+            }
+            SchemaType::Union(inner) => {
+                for item in inner.variants_types.iter_mut() {
+                    item.set_length_constraints(min, max);
+                }
+            }
+            _ => {}
+        };
+    }
+
+    /// Set the `min` and `max` constraints of the inner schema type.
+    /// Applies to floats and integers, and the non-null variants of a union.
+    pub fn set_range_constraints(&mut self, min: Option<f64>, max: Option<f64>) {
+        match self {
+            SchemaType::Float(inner) => {
+                inner.min = min.or(inner.min);
+                inner.max = max.or(inner.max);
+            }
+            SchemaType::Integer(inner) => {
+                inner.min = min.map(|value| value as isize).or(inner.min);
+                inner.max = max.map(|value| value as isize).or(inner.max);
+            }
+            SchemaType::Union(inner) => {
+                for item in inner.variants_types.iter_mut() {
+                    item.set_range_constraints(min, max);
+                }
+            }
+            _ => {}
+        };
+    }
+
+    /// Set the `pattern` constraint of the inner schema type.
+    /// Applies to strings, and the non-null variants of a union.
+    pub fn set_pattern_constraint(&mut self, pattern: impl AsRef<str>) {
+        match self {
+            SchemaType::String(inner) => {
+                inner.pattern = Some(pattern.as_ref().to_owned());
+            }
+            SchemaType::Union(inner) => {
+                for item in inner.variants_types.iter_mut() {
+                    item.set_pattern_constraint(pattern.as_ref());
+                }
+            }
+            _ => {}
+        };
+    }
+
+    /// Set the `format` of the inner schema type.
+    /// Applies to floats, integers, and strings, and the non-null variants of a union.
+    pub fn set_format(&mut self, format: impl AsRef<str>) {
+        match self {
+            SchemaType::Float(inner) => {
+                inner.format = Some(format.as_ref().to_owned());
+            }
+            SchemaType::Integer(inner) => {
+                inner.format = Some(format.as_ref().to_owned());
+            }
+            SchemaType::String(inner) => {
+                inner.format = Some(format.as_ref().to_owned());
+            }
+            SchemaType::Union(inner) => {
+                for item in inner.variants_types.iter_mut() {
+                    item.set_format(format.as_ref());
+                }
+            }
+            _ => {}
+        };
+    }
+
     /// Add a field to the type if it's a struct.
     pub fn add_field(&mut self, key: &str, value: impl Into<SchemaField>) {
         if let SchemaType::Struct(map) = self {
@@ -5,6 +5,21 @@ use std::fmt;
 #[derive(Clone, Debug, Default, PartialEq)]
 #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub struct StructType {
+    // Delimiter for mapping environment variables to nested settings,
+    // from `#[config(env_nested_delimiter)]`.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub env_nested_delimiter: Option<String>,
+
+    // Prefix of environment variables that map to nested settings.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub env_prefix: Option<String>,
+
     pub fields: BTreeMap<String, Box<SchemaField>>,
 
     // The type is a partial nested config, like `PartialConfig`.
@@ -299,7 +299,8 @@ fn supports_cycles() {
                 })
             ),]),
             partial: false,
-            required: None
+            required: None,
+            ..Default::default()
         }))
     );
 }